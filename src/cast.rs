@@ -1,5 +1,5 @@
 use crate::{complex, Complex};
-use num_traits::{AsPrimitive, FromPrimitive, Num, NumCast, ToPrimitive};
+use num_traits::{AsPrimitive, Float, FromPrimitive, Num, NumCast, ToPrimitive};
 
 macro_rules! impl_toprim {
     ($ty:ty, $tofn:ident) => {
@@ -64,6 +64,21 @@ impl<T: NumCast + Num> NumCast for Complex<T> {
     }
 }
 
+/// Real floats as complex numbers with zero imaginary part
+macro_rules! impl_from_real {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Complex<$t> {
+                fn from(real: $t) -> Self {
+                    complex(real, 0.0)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_real!(f32, f64);
+
 impl<T, U> AsPrimitive<U> for Complex<T>
 where
     T: AsPrimitive<U>,
@@ -73,3 +88,8 @@ where
         self.real.as_()
     }
 }
+
+/// Converts an `f64` constant into the float type `T`
+pub(crate) fn from_f64<T: Float>(x: f64) -> T {
+    T::from(x).expect("NaN")
+}
@@ -61,7 +61,7 @@ impl<T: Num + Clone> Neg for Complex<T> {
         }
     }
 }
-impl<'a, T: Num + Clone> Neg for &'a Complex<T> {
+impl<T: Num + Clone> Neg for &Complex<T> {
     type Output = Complex<T>;
 
     #[inline]
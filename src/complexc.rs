@@ -78,20 +78,17 @@ impl<T: Float> Complex<T> {
     }
     pub fn asin(&self) -> Self {
         let i = Self::i();
-        -i * ((complex(T::one(), T::zero()) - self.clone() * self.clone()).sqrt()
-            + self.clone() * i)
-            .ln()
+        -i * ((complex(T::one(), T::zero()) - *self * *self).sqrt() + *self * i).ln()
     }
     pub fn acos(&self) -> Self {
         let i = Self::i();
-        -i * ((self.clone() * self.clone() - complex(T::one(), T::zero())).sqrt() + self.clone())
-            .ln()
+        -i * ((*self * *self - complex(T::one(), T::zero())).sqrt() + *self).ln()
     }
     pub fn atan(&self) -> Self {
         let i = Self::i();
         let one = complex(T::one(), T::zero());
         let two = one + one;
-        let iz = i * self.clone();
+        let iz = i * *self;
         (i / two) * ((one - iz) / (one + iz)).ln()
     }
     pub fn sinh(&self) -> Self {
@@ -163,7 +160,7 @@ impl<T: Float> Complex<T> {
     }
     pub fn ln(&self) -> Self {
         let (r, theta) = self.to_polar();
-        Self::from_polar(r.ln(), theta)
+        complex(r.ln(), theta)
     }
     pub fn log(&self, base: T) -> Self {
         let (r, theta) = self.to_polar();
@@ -197,10 +194,10 @@ impl<T: Float + FloatConst> Complex<T> {
     }
 
     pub fn log2(&self) -> Self {
-        Self::ln(&self) / T::LN_2()
+        Self::ln(self) / T::LN_2()
     }
 
     pub fn log10(&self) -> Self {
-        Self::ln(&self) / T::LN_10()
+        Self::ln(self) / T::LN_10()
     }
 }
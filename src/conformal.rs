@@ -0,0 +1,460 @@
+//! Conformal maps with their inverses and derivatives
+//!
+//! Every map implements [`ConformalMap`]. Möbius maps cover the Cayley
+//! transform and the disk to half-plane maps. [`SchwarzChristoffel`] maps the
+//! unit disk onto the interior of a polygon, with the prevertices found
+//! numerically.
+//! ```rust
+//! use ancomplex::*;
+//! use ancomplex::conformal::{cayley, ConformalMap, Joukowski};
+//!
+//! // The upper half-plane goes to the unit disk, i to its center
+//! let map = cayley::<f64>();
+//! assert_eq!(map.map(c64(0.0, 1.0)), c64(0.0, 0.0));
+//! assert!((map.map(c64(3.0, 0.0)).norm() - 1.0).abs() < 1e-15);
+//!
+//! // Joukowski maps the circle |z| = 1 onto the segment [-2, 2]
+//! let joukowski = Joukowski::new(1.0);
+//! let w = joukowski.map(c64(0.6, 0.8));
+//! assert!((w - c64(1.2, 0.0)).norm() < 1e-15);
+//! assert!((joukowski.inverse_map(c64(2.5, 0.0)) - c64(2.0, 0.0)).norm() < 1e-15);
+//! ```
+use crate::cast::from_f64;
+use crate::geometry::signed_area;
+use crate::matrix::{CMatrix, CVector};
+use crate::mobius::Mobius;
+use crate::{complex, Complex};
+use num_traits::{Float, FloatConst};
+
+/// Quadrature points per panel in the Schwarz–Christoffel integrals
+const NODES: usize = 16;
+const MAX_NEWTON_STEPS: usize = 100;
+/// Points on the line from the conformal center used to track inverse images
+const CONTINUATION_STEPS: usize = 16;
+
+fn zero<T: Float>() -> Complex<T> {
+    complex(T::zero(), T::zero())
+}
+
+fn one<T: Float>() -> Complex<T> {
+    complex(T::one(), T::zero())
+}
+
+/// Angle-preserving map of a domain in the complex plane
+pub trait ConformalMap<T: Float> {
+    /// Image of `z`
+    fn map(&self, z: Complex<T>) -> Complex<T>;
+
+    /// Point mapping to `w`
+    fn inverse_map(&self, w: Complex<T>) -> Complex<T>;
+
+    /// Complex derivative of the map at `z`
+    fn derivative(&self, z: Complex<T>) -> Complex<T>;
+}
+
+impl<T: Float> ConformalMap<T> for Mobius<T> {
+    /// Non-finite at the pole -d/c
+    fn map(&self, z: Complex<T>) -> Complex<T> {
+        (self.a * z + self.b) / (self.c * z + self.d)
+    }
+
+    fn inverse_map(&self, w: Complex<T>) -> Complex<T> {
+        self.inverse().map(w)
+    }
+
+    /// (ad - bc)/(cz + d)²
+    fn derivative(&self, z: Complex<T>) -> Complex<T> {
+        let denominator = self.c * z + self.d;
+        self.det() / (denominator * denominator)
+    }
+}
+
+/// Cayley transform (z - i)/(z + i) from the upper half-plane onto the unit disk
+pub fn cayley<T: Float>() -> Mobius<T> {
+    let i = complex(T::zero(), T::one());
+    Mobius::new(one(), -i, one(), i)
+}
+
+/// Map (1 + z)/(1 - z) from the unit disk onto the right half-plane, as
+/// between reflection coefficient and normalized impedance
+pub fn disk_to_right_half_plane<T: Float>() -> Mobius<T> {
+    Mobius::new(one(), one(), -one::<T>(), one())
+}
+
+/// Joukowski map w = z + c²/z
+///
+/// The circle |z| = c maps onto the segment [-2c, 2c]. The exterior of the
+/// circle maps one-to-one onto the rest of the plane, and circles through
+/// z = c give airfoils with a sharp trailing edge at w = 2c.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Joukowski<T> {
+    pub c: T,
+}
+
+impl<T: Float> Joukowski<T> {
+    pub fn new(c: T) -> Self {
+        Joukowski { c }
+    }
+}
+
+impl<T: Float + FloatConst> Joukowski<T> {
+    /// `points` samples of the airfoil traced by the circle through z = c
+    /// centered at `center`, counterclockwise from the trailing edge
+    pub fn airfoil(&self, center: Complex<T>, points: usize) -> Vec<Complex<T>> {
+        let trailing = complex(self.c, T::zero()) - center;
+        let radius = trailing.norm();
+        let start = trailing.arg();
+        let step = (T::PI() + T::PI()) / from_f64::<T>(points as f64);
+        (0..points)
+            .map(|k| {
+                let theta = start + step * from_f64::<T>(k as f64);
+                self.map(center + Complex::from_polar(radius, theta))
+            })
+            .collect()
+    }
+}
+
+impl<T: Float> ConformalMap<T> for Joukowski<T> {
+    fn map(&self, z: Complex<T>) -> Complex<T> {
+        z + z.inv().scale(self.c * self.c)
+    }
+
+    /// Preimage with |z| ≥ c, continuous off the slit [-2c, 2c]
+    fn inverse_map(&self, w: Complex<T>) -> Complex<T> {
+        let two_c = complex(self.c + self.c, T::zero());
+        // √(w - 2c)·√(w + 2c) behaves like w at infinity, unlike √(w² - 4c²)
+        let root = (w - two_c).sqrt() * (w + two_c).sqrt();
+        (w + root).unscale(T::one() + T::one())
+    }
+
+    fn derivative(&self, z: Complex<T>) -> Complex<T> {
+        one::<T>() - (z * z).inv().scale(self.c * self.c)
+    }
+}
+
+/// Exponential map e^(πz/width) from the strip 0 < Im z < width onto the
+/// upper half-plane, with the principal logarithm as inverse
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Strip<T> {
+    pub width: T,
+}
+
+impl<T: Float> Strip<T> {
+    pub fn new(width: T) -> Self {
+        Strip { width }
+    }
+}
+
+impl<T: Float + FloatConst> ConformalMap<T> for Strip<T> {
+    fn map(&self, z: Complex<T>) -> Complex<T> {
+        z.scale(T::PI() / self.width).exp()
+    }
+
+    fn inverse_map(&self, w: Complex<T>) -> Complex<T> {
+        w.ln().scale(self.width / T::PI())
+    }
+
+    fn derivative(&self, z: Complex<T>) -> Complex<T> {
+        self.map(z).scale(T::PI() / self.width)
+    }
+}
+
+/// Nodes and weights of Gauss–Jacobi quadrature for the weight (1 - x)^α on
+/// [-1, 1] by the Golub–Welsch algorithm, α = 0 gives Gauss–Legendre
+fn gauss_jacobi<T: Float>(n: usize, alpha: T) -> Vec<(T, T)> {
+    let two = from_f64::<T>(2.0);
+    let mut jacobi = CMatrix::from_fn(n, n, |i, j| {
+        let value = if i == j {
+            let k = from_f64::<T>(i as f64);
+            -alpha * alpha / ((two * k + alpha) * (two * k + alpha + two))
+        } else if i + 1 == j || j + 1 == i {
+            let k = from_f64::<T>(i.max(j) as f64);
+            let s = two * k + alpha;
+            two * k * (k + alpha) / (s * (s * s - T::one()).sqrt())
+        } else {
+            T::zero()
+        };
+        complex(value, T::zero())
+    });
+    // The k = 0 diagonal entry has the limit -α/(α + 2) also for α = 0
+    jacobi[(0, 0)] = complex(-alpha / (alpha + two), T::zero());
+    let moment = two.powf(alpha + T::one()) / (alpha + T::one());
+    let eigen = jacobi.eigh();
+    eigen
+        .values
+        .iter()
+        .enumerate()
+        .map(|(i, &x)| (x, moment * eigen.vectors[(0, i)].square_norm()))
+        .collect()
+}
+
+/// Schwarz–Christoffel map from the unit disk onto a bounded polygon
+///
+/// f(z) = A + C·∫₀ᶻ Π(1 - ζ/z_k)^(α_k - 1) dζ, where α_k·π are the interior
+/// angles and z_k the prevertices on the unit circle. The prevertices are
+/// found by Newton's method on the side length ratios, with f(0) at the
+/// requested conformal center.
+#[derive(Debug, Clone)]
+pub struct SchwarzChristoffel<T> {
+    vertices: Vec<Complex<T>>,
+    prevertices: Vec<Complex<T>>,
+    exponents: Vec<T>,
+    center: Complex<T>,
+    constant: Complex<T>,
+    legendre: Vec<(T, T)>,
+    jacobi: Vec<Vec<(T, T)>>,
+}
+
+impl<T: Float + FloatConst> SchwarzChristoffel<T> {
+    /// Map onto the polygon with counterclockwise `vertices`, sending 0 to
+    /// `center` inside it
+    ///
+    /// Returns `None` if the parameter problem does not converge, which
+    /// happens for polygons with strongly crowded prevertices.
+    pub fn new(vertices: &[Complex<T>], center: Complex<T>) -> Option<Self> {
+        let n = vertices.len();
+        assert!(n >= 3, "polygon needs at least three vertices");
+        assert!(
+            signed_area(vertices) > T::zero(),
+            "polygon vertices must be counterclockwise"
+        );
+        let exponents: Vec<T> = (0..n)
+            .map(|k| {
+                let incoming = vertices[k] - vertices[(k + n - 1) % n];
+                let outgoing = vertices[(k + 1) % n] - vertices[k];
+                -(outgoing / incoming).arg() / T::PI()
+            })
+            .collect();
+        let mut map = SchwarzChristoffel {
+            vertices: vertices.to_vec(),
+            prevertices: vec![],
+            jacobi: exponents.iter().map(|&b| gauss_jacobi(NODES, b)).collect(),
+            legendre: gauss_jacobi(NODES, T::zero()),
+            exponents,
+            center,
+            constant: one(),
+        };
+        let mut y = vec![T::zero(); n - 1];
+        let mut residual = map.residual(&y);
+        let tolerance = T::epsilon().sqrt() * from_f64::<T>(1e-3);
+        let step = T::epsilon().sqrt();
+        for _ in 0..MAX_NEWTON_STEPS {
+            if residual_norm(&residual) < tolerance {
+                break;
+            }
+            let mut jacobian = CMatrix::zeros(n - 1, n - 1);
+            for j in 0..n - 1 {
+                let mut shifted = y.clone();
+                shifted[j] = shifted[j] + step;
+                let column = map.residual(&shifted);
+                for i in 0..n - 1 {
+                    jacobian[(i, j)] = complex((column[i] - residual[i]) / step, T::zero());
+                }
+            }
+            let rhs = CVector::new(residual.iter().map(|&r| complex(-r, T::zero())).collect());
+            let direction = jacobian.lu().solve(&rhs)?;
+            // Halve the step until the residual decreases
+            let mut lambda = T::one();
+            let improved = loop {
+                let candidate: Vec<T> = y
+                    .iter()
+                    .zip(direction.as_slice())
+                    .map(|(&yk, dk)| yk + lambda * dk.real)
+                    .collect();
+                let next = map.residual(&candidate);
+                if residual_norm(&next) < residual_norm(&residual) {
+                    break Some((candidate, next));
+                }
+                lambda = lambda / from_f64::<T>(2.0);
+                if lambda < from_f64::<T>(1e-4) {
+                    break None;
+                }
+            };
+            match improved {
+                Some((candidate, next)) => {
+                    y = candidate;
+                    residual = next;
+                }
+                None => break,
+            }
+        }
+        if residual_norm(&residual) > T::epsilon().sqrt() {
+            return None;
+        }
+        map.set_prevertices(&y);
+        let integrals = map.prevertex_integrals();
+        map.constant = (vertices[1] - vertices[0]) / (integrals[1] - integrals[0]);
+        map.center = vertices[0] - map.constant * integrals[0];
+        Some(map)
+    }
+
+    pub fn vertices(&self) -> &[Complex<T>] {
+        &self.vertices
+    }
+
+    /// Points on the unit circle mapping to the vertices
+    pub fn prevertices(&self) -> &[Complex<T>] {
+        &self.prevertices
+    }
+
+    /// Prevertices from the logarithms of relative gaps between consecutive
+    /// angles, with the last prevertex fixed at 1
+    fn set_prevertices(&mut self, y: &[T]) {
+        let gaps: Vec<T> = y
+            .iter()
+            .map(|&v| v.exp())
+            .chain(std::iter::once(T::one()))
+            .collect();
+        let total = gaps.iter().fold(T::zero(), |acc, &g| acc + g);
+        let scale = (T::PI() + T::PI()) / total;
+        let mut angle = T::zero();
+        self.prevertices = gaps
+            .iter()
+            .map(|&g| {
+                angle = angle + g * scale;
+                Complex::from_polar(T::one(), angle)
+            })
+            .collect();
+    }
+
+    /// Side length ratios after the first side and the conformal center, as
+    /// n - 1 real equations for the n - 1 free prevertex angles
+    fn residual(&mut self, y: &[T]) -> Vec<T> {
+        self.set_prevertices(y);
+        let n = self.vertices.len();
+        let w = &self.vertices;
+        let integrals = self.prevertex_integrals();
+        let first_side = (w[1] - w[0]).norm();
+        let first_image = (integrals[1] - integrals[0]).norm();
+        let mut residual: Vec<T> = (1..n - 2)
+            .map(|k| {
+                let side = (w[k + 1] - w[k]).norm() / first_side;
+                let image = (integrals[k + 1] - integrals[k]).norm() / first_image;
+                image.ln() - side.ln()
+            })
+            .collect();
+        let constant = (w[1] - w[0]) / (integrals[1] - integrals[0]);
+        let center = (w[0] - constant * integrals[0] - self.center).unscale(first_side);
+        residual.push(center.real);
+        residual.push(center.imag);
+        residual
+    }
+
+    /// Π(1 - ζ/z_j)^(α_j - 1) without the factor of prevertex `skip`
+    fn integrand(&self, zeta: Complex<T>, skip: Option<usize>) -> Complex<T> {
+        self.prevertices
+            .iter()
+            .zip(&self.exponents)
+            .enumerate()
+            .filter(|&(j, _)| Some(j) != skip)
+            .fold(one(), |acc, (_, (&z, &b))| {
+                acc * (one::<T>() - zeta / z).powf(b)
+            })
+    }
+
+    /// ∫ from a to b of the integrand, with panels shrinking geometrically
+    /// towards b down to the fraction `smallest` of the path
+    fn graded_integral(&self, a: Complex<T>, b: Complex<T>, smallest: T) -> Complex<T> {
+        let half = from_f64::<T>(0.5);
+        let mut sum = zero();
+        let mut end = T::one();
+        let mut length = smallest.min(T::one()).max(T::epsilon());
+        loop {
+            let start = (end - length).max(T::zero());
+            let (middle, radius) = ((start + end) * half, (end - start) * half);
+            for &(x, weight) in &self.legendre {
+                let zeta = a + (b - a).scale(middle + radius * x);
+                sum = sum + self.integrand(zeta, None).scale(weight * radius);
+            }
+            if start == T::zero() {
+                break;
+            }
+            end = start;
+            length = length + length;
+        }
+        sum * (b - a)
+    }
+
+    /// ∫ from 0 to z_k of the integrand for every prevertex, with the
+    /// singular end handled by Gauss–Jacobi quadrature
+    fn prevertex_integrals(&self) -> Vec<Complex<T>> {
+        let half = from_f64::<T>(0.5);
+        (0..self.prevertices.len())
+            .map(|k| {
+                let z = self.prevertices[k];
+                let gap = self
+                    .prevertices
+                    .iter()
+                    .enumerate()
+                    .filter(|&(j, _)| j != k)
+                    .fold(T::infinity(), |acc, (_, &p)| acc.min((p - z).norm()));
+                let h = (gap * half).min(half);
+                let inner = z.scale(T::one() - h);
+                let body = self.graded_integral(zero(), inner, h / (T::one() - h));
+                // ζ = t·z_k on [1 - h, 1] with weight (1 - t)^β
+                let beta = self.exponents[k];
+                let tip = self.jacobi[k].iter().fold(zero(), |acc, &(x, weight)| {
+                    let t = T::one() - h * (T::one() - x) * half;
+                    acc + self.integrand(z.scale(t), Some(k)).scale(weight)
+                });
+                body + tip * z.scale((h * half).powf(beta + T::one()))
+            })
+            .collect()
+    }
+}
+
+fn residual_norm<T: Float>(residual: &[T]) -> T {
+    residual
+        .iter()
+        .fold(T::zero(), |acc, &r| acc + r * r)
+        .sqrt()
+}
+
+impl<T: Float + FloatConst> ConformalMap<T> for SchwarzChristoffel<T> {
+    /// Image of `z` in the closed unit disk
+    fn map(&self, z: Complex<T>) -> Complex<T> {
+        let distance = self.prevertices.iter().zip(&self.vertices).fold(
+            (T::infinity(), zero()),
+            |(d, w), (&p, &v)| {
+                let d_p = (z - p).norm();
+                if d_p < d {
+                    (d_p, v)
+                } else {
+                    (d, w)
+                }
+            },
+        );
+        if distance.0 == T::zero() {
+            return distance.1;
+        }
+        if z.norm() == T::zero() {
+            return self.center;
+        }
+        let integral = self.graded_integral(zero(), z, distance.0 / z.norm());
+        self.center + self.constant * integral
+    }
+
+    /// Preimage of `w` by Newton's method continued along the segment from
+    /// the conformal center, which must lie inside the polygon
+    fn inverse_map(&self, w: Complex<T>) -> Complex<T> {
+        let mut z = zero();
+        let tolerance = T::epsilon() * from_f64::<T>(16.0);
+        for step in 1..=CONTINUATION_STEPS {
+            let fraction = from_f64::<T>(step as f64 / CONTINUATION_STEPS as f64);
+            let target = self.center + (w - self.center).scale(fraction);
+            let iterations = if step == CONTINUATION_STEPS { 20 } else { 3 };
+            for _ in 0..iterations {
+                let correction = (self.map(z) - target) / self.derivative(z);
+                z = z - correction;
+                if correction.norm() <= tolerance {
+                    break;
+                }
+            }
+        }
+        z
+    }
+
+    fn derivative(&self, z: Complex<T>) -> Complex<T> {
+        self.constant * self.integrand(z, None)
+    }
+}
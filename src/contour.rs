@@ -0,0 +1,290 @@
+//! Contour integrals, residues and the argument principle
+//!
+//! A [`Contour`] is a chain of line segments and circular arcs. Integrals
+//! along it use adaptive 7/15-point Gauss–Kronrod quadrature on each path.
+//! ```rust
+//! use ancomplex::*;
+//! use ancomplex::contour::{argument_principle, residue, Contour};
+//!
+//! // ∮ dz/z = 2πi around the unit circle
+//! let circle = Contour::circle(c64(0.0, 0.0), 1.0);
+//! let integral = circle.integrate(|z| z.inv(), 1e-12);
+//! assert!((integral - c64(0.0, 2.0 * std::f64::consts::PI)).norm() < 1e-12);
+//!
+//! // e^z/(z - 1) has residue e at z = 1
+//! let r = residue(|z: c64| z.exp() / (z - c64(1.0, 0.0)), c64(1.0, 0.0), 0.1, 1e-12);
+//! assert!((r - c64(std::f64::consts::E, 0.0)).norm() < 1e-12);
+//!
+//! // z²(z - 3) has two zeros inside the unit circle
+//! let f = |z: c64| z * z * (z - c64(3.0, 0.0));
+//! assert_eq!(argument_principle(f, &circle), Some(2));
+//! ```
+use crate::cast::from_f64;
+use crate::{complex, Complex};
+use num_traits::{Float, FloatConst};
+
+/// Kronrod nodes on [0, 1) of the 15-point rule, the odd ones are the
+/// 7-point Gauss nodes, with their Kronrod weights
+const KRONROD: [(f64, f64); 8] = [
+    (0.991_455_371_120_812_6, 0.022_935_322_010_529_225),
+    (0.949_107_912_342_758_5, 0.063_092_092_629_978_55),
+    (0.864_864_423_359_769_1, 0.104_790_010_322_250_18),
+    (0.741_531_185_599_394_4, 0.140_653_259_715_525_92),
+    (0.586_087_235_467_691_1, 0.169_004_726_639_267_9),
+    (0.405_845_151_377_397_2, 0.190_350_578_064_785_4),
+    (0.207_784_955_007_898_47, 0.204_432_940_075_298_9),
+    (0.0, 0.209_482_141_084_727_83),
+];
+
+/// Gauss weights of the nodes KRONROD[1], KRONROD[3], KRONROD[5], KRONROD[7]
+const GAUSS: [f64; 4] = [
+    0.129_484_966_168_869_7,
+    0.279_705_391_489_276_7,
+    0.381_830_050_505_118_9,
+    0.417_959_183_673_469_4,
+];
+
+/// Bisection depth after which a panel is accepted regardless of its error
+const MAX_DEPTH: usize = 50;
+/// Panels evaluated on one path before the pending ones are accepted as they are
+const MAX_PANELS: usize = 2_000;
+/// Multiple of ε·|K| below which a panel error is rounding noise
+const NOISE_FACTOR: f64 = 50.0;
+/// Initial samples per path when tracking the argument
+const ARGUMENT_SAMPLES: usize = 64;
+/// Largest phase change accepted between neighbouring samples
+const MAX_PHASE_STEP: f64 = 0.5;
+const MAX_ARGUMENT_SAMPLES: usize = 1 << 20;
+
+fn zero<T: Float>() -> Complex<T> {
+    complex(T::zero(), T::zero())
+}
+
+/// Smooth piece of a contour, parametrized by t in [0, 1]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Path<T> {
+    Segment {
+        start: Complex<T>,
+        end: Complex<T>,
+    },
+    /// Counterclockwise when `end_angle` > `start_angle`
+    Arc {
+        center: Complex<T>,
+        radius: T,
+        start_angle: T,
+        end_angle: T,
+    },
+}
+
+impl<T: Float> Path<T> {
+    pub fn point(&self, t: T) -> Complex<T> {
+        match *self {
+            Path::Segment { start, end } => start + (end - start).scale(t),
+            Path::Arc {
+                center,
+                radius,
+                start_angle,
+                end_angle,
+            } => center + Complex::from_polar(radius, start_angle + (end_angle - start_angle) * t),
+        }
+    }
+
+    /// dz/dt at parameter `t`
+    pub fn tangent(&self, t: T) -> Complex<T> {
+        match *self {
+            Path::Segment { start, end } => end - start,
+            Path::Arc {
+                radius,
+                start_angle,
+                end_angle,
+                ..
+            } => {
+                let sweep = end_angle - start_angle;
+                let theta = start_angle + sweep * t;
+                complex(T::zero(), radius * sweep) * Complex::from_polar(T::one(), theta)
+            }
+        }
+    }
+
+    pub fn start(&self) -> Complex<T> {
+        self.point(T::zero())
+    }
+
+    pub fn end(&self) -> Complex<T> {
+        self.point(T::one())
+    }
+
+    /// Kronrod and Gauss estimates of ∫ f dz over parameters [a, b]
+    fn kronrod<F: Fn(Complex<T>) -> Complex<T>>(
+        &self,
+        f: &F,
+        a: T,
+        b: T,
+    ) -> (Complex<T>, Complex<T>) {
+        let half = from_f64::<T>(0.5);
+        let (middle, radius) = ((a + b) * half, (b - a) * half);
+        let value = |t: T| f(self.point(t)) * self.tangent(t);
+        let center = value(middle);
+        let mut kronrod = center.scale(from_f64::<T>(KRONROD[7].1));
+        let mut gauss = center.scale(from_f64::<T>(GAUSS[3]));
+        for (k, &(x, weight)) in KRONROD[..7].iter().enumerate() {
+            let offset = radius * from_f64::<T>(x);
+            let pair = value(middle - offset) + value(middle + offset);
+            kronrod = kronrod + pair.scale(from_f64::<T>(weight));
+            if k % 2 == 1 {
+                gauss = gauss + pair.scale(from_f64::<T>(GAUSS[k / 2]));
+            }
+        }
+        (kronrod.scale(radius), gauss.scale(radius))
+    }
+
+    /// Adaptive ∫ f dz along the path with absolute error target `tol`
+    fn integrate<F: Fn(Complex<T>) -> Complex<T>>(&self, f: &F, tol: T) -> Complex<T> {
+        let noise = from_f64::<T>(NOISE_FACTOR) * T::epsilon();
+        let mut sum = zero();
+        let mut pending = vec![(T::zero(), T::one(), 0)];
+        let mut panels = 0;
+        while let Some((a, b, depth)) = pending.pop() {
+            let (kronrod, gauss) = self.kronrod(f, a, b);
+            panels += 1;
+            let error = (kronrod - gauss).norm();
+            // NaN errors never shrink, so they are accepted and propagate to the sum
+            let accepted = error <= (tol * (b - a)).max(noise * kronrod.norm()) || error.is_nan();
+            if accepted || depth == MAX_DEPTH || panels >= MAX_PANELS {
+                sum = sum + kronrod;
+            } else {
+                let middle = (a + b) * from_f64::<T>(0.5);
+                pending.push((middle, b, depth + 1));
+                pending.push((a, middle, depth + 1));
+            }
+        }
+        sum
+    }
+}
+
+/// Chain of paths, closed when the last one ends where the first starts
+#[derive(Debug, Clone, PartialEq)]
+pub struct Contour<T> {
+    pub paths: Vec<Path<T>>,
+}
+
+impl<T: Float> Contour<T> {
+    pub fn new(paths: Vec<Path<T>>) -> Self {
+        Contour { paths }
+    }
+
+    /// Open chain of segments through `points`
+    pub fn polyline(points: &[Complex<T>]) -> Self {
+        let paths = points
+            .windows(2)
+            .map(|pair| Path::Segment {
+                start: pair[0],
+                end: pair[1],
+            })
+            .collect();
+        Contour { paths }
+    }
+
+    /// Closed chain of segments through `vertices` and back to the first
+    pub fn polygon(vertices: &[Complex<T>]) -> Self {
+        let mut points = vertices.to_vec();
+        points.extend(vertices.first());
+        Self::polyline(&points)
+    }
+
+    /// Appends a path, returning the contour for chaining
+    pub fn then(mut self, path: Path<T>) -> Self {
+        self.paths.push(path);
+        self
+    }
+
+    /// ∫ f(z) dz along the contour
+    ///
+    /// `tol` is an absolute error target for each path. Panels are bisected
+    /// until the Gauss and Kronrod estimates agree to `tol` or to rounding
+    /// error, so singular points on the contour are not detected but only
+    /// slow the integration down. Each path stops refining after a fixed
+    /// budget of panels, and a NaN integrand gives a NaN result.
+    pub fn integrate<F: Fn(Complex<T>) -> Complex<T>>(&self, f: F, tol: T) -> Complex<T> {
+        self.paths
+            .iter()
+            .fold(zero(), |acc, path| acc + path.integrate(&f, tol))
+    }
+}
+
+impl<T: Float + FloatConst> Contour<T> {
+    /// Counterclockwise circle
+    pub fn circle(center: Complex<T>, radius: T) -> Self {
+        Contour {
+            paths: vec![Path::Arc {
+                center,
+                radius,
+                start_angle: T::zero(),
+                end_angle: T::PI() + T::PI(),
+            }],
+        }
+    }
+}
+
+/// Residue of `f` at `pole` as (1/2πi)∮ f dz over the circle of `radius`,
+/// which must enclose no other singularity
+pub fn residue<T, F>(f: F, pole: Complex<T>, radius: T, tol: T) -> Complex<T>
+where
+    T: Float + FloatConst,
+    F: Fn(Complex<T>) -> Complex<T>,
+{
+    let two_pi = T::PI() + T::PI();
+    let integral = Contour::circle(pole, radius).integrate(f, tol * two_pi);
+    integral / complex(T::zero(), two_pi)
+}
+
+/// Zeros minus poles of `f` inside the closed `contour`, counted with
+/// multiplicity, as the winding number of f(γ) around the origin
+///
+/// The argument of f is tracked along the contour with samples refined
+/// until neighbouring phases differ by less than half a radian. Each path
+/// starts with 64 samples, so features of f shorter than that spacing can
+/// be missed and long paths should be split. Returns `None` if f vanishes
+/// or is not finite on the contour.
+pub fn argument_principle<T, F>(f: F, contour: &Contour<T>) -> Option<i32>
+where
+    T: Float + FloatConst,
+    F: Fn(Complex<T>) -> Complex<T>,
+{
+    let finite = |w: Complex<T>| {
+        w.real.is_finite() && w.imag.is_finite() && (w.real != T::zero() || w.imag != T::zero())
+    };
+    let max_step = from_f64::<T>(MAX_PHASE_STEP);
+    let min_width = T::one() / from_f64::<T>(MAX_ARGUMENT_SAMPLES as f64);
+    let mut total = T::zero();
+    for path in &contour.paths {
+        let step = T::one() / from_f64::<T>(ARGUMENT_SAMPLES as f64);
+        let mut t = T::zero();
+        let mut value = f(path.point(t));
+        if !finite(value) {
+            return None;
+        }
+        while t < T::one() {
+            let mut width = step.min(T::one() - t);
+            loop {
+                let next = f(path.point(t + width));
+                if !finite(next) {
+                    return None;
+                }
+                let change = (next / value).arg();
+                if change.abs() <= max_step {
+                    total = total + change;
+                    value = next;
+                    t = t + width;
+                    break;
+                }
+                width = width * from_f64::<T>(0.5);
+                if width < min_width {
+                    return None;
+                }
+            }
+        }
+    }
+    let turns = total / (T::PI() + T::PI());
+    turns.round().to_i32()
+}
@@ -0,0 +1,253 @@
+//! FFT based convolution and correlation of complex sequences
+//!
+//! Includes 2-D convolution of row-major matrices and streaming
+//! overlap-add/overlap-save block convolvers.
+//! ```rust
+//! use ancomplex::*;
+//! use ancomplex::convolution::convolve;
+//!
+//! let a = [c64(1.0, 0.0), c64(2.0, 0.0)];
+//! let b = [c64(1.0, 0.0), c64(0.0, 1.0)];
+//! let c = convolve(&a, &b);
+//! assert!((c[1] - c64(2.0, 1.0)).norm() < 1e-12);
+//! ```
+use crate::fft::{FftPlan, FftPlanNd};
+use crate::{complex, Complex};
+use num_traits::{Float, FloatConst};
+
+fn zeros<T: Float>(len: usize) -> Vec<Complex<T>> {
+    vec![complex(T::zero(), T::zero()); len]
+}
+
+/// Copies `data` into zero padded buffer of length `len`
+fn padded<T: Float>(data: &[Complex<T>], len: usize) -> Vec<Complex<T>> {
+    let mut buffer = zeros(len);
+    buffer[..data.len()].copy_from_slice(data);
+    buffer
+}
+
+/// Multiplies spectra of two equally long buffers and transforms back
+fn multiply_spectra<T: Float + FloatConst>(
+    plan: &FftPlan<T>,
+    mut a: Vec<Complex<T>>,
+    mut b: Vec<Complex<T>>,
+) -> Vec<Complex<T>> {
+    plan.forward(&mut a);
+    plan.forward(&mut b);
+    for (x, y) in a.iter_mut().zip(&b) {
+        *x *= *y;
+    }
+    plan.inverse(&mut a);
+    a
+}
+
+/// Linear convolution, result has length `a.len() + b.len() - 1`
+pub fn convolve<T: Float + FloatConst>(a: &[Complex<T>], b: &[Complex<T>]) -> Vec<Complex<T>> {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+    let len = a.len() + b.len() - 1;
+    let plan = FftPlan::new(len.next_power_of_two());
+    let mut result = multiply_spectra(&plan, padded(a, plan.len()), padded(b, plan.len()));
+    result.truncate(len);
+    result
+}
+
+/// Circular convolution of two sequences of equal length
+pub fn circular_convolve<T: Float + FloatConst>(
+    a: &[Complex<T>],
+    b: &[Complex<T>],
+) -> Vec<Complex<T>> {
+    assert_eq!(a.len(), b.len(), "sequences must have equal length");
+    let plan = FftPlan::new(a.len());
+    multiply_spectra(&plan, a.to_vec(), b.to_vec())
+}
+
+/// Full cross-correlation r[k] = Σ a[n+k]·conj(b[n])
+///
+/// Result has length `a.len() + b.len() - 1`, zero lag is at index `b.len() - 1`.
+pub fn correlate<T: Float + FloatConst>(a: &[Complex<T>], b: &[Complex<T>]) -> Vec<Complex<T>> {
+    let reversed: Vec<Complex<T>> = b.iter().rev().map(|x| x.conj()).collect();
+    convolve(a, &reversed)
+}
+
+/// Circular cross-correlation r[k] = Σ a[(n+k) mod N]·conj(b[n])
+pub fn circular_correlate<T: Float + FloatConst>(
+    a: &[Complex<T>],
+    b: &[Complex<T>],
+) -> Vec<Complex<T>> {
+    assert_eq!(a.len(), b.len(), "sequences must have equal length");
+    let plan = FftPlan::new(a.len());
+    let mut fa = a.to_vec();
+    let mut fb = b.to_vec();
+    plan.forward(&mut fa);
+    plan.forward(&mut fb);
+    for (x, y) in fa.iter_mut().zip(&fb) {
+        *x *= y.conj();
+    }
+    plan.inverse(&mut fa);
+    fa
+}
+
+/// Linear 2-D convolution of row-major matrices
+///
+/// Result is a row-major matrix of shape `(a_rows + b_rows - 1, a_cols + b_cols - 1)`.
+pub fn convolve2d<T: Float + FloatConst>(
+    a: &[Complex<T>],
+    a_shape: (usize, usize),
+    b: &[Complex<T>],
+    b_shape: (usize, usize),
+) -> Vec<Complex<T>> {
+    assert_eq!(a.len(), a_shape.0 * a_shape.1, "a does not match its shape");
+    assert_eq!(b.len(), b_shape.0 * b_shape.1, "b does not match its shape");
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+    let rows = a_shape.0 + b_shape.0 - 1;
+    let cols = a_shape.1 + b_shape.1 - 1;
+    let (fft_rows, fft_cols) = (rows.next_power_of_two(), cols.next_power_of_two());
+    let embed = |data: &[Complex<T>], shape: (usize, usize)| {
+        let mut buffer = zeros(fft_rows * fft_cols);
+        for (r, row) in data.chunks(shape.1).enumerate() {
+            buffer[r * fft_cols..r * fft_cols + shape.1].copy_from_slice(row);
+        }
+        buffer
+    };
+    let plan = FftPlanNd::new(&[fft_rows, fft_cols]);
+    let mut fa = embed(a, a_shape);
+    let mut fb = embed(b, b_shape);
+    plan.forward(&mut fa);
+    plan.forward(&mut fb);
+    for (x, y) in fa.iter_mut().zip(&fb) {
+        *x *= *y;
+    }
+    plan.inverse(&mut fa);
+    fa.chunks(fft_cols)
+        .take(rows)
+        .flat_map(|row| row[..cols].iter().copied())
+        .collect()
+}
+
+/// Streaming FIR filtering by the overlap-add method
+///
+/// Each call to [`OverlapAdd::process`] returns as many samples as it gets,
+/// the convolution tail is carried over to the next call.
+#[derive(Debug, Clone)]
+pub struct OverlapAdd<T> {
+    plan: FftPlan<T>,
+    block_len: usize,
+    filter_len: usize,
+    spectrum: Vec<Complex<T>>,
+    tail: Vec<Complex<T>>,
+}
+
+impl<T: Float + FloatConst> OverlapAdd<T> {
+    /// Creates convolver for `filter`, input is processed in blocks of `block_len` samples
+    pub fn new(filter: &[Complex<T>], block_len: usize) -> Self {
+        assert!(!filter.is_empty(), "filter must not be empty");
+        assert!(block_len > 0, "block length must be positive");
+        let plan = FftPlan::new((block_len + filter.len() - 1).next_power_of_two());
+        let mut spectrum = padded(filter, plan.len());
+        plan.forward(&mut spectrum);
+        OverlapAdd {
+            block_len,
+            filter_len: filter.len(),
+            spectrum,
+            tail: zeros(filter.len() - 1),
+            plan,
+        }
+    }
+
+    /// Filters next chunk of the stream
+    pub fn process(&mut self, input: &[Complex<T>]) -> Vec<Complex<T>> {
+        let mut output = Vec::with_capacity(input.len());
+        for block in input.chunks(self.block_len) {
+            let mut buffer = padded(block, self.plan.len());
+            self.plan.forward(&mut buffer);
+            for (x, h) in buffer.iter_mut().zip(&self.spectrum) {
+                *x *= *h;
+            }
+            self.plan.inverse(&mut buffer);
+            for (k, t) in self.tail.iter().enumerate() {
+                buffer[k] = buffer[k] + *t;
+            }
+            output.extend_from_slice(&buffer[..block.len()]);
+            let tail_len = self.filter_len - 1;
+            self.tail
+                .copy_from_slice(&buffer[block.len()..block.len() + tail_len]);
+        }
+        output
+    }
+
+    /// Returns the remaining `filter.len() - 1` samples and resets the state
+    pub fn flush(&mut self) -> Vec<Complex<T>> {
+        let tail = self.tail.clone();
+        self.reset();
+        tail
+    }
+
+    /// Clears the carried convolution tail
+    pub fn reset(&mut self) {
+        self.tail = zeros(self.filter_len - 1);
+    }
+}
+
+/// Streaming FIR filtering by the overlap-save method
+///
+/// Each call to [`OverlapSave::process`] returns as many samples as it gets,
+/// the last `filter.len() - 1` input samples are kept as history.
+#[derive(Debug, Clone)]
+pub struct OverlapSave<T> {
+    plan: FftPlan<T>,
+    filter_len: usize,
+    spectrum: Vec<Complex<T>>,
+    history: Vec<Complex<T>>,
+}
+
+impl<T: Float + FloatConst> OverlapSave<T> {
+    /// Creates convolver for `filter`, input is processed in blocks of `block_len` samples
+    pub fn new(filter: &[Complex<T>], block_len: usize) -> Self {
+        assert!(!filter.is_empty(), "filter must not be empty");
+        assert!(block_len > 0, "block length must be positive");
+        let plan = FftPlan::new((block_len + filter.len() - 1).next_power_of_two());
+        let mut spectrum = padded(filter, plan.len());
+        plan.forward(&mut spectrum);
+        OverlapSave {
+            filter_len: filter.len(),
+            spectrum,
+            history: zeros(filter.len() - 1),
+            plan,
+        }
+    }
+
+    /// Number of new samples consumed by a single transform
+    fn step(&self) -> usize {
+        self.plan.len() - (self.filter_len - 1)
+    }
+
+    /// Filters next chunk of the stream
+    pub fn process(&mut self, input: &[Complex<T>]) -> Vec<Complex<T>> {
+        let overlap = self.filter_len - 1;
+        let mut output = Vec::with_capacity(input.len());
+        for block in input.chunks(self.step()) {
+            let mut frame = zeros(self.plan.len());
+            frame[..overlap].copy_from_slice(&self.history);
+            frame[overlap..overlap + block.len()].copy_from_slice(block);
+            let joined_len = overlap + block.len();
+            let new_history = frame[joined_len - overlap..joined_len].to_vec();
+            self.plan.forward(&mut frame);
+            for (x, h) in frame.iter_mut().zip(&self.spectrum) {
+                *x *= *h;
+            }
+            self.plan.inverse(&mut frame);
+            output.extend_from_slice(&frame[overlap..joined_len]);
+            self.history = new_history;
+        }
+        output
+    }
+
+    /// Clears the input history
+    pub fn reset(&mut self) {
+        self.history = zeros(self.filter_len - 1);
+    }
+}
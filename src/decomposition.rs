@@ -0,0 +1,490 @@
+//! LU, QR and Cholesky decompositions of complex matrices
+//!
+//! Factorizations are computed once and reused for any number of right-hand
+//! sides. Singular or rank-deficient systems make `solve` return `None`.
+//! ```rust
+//! use ancomplex::*;
+//! use ancomplex::matrix::{CMatrix, CVector};
+//!
+//! // Nodal admittance matrix of two nodes joined by an inductor
+//! let y = CMatrix::new(2, 2, vec![c64(1.0, -1.0), c64(0.0, 1.0), c64(0.0, 1.0), c64(0.5, -1.0)]);
+//! let current = CVector::new(vec![c64(1.0, 0.0), c64(0.0, 0.0)]);
+//! let voltage = y.lu().solve(&current).unwrap();
+//! let residual = &(&y * &voltage) - &current;
+//! assert!(residual.norm() < 1e-12);
+//! ```
+use crate::cast::from_f64;
+use crate::matrix::{CMatrix, CVector};
+use crate::{complex, Complex};
+use num_traits::Float;
+
+fn zero<T: Float>() -> Complex<T> {
+    complex(T::zero(), T::zero())
+}
+
+fn one<T: Float>() -> Complex<T> {
+    complex(T::one(), T::zero())
+}
+
+/// z/|z|, or 1 for zero
+fn phase<T: Float>(z: Complex<T>) -> Complex<T> {
+    let r = z.norm();
+    if r == T::zero() {
+        one()
+    } else {
+        z.unscale(r)
+    }
+}
+
+impl<T: Float> CMatrix<T> {
+    /// LU decomposition with partial pivoting
+    pub fn lu(&self) -> Lu<T> {
+        Lu::new(self)
+    }
+
+    /// Householder QR decomposition
+    pub fn qr(&self) -> Qr<T> {
+        Qr::new(self)
+    }
+
+    /// Cholesky decomposition, `None` unless the matrix is Hermitian positive definite
+    pub fn cholesky(&self) -> Option<Cholesky<T>> {
+        Cholesky::new(self)
+    }
+}
+
+/// PA = LU with unit lower triangular L and row permutation P
+#[derive(Debug, Clone)]
+pub struct Lu<T> {
+    /// L below the diagonal, U on and above it
+    factors: CMatrix<T>,
+    /// Row swapped with row k at step k, as in LAPACK's ipiv
+    pivots: Vec<usize>,
+    /// Parity of the permutation, ±1
+    sign: T,
+    /// ‖A‖₁ kept for condition estimates
+    norm_one: T,
+    singular: bool,
+}
+
+impl<T: Float> Lu<T> {
+    pub fn new(a: &CMatrix<T>) -> Self {
+        assert!(a.is_square(), "LU of non-square matrix");
+        let n = a.rows();
+        let mut f = a.clone();
+        let mut pivots = Vec::with_capacity(n);
+        let mut sign = T::one();
+        let mut singular = false;
+        // Pivots this small are rounding noise, as in Qr::is_rank_deficient
+        let largest = (0..n).fold(T::zero(), |acc, i| {
+            (0..n).fold(acc, |acc, j| acc.max(a[(i, j)].norm()))
+        });
+        let tol = largest * T::epsilon() * from_f64::<T>(n.max(1) as f64);
+        for k in 0..n {
+            let p = (k..n).fold(k, |best, i| {
+                if f[(i, k)].norm() > f[(best, k)].norm() {
+                    i
+                } else {
+                    best
+                }
+            });
+            pivots.push(p);
+            if p != k {
+                for j in 0..n {
+                    let tmp = f[(k, j)];
+                    f[(k, j)] = f[(p, j)];
+                    f[(p, j)] = tmp;
+                }
+                sign = -sign;
+            }
+            let pivot = f[(k, k)];
+            if pivot.norm() <= tol {
+                singular = true;
+            }
+            if pivot.norm() == T::zero() {
+                continue;
+            }
+            for i in k + 1..n {
+                let l = f[(i, k)] / pivot;
+                f[(i, k)] = l;
+                for j in k + 1..n {
+                    f[(i, j)] = f[(i, j)] - l * f[(k, j)];
+                }
+            }
+        }
+        Lu {
+            factors: f,
+            pivots,
+            sign,
+            norm_one: a.norm_one(),
+            singular,
+        }
+    }
+
+    /// Checks whether some pivot is negligible against the largest entry of A
+    pub fn is_singular(&self) -> bool {
+        self.singular
+    }
+
+    /// Unit lower triangular factor
+    pub fn l(&self) -> CMatrix<T> {
+        let n = self.factors.rows();
+        CMatrix::from_fn(n, n, |i, j| match i.cmp(&j) {
+            std::cmp::Ordering::Greater => self.factors[(i, j)],
+            std::cmp::Ordering::Equal => one(),
+            std::cmp::Ordering::Less => zero(),
+        })
+    }
+
+    /// Upper triangular factor
+    pub fn u(&self) -> CMatrix<T> {
+        let n = self.factors.rows();
+        CMatrix::from_fn(
+            n,
+            n,
+            |i, j| if i <= j { self.factors[(i, j)] } else { zero() },
+        )
+    }
+
+    /// Permutation matrix P with PA = LU
+    pub fn p(&self) -> CMatrix<T> {
+        let mut p = CMatrix::identity(self.factors.rows());
+        self.permute_rows(p.as_mut_slice(), self.factors.rows());
+        p
+    }
+
+    /// Applies the row swaps to row-major data with `width` columns
+    fn permute_rows(&self, data: &mut [Complex<T>], width: usize) {
+        for (k, &p) in self.pivots.iter().enumerate() {
+            if p != k {
+                for j in 0..width {
+                    data.swap(k * width + j, p * width + j);
+                }
+            }
+        }
+    }
+
+    /// Solves Ax = b
+    pub fn solve(&self, b: &CVector<T>) -> Option<CVector<T>> {
+        let n = self.factors.rows();
+        assert_eq!(b.len(), n, "right-hand side length does not match matrix");
+        if self.singular {
+            return None;
+        }
+        let f = &self.factors;
+        let mut x = b.clone();
+        self.permute_rows(x.as_mut_slice(), 1);
+        for i in 0..n {
+            let sum = (0..i).fold(x[i], |acc, j| acc - f[(i, j)] * x[j]);
+            x[i] = sum;
+        }
+        for i in (0..n).rev() {
+            let sum = (i + 1..n).fold(x[i], |acc, j| acc - f[(i, j)] * x[j]);
+            x[i] = sum / f[(i, i)];
+        }
+        Some(x)
+    }
+
+    /// Solves Aᴴx = b using the same factors
+    pub fn solve_hermitian(&self, b: &CVector<T>) -> Option<CVector<T>> {
+        let n = self.factors.rows();
+        assert_eq!(b.len(), n, "right-hand side length does not match matrix");
+        if self.singular {
+            return None;
+        }
+        let f = &self.factors;
+        // Aᴴ = Uᴴ·Lᴴ·P
+        let mut x = b.clone();
+        for i in 0..n {
+            let sum = (0..i).fold(x[i], |acc, j| acc - f[(j, i)].conj() * x[j]);
+            x[i] = sum / f[(i, i)].conj();
+        }
+        for i in (0..n).rev() {
+            let sum = (i + 1..n).fold(x[i], |acc, j| acc - f[(j, i)].conj() * x[j]);
+            x[i] = sum;
+        }
+        for (k, &p) in self.pivots.iter().enumerate().rev() {
+            x.as_mut_slice().swap(k, p);
+        }
+        Some(x)
+    }
+
+    /// Solves AX = B column by column
+    pub fn solve_matrix(&self, b: &CMatrix<T>) -> Option<CMatrix<T>> {
+        let n = self.factors.rows();
+        assert_eq!(b.rows(), n, "right-hand side rows do not match matrix");
+        let mut x = CMatrix::zeros(n, b.cols());
+        for j in 0..b.cols() {
+            let column = self.solve(&b.column(j))?;
+            for i in 0..n {
+                x[(i, j)] = column[i];
+            }
+        }
+        Some(x)
+    }
+
+    pub fn det(&self) -> Complex<T> {
+        let n = self.factors.rows();
+        (0..n).fold(complex(self.sign, T::zero()), |acc, i| {
+            acc * self.factors[(i, i)]
+        })
+    }
+
+    pub fn inverse(&self) -> Option<CMatrix<T>> {
+        self.solve_matrix(&CMatrix::identity(self.factors.rows()))
+    }
+
+    /// Estimate of the condition number κ₁(A) = ‖A‖₁·‖A⁻¹‖₁
+    ///
+    /// Uses Hager's method as refined by Higham, which needs a few solves
+    /// instead of the full inverse. The estimate never exceeds the true
+    /// value and is usually within a factor of 3. Singular matrices give infinity.
+    pub fn condition_estimate(&self) -> T {
+        if self.singular {
+            return T::infinity();
+        }
+        let n = self.factors.rows();
+        let inverse_norm = inverse_norm_estimate(
+            n,
+            |x| self.solve(x).expect("matrix is not singular"),
+            |x| self.solve_hermitian(x).expect("matrix is not singular"),
+        );
+        self.norm_one * inverse_norm
+    }
+}
+
+/// Hager–Higham estimate of ‖A⁻¹‖₁ from solvers for A and Aᴴ
+fn inverse_norm_estimate<T, S, H>(n: usize, solve: S, solve_hermitian: H) -> T
+where
+    T: Float,
+    S: Fn(&CVector<T>) -> CVector<T>,
+    H: Fn(&CVector<T>) -> CVector<T>,
+{
+    if n == 0 {
+        return T::zero();
+    }
+    let norm1 = |v: &CVector<T>| v.as_slice().iter().fold(T::zero(), |acc, x| acc + x.norm());
+    let uniform = complex(T::one() / from_f64::<T>(n as f64), T::zero());
+    let mut x = CVector::new(vec![uniform; n]);
+    let mut estimate = T::zero();
+    let mut last = None;
+    for _ in 0..5 {
+        let y = solve(&x);
+        estimate = estimate.max(norm1(&y));
+        let signs = CVector::new(y.as_slice().iter().map(|&v| phase(v)).collect());
+        let z = solve_hermitian(&signs);
+        let (j, largest) =
+            z.as_slice()
+                .iter()
+                .enumerate()
+                .fold((0, T::zero()), |(bj, bv), (k, v)| {
+                    if v.norm() > bv {
+                        (k, v.norm())
+                    } else {
+                        (bj, bv)
+                    }
+                });
+        if largest <= z.dotc(&x).real || last == Some(j) {
+            break;
+        }
+        last = Some(j);
+        x = CVector::zeros(n);
+        x[j] = one();
+    }
+    estimate
+}
+
+/// A = QR with Q having orthonormal columns and R upper triangular
+///
+/// Q is kept as Householder reflectors H = I - 2vvᴴ/(vᴴv), so applying Qᴴ
+/// to a vector costs one pass over them.
+#[derive(Debug, Clone)]
+pub struct Qr<T> {
+    /// R on and above the diagonal, garbage below
+    r: CMatrix<T>,
+    reflectors: Vec<Vec<Complex<T>>>,
+}
+
+impl<T: Float> Qr<T> {
+    /// Factors m×n matrix with m ≥ n
+    pub fn new(a: &CMatrix<T>) -> Self {
+        let (m, n) = (a.rows(), a.cols());
+        assert!(m >= n, "QR needs at least as many rows as columns");
+        let mut r = a.clone();
+        let mut reflectors = Vec::with_capacity(n);
+        for k in 0..n {
+            let mut v: Vec<Complex<T>> = (k..m).map(|i| r[(i, k)]).collect();
+            let norm = CVector::new(v.clone()).norm();
+            // α = -e^(i·arg x₀)·‖x‖ keeps v₀ = x₀ - α free of cancellation
+            let alpha = -phase(v[0]).scale(norm);
+            v[0] = v[0] - alpha;
+            let vv = v.iter().fold(T::zero(), |acc, x| acc + x.square_norm());
+            if vv > T::zero() {
+                for j in k..n {
+                    let dot = (k..m).fold(zero(), |acc, i| acc + v[i - k].conj() * r[(i, j)]);
+                    let factor = dot * (T::one() + T::one()) / vv;
+                    for i in k..m {
+                        r[(i, j)] = r[(i, j)] - v[i - k] * factor;
+                    }
+                }
+            }
+            reflectors.push(v);
+        }
+        Qr { r, reflectors }
+    }
+
+    /// Applies Qᴴ = H_(n-1)···H_0 to vector of length m
+    fn apply_qh(&self, b: &mut [Complex<T>]) {
+        for (k, v) in self.reflectors.iter().enumerate() {
+            reflect(v, &mut b[k..]);
+        }
+    }
+
+    /// Thin factor Q, m×n with orthonormal columns
+    pub fn q(&self) -> CMatrix<T> {
+        let (m, n) = (self.r.rows(), self.r.cols());
+        let mut q = CMatrix::zeros(m, n);
+        for j in 0..n {
+            let mut e = vec![zero(); m];
+            e[j] = one();
+            // Q = H_0···H_(n-1)
+            for (k, v) in self.reflectors.iter().enumerate().rev() {
+                reflect(v, &mut e[k..]);
+            }
+            for i in 0..m {
+                q[(i, j)] = e[i];
+            }
+        }
+        q
+    }
+
+    /// Square upper triangular factor, n×n
+    pub fn r(&self) -> CMatrix<T> {
+        let n = self.r.cols();
+        CMatrix::from_fn(n, n, |i, j| if i <= j { self.r[(i, j)] } else { zero() })
+    }
+
+    /// Checks whether some diagonal entry of R is negligible against the largest one
+    pub fn is_rank_deficient(&self) -> bool {
+        let n = self.r.cols();
+        let largest = (0..n).fold(T::zero(), |acc, i| acc.max(self.r[(i, i)].norm()));
+        let tol = largest * T::epsilon() * from_f64::<T>(self.r.rows().max(1) as f64);
+        (0..n).any(|i| self.r[(i, i)].norm() <= tol)
+    }
+
+    /// Minimizes ‖Ax - b‖₂, `None` if A is rank deficient
+    pub fn solve_least_squares(&self, b: &CVector<T>) -> Option<CVector<T>> {
+        let (m, n) = (self.r.rows(), self.r.cols());
+        assert_eq!(b.len(), m, "right-hand side length does not match matrix");
+        if self.is_rank_deficient() {
+            return None;
+        }
+        let mut y = b.clone().into_vec();
+        self.apply_qh(&mut y);
+        let mut x = vec![zero(); n];
+        for i in (0..n).rev() {
+            let sum = (i + 1..n).fold(y[i], |acc, j| acc - self.r[(i, j)] * x[j]);
+            x[i] = sum / self.r[(i, i)];
+        }
+        Some(CVector::new(x))
+    }
+}
+
+/// Applies H = I - 2vvᴴ/(vᴴv) to `x` in place
+fn reflect<T: Float>(v: &[Complex<T>], x: &mut [Complex<T>]) {
+    let vv = v.iter().fold(T::zero(), |acc, c| acc + c.square_norm());
+    if vv == T::zero() {
+        return;
+    }
+    let dot = v
+        .iter()
+        .zip(x.iter())
+        .fold(zero(), |acc, (a, b)| acc + a.conj() * *b);
+    let factor = dot * (T::one() + T::one()) / vv;
+    for (xi, vi) in x.iter_mut().zip(v) {
+        *xi = *xi - *vi * factor;
+    }
+}
+
+/// A = LLᴴ for Hermitian positive definite A
+#[derive(Debug, Clone)]
+pub struct Cholesky<T> {
+    l: CMatrix<T>,
+    norm_one: T,
+}
+
+impl<T: Float> Cholesky<T> {
+    /// Factors Hermitian positive definite matrix, only its lower triangle is read
+    ///
+    /// Returns `None` when a pivot is not positive, i.e. the matrix is not
+    /// positive definite.
+    pub fn new(a: &CMatrix<T>) -> Option<Self> {
+        assert!(a.is_square(), "Cholesky of non-square matrix");
+        let n = a.rows();
+        let mut l = CMatrix::zeros(n, n);
+        for j in 0..n {
+            let d = (0..j).fold(a[(j, j)].real, |acc, k| acc - l[(j, k)].square_norm());
+            if d.is_nan() || d <= T::zero() {
+                return None;
+            }
+            let ljj = d.sqrt();
+            l[(j, j)] = complex(ljj, T::zero());
+            for i in j + 1..n {
+                let sum = (0..j).fold(a[(i, j)], |acc, k| acc - l[(i, k)] * l[(j, k)].conj());
+                l[(i, j)] = sum.unscale(ljj);
+            }
+        }
+        Some(Cholesky {
+            l,
+            norm_one: a.norm_one(),
+        })
+    }
+
+    /// Lower triangular factor with real positive diagonal
+    pub fn l(&self) -> &CMatrix<T> {
+        &self.l
+    }
+
+    pub fn solve(&self, b: &CVector<T>) -> CVector<T> {
+        let n = self.l.rows();
+        assert_eq!(b.len(), n, "right-hand side length does not match matrix");
+        let l = &self.l;
+        let mut x = b.clone();
+        for i in 0..n {
+            let sum = (0..i).fold(x[i], |acc, j| acc - l[(i, j)] * x[j]);
+            x[i] = sum.unscale(l[(i, i)].real);
+        }
+        for i in (0..n).rev() {
+            let sum = (i + 1..n).fold(x[i], |acc, j| acc - l[(j, i)].conj() * x[j]);
+            x[i] = sum.unscale(l[(i, i)].real);
+        }
+        x
+    }
+
+    /// Determinant, real and positive
+    pub fn det(&self) -> T {
+        (0..self.l.rows()).fold(T::one(), |acc, i| {
+            acc * self.l[(i, i)].real * self.l[(i, i)].real
+        })
+    }
+
+    pub fn inverse(&self) -> CMatrix<T> {
+        let n = self.l.rows();
+        let mut inverse = CMatrix::zeros(n, n);
+        for j in 0..n {
+            let mut e = CVector::zeros(n);
+            e[j] = one();
+            let column = self.solve(&e);
+            for i in 0..n {
+                inverse[(i, j)] = column[i];
+            }
+        }
+        inverse
+    }
+
+    /// Estimate of κ₁(A), see [`Lu::condition_estimate`]
+    pub fn condition_estimate(&self) -> T {
+        let n = self.l.rows();
+        self.norm_one * inverse_norm_estimate(n, |x| self.solve(x), |x| self.solve(x))
+    }
+}
@@ -0,0 +1,475 @@
+//! Digital IIR filter design from analog prototypes
+//!
+//! Prototypes are normalized lowpass filters in zero-pole-gain form with
+//! cutoff at 1 rad/s. They are moved to the requested band with frequency
+//! transformations and mapped to the z-plane by the bilinear transform.
+//! ```rust
+//! use ancomplex::design::{butter, Band};
+//! use ancomplex::filter::Filter;
+//!
+//! let zpk = butter(4, Band::Lowpass(100.0), 1000.0);
+//! let sos = zpk.to_sos();
+//! let omega = 2.0 * std::f64::consts::PI * 100.0 / 1000.0;
+//! let gain = sos.frequency_response(omega).norm();
+//! assert!((gain - 0.5f64.sqrt()).abs() < 1e-12);
+//! ```
+use crate::cast::from_f64;
+use crate::elliptic::{ellipf, ellipj, ellipk, theta2, theta3};
+use crate::filter::{Biquad, Sos};
+use crate::polynomial::Polynomial;
+use crate::{complex, Complex};
+use num_traits::{Float, FloatConst};
+
+fn real<T: Float>(x: T) -> Complex<T> {
+    complex(x, T::zero())
+}
+
+fn product<T: Float>(values: &[Complex<T>]) -> Complex<T> {
+    values.iter().fold(real(T::one()), |acc, &x| acc * x)
+}
+
+/// Filter in zero-pole-gain form H(s) = k·Π(s - zᵢ)/Π(s - pᵢ)
+#[derive(Debug, Clone, PartialEq)]
+pub struct Zpk<T> {
+    pub zeros: Vec<Complex<T>>,
+    pub poles: Vec<Complex<T>>,
+    pub gain: T,
+}
+
+/// Frequency band of a digital filter, edges are in the units of the sample rate
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Band<T> {
+    Lowpass(T),
+    Highpass(T),
+    /// Lower and upper edge
+    Bandpass(T, T),
+    /// Lower and upper edge
+    Bandstop(T, T),
+}
+
+impl<T: Float + FloatConst> Zpk<T> {
+    pub fn new(zeros: Vec<Complex<T>>, poles: Vec<Complex<T>>, gain: T) -> Self {
+        Zpk { zeros, poles, gain }
+    }
+
+    /// Evaluates transfer function at `s` (or `z` for digital filters)
+    pub fn response(&self, s: Complex<T>) -> Complex<T> {
+        let num = self
+            .zeros
+            .iter()
+            .fold(real(self.gain), |acc, &z| acc * (s - z));
+        self.poles.iter().fold(num, |acc, &p| acc / (s - p))
+    }
+
+    /// Number of poles in excess of zeros
+    fn degree(&self) -> usize {
+        self.poles.len().saturating_sub(self.zeros.len())
+    }
+
+    /// Lowpass to lowpass with cutoff `wo` rad/s
+    pub fn lp2lp(&self, wo: T) -> Self {
+        let scale = |v: &[Complex<T>]| v.iter().map(|&x| x * wo).collect();
+        Zpk {
+            zeros: scale(&self.zeros),
+            poles: scale(&self.poles),
+            gain: self.gain * wo.powi(self.degree() as i32),
+        }
+    }
+
+    /// Lowpass to highpass with cutoff `wo` rad/s
+    pub fn lp2hp(&self, wo: T) -> Self {
+        let invert = |v: &[Complex<T>]| v.iter().map(|&x| real(wo) / x).collect::<Vec<_>>();
+        let mut zeros = invert(&self.zeros);
+        zeros.resize(self.poles.len().max(self.zeros.len()), real(T::zero()));
+        let neg = |v: &[Complex<T>]| v.iter().map(|&x| -x).collect::<Vec<_>>();
+        let ratio = product(&neg(&self.zeros)) / product(&neg(&self.poles));
+        Zpk {
+            zeros,
+            poles: invert(&self.poles),
+            gain: self.gain * ratio.real,
+        }
+    }
+
+    /// Lowpass to bandpass with center `wo` rad/s and bandwidth `bw` rad/s
+    pub fn lp2bp(&self, wo: T, bw: T) -> Self {
+        let split = |v: &[Complex<T>]| {
+            let mut out = Vec::with_capacity(2 * v.len());
+            for &x in v {
+                let x = x * bw / from_f64::<T>(2.0);
+                let root = (x * x - wo * wo).sqrt();
+                out.push(x + root);
+                out.push(x - root);
+            }
+            out
+        };
+        let mut zeros = split(&self.zeros);
+        zeros.extend(vec![real(T::zero()); self.degree()]);
+        Zpk {
+            zeros,
+            poles: split(&self.poles),
+            gain: self.gain * bw.powi(self.degree() as i32),
+        }
+    }
+
+    /// Lowpass to bandstop with center `wo` rad/s and bandwidth `bw` rad/s
+    pub fn lp2bs(&self, wo: T, bw: T) -> Self {
+        let split = |v: &[Complex<T>]| {
+            let mut out = Vec::with_capacity(2 * v.len());
+            for &x in v {
+                let x = real(bw / from_f64::<T>(2.0)) / x;
+                let root = (x * x - wo * wo).sqrt();
+                out.push(x + root);
+                out.push(x - root);
+            }
+            out
+        };
+        let mut zeros = split(&self.zeros);
+        for _ in 0..self.degree() {
+            zeros.push(complex(T::zero(), wo));
+            zeros.push(complex(T::zero(), -wo));
+        }
+        let neg = |v: &[Complex<T>]| v.iter().map(|&x| -x).collect::<Vec<_>>();
+        let ratio = product(&neg(&self.zeros)) / product(&neg(&self.poles));
+        Zpk {
+            zeros,
+            poles: split(&self.poles),
+            gain: self.gain * ratio.real,
+        }
+    }
+
+    /// Bilinear transform s = 2fs·(z - 1)/(z + 1) to a digital filter
+    pub fn bilinear(&self, fs: T) -> Self {
+        let fs2 = real(fs + fs);
+        let map = |v: &[Complex<T>]| v.iter().map(|&x| (fs2 + x) / (fs2 - x)).collect();
+        let mut zeros: Vec<Complex<T>> = map(&self.zeros);
+        zeros.extend(vec![real(-T::one()); self.degree()]);
+        let shifted = |v: &[Complex<T>]| v.iter().map(|&x| fs2 - x).collect::<Vec<_>>();
+        let ratio = product(&shifted(&self.zeros)) / product(&shifted(&self.poles));
+        Zpk {
+            zeros,
+            poles: map(&self.poles),
+            gain: self.gain * ratio.real,
+        }
+    }
+
+    /// Second order sections of a digital filter
+    ///
+    /// Conjugate roots share a section, so real filters get real coefficients.
+    /// Poles closest to the unit circle go to the last section and each pole
+    /// pair takes the nearest remaining zeros.
+    pub fn to_sos(&self) -> Sos<T> {
+        let mut pole_groups = pair_roots(&self.poles);
+        let mut zero_groups = pair_roots(&self.zeros);
+        let sections = pole_groups.len().max(zero_groups.len()).max(1);
+        pole_groups.resize(sections, Vec::new());
+        zero_groups.resize(sections, Vec::new());
+        let distance = |group: &Vec<Complex<T>>| {
+            group
+                .iter()
+                .fold(T::infinity(), |acc, p| acc.min((T::one() - p.norm()).abs()))
+        };
+        // Farthest from unit circle first, the most resonant section ends the cascade
+        pole_groups.sort_by(|a, b| {
+            distance(b)
+                .partial_cmp(&distance(a))
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        let mut ordered_zeros = Vec::with_capacity(sections);
+        for poles in pole_groups.iter().rev() {
+            let target = poles.first().copied().unwrap_or_else(|| real(T::zero()));
+            let (index, _) = zero_groups.iter().enumerate().fold(
+                (0, T::infinity()),
+                |(best, best_distance), (k, group)| {
+                    let d = group
+                        .iter()
+                        .fold(T::infinity(), |acc, z| acc.min((*z - target).norm()));
+                    if d < best_distance {
+                        (k, d)
+                    } else {
+                        (best, best_distance)
+                    }
+                },
+            );
+            ordered_zeros.push(zero_groups.remove(index));
+        }
+        ordered_zeros.reverse();
+        let biquads = pole_groups
+            .iter()
+            .zip(&ordered_zeros)
+            .enumerate()
+            .map(|(k, (poles, zeros))| {
+                let mut b = section_polynomial(zeros);
+                if k == 0 {
+                    b = [b[0] * self.gain, b[1] * self.gain, b[2] * self.gain];
+                }
+                Biquad::new(b, section_polynomial(poles))
+            })
+            .collect();
+        Sos::new(biquads)
+    }
+}
+
+/// Groups roots into conjugate pairs, pairs of real roots and a leftover single root
+fn pair_roots<T: Float>(roots: &[Complex<T>]) -> Vec<Vec<Complex<T>>> {
+    let tol = from_f64::<T>(1e3) * T::epsilon();
+    let is_real = |z: &Complex<T>| z.imag.abs() <= tol * (T::one() + z.norm());
+    let mut reals: Vec<Complex<T>> = roots.iter().filter(|z| is_real(z)).copied().collect();
+    let mut complexes: Vec<Complex<T>> = roots.iter().filter(|z| !is_real(z)).copied().collect();
+    let mut groups = Vec::new();
+    while let Some(z) = complexes.pop() {
+        let partner =
+            complexes
+                .iter()
+                .enumerate()
+                .fold(None, |best: Option<(usize, T)>, (k, w)| {
+                    let d = (*w - z.conj()).norm();
+                    match best {
+                        Some((_, bd)) if bd <= d => best,
+                        _ => Some((k, d)),
+                    }
+                });
+        match partner {
+            Some((k, d)) if d <= tol.sqrt() * (T::one() + z.norm()) => {
+                let w = complexes.remove(k);
+                groups.push(vec![z, w]);
+            }
+            _ => groups.push(vec![z]),
+        }
+    }
+    reals.sort_by(|a, b| {
+        a.real
+            .partial_cmp(&b.real)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+    for pair in reals.chunks(2) {
+        groups.push(pair.iter().map(|z| real(z.real)).collect());
+    }
+    groups
+}
+
+/// Coefficients of Π(1 - rᵢ·z⁻¹) for up to two roots, real when roots are conjugate
+fn section_polynomial<T: Float>(roots: &[Complex<T>]) -> [Complex<T>; 3] {
+    let zero = real(T::zero());
+    match roots {
+        [] => [real(T::one()), zero, zero],
+        [r] => [real(T::one()), -*r, zero],
+        [r1, r2] => {
+            let (sum, prod) = (*r1 + *r2, *r1 * *r2);
+            if (*r1 - r2.conj()).norm()
+                <= (T::epsilon() * from_f64::<T>(1e6)) * (T::one() + r1.norm())
+            {
+                [real(T::one()), real(-sum.real), real(prod.real)]
+            } else {
+                [real(T::one()), -sum, prod]
+            }
+        }
+        _ => unreachable!("sections hold at most two roots"),
+    }
+}
+
+/// Analog Butterworth lowpass prototype of given order
+pub fn butter_prototype<T: Float + FloatConst>(order: usize) -> Zpk<T> {
+    let n = from_f64::<T>(order as f64);
+    let poles = (0..order)
+        .map(|k| {
+            let m = from_f64::<T>((2 * k + 1) as f64) - n;
+            -Complex::from_polar(T::one(), T::PI() * m / (n + n))
+        })
+        .collect();
+    Zpk::new(Vec::new(), poles, T::one())
+}
+
+/// Analog Chebyshev type I lowpass prototype with `ripple_db` passband ripple
+pub fn cheby1_prototype<T: Float + FloatConst>(order: usize, ripple_db: T) -> Zpk<T> {
+    let n = from_f64::<T>(order as f64);
+    let eps = (from_f64::<T>(10.0).powf(ripple_db / from_f64::<T>(10.0)) - T::one()).sqrt();
+    let mu = (T::one() / eps).asinh() / n;
+    let poles: Vec<Complex<T>> = (0..order)
+        .map(|k| {
+            let theta = T::PI() * (from_f64::<T>((2 * k + 1) as f64) - n) / (n + n);
+            -complex(mu, theta).sinh()
+        })
+        .collect();
+    let mut gain = product(&poles.iter().map(|&p| -p).collect::<Vec<_>>()).real;
+    if order % 2 == 0 {
+        gain = gain / (T::one() + eps * eps).sqrt();
+    }
+    Zpk::new(Vec::new(), poles, gain)
+}
+
+/// Analog Chebyshev type II lowpass prototype with `stopband_db` attenuation from 1 rad/s
+pub fn cheby2_prototype<T: Float + FloatConst>(order: usize, stopband_db: T) -> Zpk<T> {
+    let n = from_f64::<T>(order as f64);
+    let de =
+        T::one() / (from_f64::<T>(10.0).powf(stopband_db / from_f64::<T>(10.0)) - T::one()).sqrt();
+    let mu = (T::one() / de).asinh() / n;
+    let zeros: Vec<Complex<T>> = (0..order)
+        .filter(|&k| 2 * k + 1 != order)
+        .map(|k| {
+            let m = from_f64::<T>((2 * k + 1) as f64) - n;
+            complex(T::zero(), T::one() / (T::PI() * m / (n + n)).sin())
+        })
+        .collect();
+    let poles: Vec<Complex<T>> = (0..order)
+        .map(|k| {
+            let m = from_f64::<T>((2 * k + 1) as f64) - n;
+            let p = -Complex::from_polar(T::one(), T::PI() * m / (n + n));
+            complex(mu.sinh() * p.real, mu.cosh() * p.imag).inv()
+        })
+        .collect();
+    let neg = |v: &[Complex<T>]| v.iter().map(|&x| -x).collect::<Vec<_>>();
+    let gain = (product(&neg(&poles)) / product(&neg(&zeros))).real;
+    Zpk::new(zeros, poles, gain)
+}
+
+/// Analog elliptic (Cauer) lowpass prototype
+///
+/// Passband ends at 1 rad/s with `ripple_db` ripple, stopband has at least
+/// `stopband_db` attenuation.
+pub fn ellip_prototype<T: Float + FloatConst>(
+    order: usize,
+    ripple_db: T,
+    stopband_db: T,
+) -> Zpk<T> {
+    let ten = from_f64::<T>(10.0);
+    let eps = (ten.powf(ripple_db / ten) - T::one()).sqrt();
+    if order == 0 {
+        // Constant response at the bottom of the passband ripple, as for Chebyshev
+        return Zpk::new(
+            Vec::new(),
+            Vec::new(),
+            (T::one() + eps * eps).sqrt().recip(),
+        );
+    }
+    if order == 1 {
+        let p = -T::one() / eps;
+        return Zpk::new(Vec::new(), vec![real(p)], -p);
+    }
+    let n = from_f64::<T>(order as f64);
+    let k1 = eps / (ten.powf(stopband_db / ten) - T::one()).sqrt();
+    let k1p2 = T::one() - k1 * k1;
+    let k_ratio = n * ellipk(real(k1 * k1)).real / ellipk(real(k1p2)).real;
+    let m = selectivity_parameter(k_ratio);
+    let capk = ellipk(real(m)).real;
+    // Inverse Jacobi sc of 1/eps with the complementary parameter
+    let r = ellipf(real((T::one() / eps).atan()), real(k1p2)).real;
+    let v0 = capk * r / (n * ellipk(real(k1 * k1)).real);
+    let (sv, cv, dv) = ellipj(real(v0), T::one() - m);
+    let (sv, cv, dv) = (sv.real, cv.real, dv.real);
+    let mut zeros = Vec::new();
+    let mut poles = Vec::new();
+    for j in ((1 - order % 2)..order).step_by(2) {
+        let u = capk * from_f64::<T>(j as f64) / n;
+        let (s, c, d) = ellipj(real(u), m);
+        let (s, c, d) = (s.real, c.real, d.real);
+        if s.abs() > T::epsilon() {
+            let z = complex(T::zero(), T::one() / (m.sqrt() * s));
+            zeros.push(z);
+            zeros.push(z.conj());
+        }
+        let p = -complex(c * d * sv * cv, s * dv) / (T::one() - (d * sv) * (d * sv));
+        poles.push(p);
+        if p.imag.abs() > T::epsilon() * p.norm().max(T::one()) {
+            poles.push(p.conj());
+        }
+    }
+    let neg = |v: &[Complex<T>]| v.iter().map(|&x| -x).collect::<Vec<_>>();
+    let mut gain = (product(&neg(&poles)) / product(&neg(&zeros))).real;
+    if order % 2 == 0 {
+        gain = gain / (T::one() + eps * eps).sqrt();
+    }
+    Zpk::new(zeros, poles, gain)
+}
+
+/// Solves K(m)/K(1-m) = `ratio` for m through the nome q = e^(-π/ratio)
+fn selectivity_parameter<T: Float + FloatConst>(ratio: T) -> T {
+    let q = real((-T::PI() / ratio).exp());
+    let zero = real(T::zero());
+    let ratio = theta2(zero, q).real / theta3(zero, q).real;
+    ratio.powi(4)
+}
+
+/// Analog Bessel lowpass prototype with phase normalization
+///
+/// Poles are scaled so the high frequency asymptote matches Butterworth of
+/// the same order, putting the phase midpoint near 1 rad/s. The group delay is
+/// maximally flat.
+pub fn bessel_prototype<T: Float + FloatConst>(order: usize) -> Zpk<T> {
+    if order == 0 {
+        return Zpk::new(Vec::new(), Vec::new(), T::one());
+    }
+    // Reverse Bessel polynomial θ_n(s) = Σ (2n-k)!/(2^(n-k)·k!·(n-k)!)·sᵏ
+    let mut coeffs = vec![T::one(); order + 1];
+    for k in (0..order).rev() {
+        // a_k / a_(k+1) = (2n-k)(k+1) / (2(n-k)), starting from a_n = 1
+        let num = from_f64::<T>(((2 * order - k) * (k + 1)) as f64);
+        let den = from_f64::<T>((2 * (order - k)) as f64);
+        coeffs[k] = coeffs[k + 1] * num / den;
+    }
+    let scale = coeffs[0].powf(T::one() / from_f64::<T>(order as f64));
+    let poles = Polynomial::from_real(&coeffs)
+        .roots()
+        .into_iter()
+        .map(|p| p / scale)
+        .collect();
+    Zpk::new(Vec::new(), poles, T::one())
+}
+
+/// Prewarps digital frequency `f` so the bilinear transform maps it exactly
+///
+/// Returns analog angular frequency 2fs·tan(πf/fs).
+pub fn prewarp<T: Float + FloatConst>(f: T, fs: T) -> T {
+    (fs + fs) * (T::PI() * f / fs).tan()
+}
+
+/// Turns analog prototype into digital filter for `band` at sample rate `fs`
+pub fn digital_from_prototype<T: Float + FloatConst>(
+    prototype: &Zpk<T>,
+    band: Band<T>,
+    fs: T,
+) -> Zpk<T> {
+    let analog = match band {
+        Band::Lowpass(f) => prototype.lp2lp(prewarp(f, fs)),
+        Band::Highpass(f) => prototype.lp2hp(prewarp(f, fs)),
+        Band::Bandpass(low, high) => {
+            let (w1, w2) = (prewarp(low, fs), prewarp(high, fs));
+            prototype.lp2bp((w1 * w2).sqrt(), w2 - w1)
+        }
+        Band::Bandstop(low, high) => {
+            let (w1, w2) = (prewarp(low, fs), prewarp(high, fs));
+            prototype.lp2bs((w1 * w2).sqrt(), w2 - w1)
+        }
+    };
+    analog.bilinear(fs)
+}
+
+/// Digital Butterworth filter, band edges are at -3 dB
+pub fn butter<T: Float + FloatConst>(order: usize, band: Band<T>, fs: T) -> Zpk<T> {
+    digital_from_prototype(&butter_prototype(order), band, fs)
+}
+
+/// Digital Chebyshev type I filter, band edges are where the ripple band ends
+pub fn cheby1<T: Float + FloatConst>(order: usize, ripple_db: T, band: Band<T>, fs: T) -> Zpk<T> {
+    digital_from_prototype(&cheby1_prototype(order, ripple_db), band, fs)
+}
+
+/// Digital Chebyshev type II filter, band edges are where the stopband begins
+pub fn cheby2<T: Float + FloatConst>(order: usize, stopband_db: T, band: Band<T>, fs: T) -> Zpk<T> {
+    digital_from_prototype(&cheby2_prototype(order, stopband_db), band, fs)
+}
+
+/// Digital elliptic filter, band edges are where the passband ripple ends
+pub fn ellip<T: Float + FloatConst>(
+    order: usize,
+    ripple_db: T,
+    stopband_db: T,
+    band: Band<T>,
+    fs: T,
+) -> Zpk<T> {
+    digital_from_prototype(&ellip_prototype(order, ripple_db, stopband_db), band, fs)
+}
+
+/// Digital Bessel filter with phase normalization at the band edges
+pub fn bessel<T: Float + FloatConst>(order: usize, band: Band<T>, fs: T) -> Zpk<T> {
+    digital_from_prototype(&bessel_prototype(order), band, fs)
+}
@@ -0,0 +1,312 @@
+//! Complex-step differentiation and forward-mode dual numbers
+//!
+//! For a real function extended analytically to complex arguments,
+//! f'(x) = Im f(x + ih)/h + O(h²) with no subtractive cancellation, so h can
+//! be tiny and the derivative is exact to rounding. [`Dual`] propagates the
+//! Wirtinger derivatives ∂/∂z and ∂/∂z̄ through arithmetic, which also
+//! covers non-holomorphic expressions such as |z|².
+//! ```rust
+//! use ancomplex::*;
+//! use ancomplex::differentiation::{complex_step_derivative, Dual};
+//!
+//! // d/dx eˣ/√x at x = 2
+//! let slope = complex_step_derivative(|z: c64| z.exp() / z.sqrt(), 2.0);
+//! let exact = 2f64.exp() / 2f64.sqrt() * (1.0 - 0.25);
+//! assert!((slope - exact).abs() < 1e-15 * exact);
+//!
+//! // |z|² has ∂/∂z = z̄ and ∂/∂z̄ = z
+//! let z = Dual::variable(c64(1.0, 2.0));
+//! let energy = z * z.conj();
+//! assert_eq!(energy.dz, c64(1.0, -2.0));
+//! assert_eq!(energy.dzbar, c64(1.0, 2.0));
+//! ```
+use crate::{complex, Complex};
+use num_traits::Float;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+fn zero<T: Float>() -> Complex<T> {
+    complex(T::zero(), T::zero())
+}
+
+fn one<T: Float>() -> Complex<T> {
+    complex(T::one(), T::zero())
+}
+
+/// Step ε²·max(1, |x|), small enough that the O(h²) error is far below rounding
+fn step<T: Float>(x: T) -> T {
+    T::epsilon() * T::epsilon() * x.abs().max(T::one())
+}
+
+/// Derivative of real `f` at `x` as Im f(x + ih)/h
+///
+/// `f` must be the analytic continuation of a real function, built from
+/// operations that do not use `conj`, `norm` or the parts of its argument.
+pub fn complex_step_derivative<T, F>(f: F, x: T) -> T
+where
+    T: Float,
+    F: Fn(Complex<T>) -> Complex<T>,
+{
+    let h = step(x);
+    f(complex(x, h)).imag / h
+}
+
+/// Gradient of real `f` of several variables at `x` by complex steps
+pub fn complex_step_gradient<T, F>(f: F, x: &[T]) -> Vec<T>
+where
+    T: Float,
+    F: Fn(&[Complex<T>]) -> Complex<T>,
+{
+    let mut point: Vec<Complex<T>> = x.iter().map(|&v| complex(v, T::zero())).collect();
+    (0..x.len())
+        .map(|k| {
+            let h = step(x[k]);
+            point[k].imag = h;
+            let slope = f(&point).imag / h;
+            point[k].imag = T::zero();
+            slope
+        })
+        .collect()
+}
+
+/// Jacobian of real vector function `f` at `x` by complex steps, with
+/// `jacobian[i][k]` = ∂fᵢ/∂x_k
+pub fn complex_step_jacobian<T, F>(f: F, x: &[T]) -> Vec<Vec<T>>
+where
+    T: Float,
+    F: Fn(&[Complex<T>]) -> Vec<Complex<T>>,
+{
+    let mut point: Vec<Complex<T>> = x.iter().map(|&v| complex(v, T::zero())).collect();
+    let mut jacobian: Vec<Vec<T>> = vec![];
+    for k in 0..x.len() {
+        let h = step(x[k]);
+        point[k].imag = h;
+        let column = f(&point);
+        point[k].imag = T::zero();
+        if jacobian.is_empty() {
+            jacobian = vec![vec![T::zero(); x.len()]; column.len()];
+        }
+        for (row, value) in jacobian.iter_mut().zip(&column) {
+            row[k] = value.imag / h;
+        }
+    }
+    jacobian
+}
+
+/// Value of a function of one complex variable z with its Wirtinger
+/// derivatives ∂/∂z and ∂/∂z̄
+///
+/// Holomorphic expressions have `dzbar` = 0 and `dz` = f'(z). For a real
+/// valued f, `dzbar` is half the steepest ascent direction ∂f/∂x + i·∂f/∂y.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Dual<T> {
+    pub value: Complex<T>,
+    pub dz: Complex<T>,
+    pub dzbar: Complex<T>,
+}
+
+impl<T: Float> Dual<T> {
+    /// The independent variable z
+    pub fn variable(z: Complex<T>) -> Self {
+        Dual {
+            value: z,
+            dz: one(),
+            dzbar: zero(),
+        }
+    }
+
+    pub fn constant(c: Complex<T>) -> Self {
+        Dual {
+            value: c,
+            dz: zero(),
+            dzbar: zero(),
+        }
+    }
+
+    /// Gradient ∂f/∂x + i·∂f/∂y = 2·∂f/∂z̄ of a real valued function
+    pub fn gradient(&self) -> Complex<T> {
+        self.dzbar + self.dzbar
+    }
+
+    /// g(self) for holomorphic g with value `value` and derivative `slope`
+    fn chain(&self, value: Complex<T>, slope: Complex<T>) -> Self {
+        Dual {
+            value,
+            dz: slope * self.dz,
+            dzbar: slope * self.dzbar,
+        }
+    }
+
+    /// Complex conjugate, which swaps and conjugates the two derivatives
+    pub fn conj(&self) -> Self {
+        Dual {
+            value: self.value.conj(),
+            dz: self.dzbar.conj(),
+            dzbar: self.dz.conj(),
+        }
+    }
+
+    /// |f|² as f·f̄
+    pub fn square_norm(&self) -> Self {
+        *self * self.conj()
+    }
+
+    /// |f|, not differentiable where f = 0
+    pub fn norm(&self) -> Self {
+        let magnitude = self.value.norm();
+        let square = self.square_norm();
+        let scale = T::one() / (magnitude + magnitude);
+        Dual {
+            value: complex(magnitude, T::zero()),
+            dz: square.dz.scale(scale),
+            dzbar: square.dzbar.scale(scale),
+        }
+    }
+
+    pub fn exp(&self) -> Self {
+        let e = self.value.exp();
+        self.chain(e, e)
+    }
+
+    pub fn ln(&self) -> Self {
+        self.chain(self.value.ln(), self.value.inv())
+    }
+
+    pub fn sqrt(&self) -> Self {
+        let root = self.value.sqrt();
+        self.chain(root, (root + root).inv())
+    }
+
+    pub fn sin(&self) -> Self {
+        self.chain(self.value.sin(), self.value.cos())
+    }
+
+    pub fn cos(&self) -> Self {
+        self.chain(self.value.cos(), -self.value.sin())
+    }
+
+    pub fn tan(&self) -> Self {
+        let t = self.value.tan();
+        self.chain(t, one::<T>() + t * t)
+    }
+
+    pub fn sinh(&self) -> Self {
+        self.chain(self.value.sinh(), self.value.cosh())
+    }
+
+    pub fn cosh(&self) -> Self {
+        self.chain(self.value.cosh(), self.value.sinh())
+    }
+
+    pub fn tanh(&self) -> Self {
+        let t = self.value.tanh();
+        self.chain(t, one::<T>() - t * t)
+    }
+
+    pub fn powi(&self, n: i32) -> Self {
+        if n == 0 {
+            return Self::constant(one());
+        }
+        let power = self.value.powi(n - 1);
+        let slope = power * complex(T::from(n).expect("exponent fits in float"), T::zero());
+        self.chain(power * self.value, slope)
+    }
+
+    /// Principal power with complex exponent, f^p = e^(p·ln f)
+    pub fn powc(&self, p: Complex<T>) -> Self {
+        (self.ln() * Self::constant(p)).exp()
+    }
+
+    pub fn inv(&self) -> Self {
+        let r = self.value.inv();
+        self.chain(r, -r * r)
+    }
+}
+
+impl<T: Float> From<Complex<T>> for Dual<T> {
+    fn from(c: Complex<T>) -> Self {
+        Dual::constant(c)
+    }
+}
+
+impl<T: Float> Add for Dual<T> {
+    type Output = Dual<T>;
+
+    fn add(self, rhs: Dual<T>) -> Dual<T> {
+        Dual {
+            value: self.value + rhs.value,
+            dz: self.dz + rhs.dz,
+            dzbar: self.dzbar + rhs.dzbar,
+        }
+    }
+}
+
+impl<T: Float> Sub for Dual<T> {
+    type Output = Dual<T>;
+
+    fn sub(self, rhs: Dual<T>) -> Dual<T> {
+        Dual {
+            value: self.value - rhs.value,
+            dz: self.dz - rhs.dz,
+            dzbar: self.dzbar - rhs.dzbar,
+        }
+    }
+}
+
+impl<T: Float> Mul for Dual<T> {
+    type Output = Dual<T>;
+
+    fn mul(self, rhs: Dual<T>) -> Dual<T> {
+        Dual {
+            value: self.value * rhs.value,
+            dz: self.dz * rhs.value + self.value * rhs.dz,
+            dzbar: self.dzbar * rhs.value + self.value * rhs.dzbar,
+        }
+    }
+}
+
+impl<T: Float> Div for Dual<T> {
+    type Output = Dual<T>;
+
+    /// d(u/v) = (du - (u/v)·dv)/v
+    fn div(self, rhs: Dual<T>) -> Dual<T> {
+        let value = self.value / rhs.value;
+        Dual {
+            value,
+            dz: (self.dz - value * rhs.dz) / rhs.value,
+            dzbar: (self.dzbar - value * rhs.dzbar) / rhs.value,
+        }
+    }
+}
+
+impl<T: Float> Neg for Dual<T> {
+    type Output = Dual<T>;
+
+    fn neg(self) -> Dual<T> {
+        Dual {
+            value: -self.value,
+            dz: -self.dz,
+            dzbar: -self.dzbar,
+        }
+    }
+}
+
+macro_rules! forward_constant_ops {
+    ($($imp:ident, $method:ident;)*) => {
+        $(
+            impl<T: Float> $imp<Complex<T>> for Dual<T> {
+                type Output = Dual<T>;
+
+                fn $method(self, rhs: Complex<T>) -> Dual<T> {
+                    self.$method(Dual::constant(rhs))
+                }
+            }
+        )*
+    };
+}
+
+forward_constant_ops! {
+    Add, add;
+    Sub, sub;
+    Mul, mul;
+    Div, div;
+}
@@ -0,0 +1,476 @@
+//! Eigenvalue and singular value decompositions of complex matrices
+//!
+//! General matrices are reduced to Hessenberg form and then to complex
+//! Schur form by shifted QR iteration. Hermitian matrices and the SVD use
+//! Jacobi rotations, which give small eigenvalues and singular values to
+//! high relative accuracy.
+//! ```rust
+//! use ancomplex::*;
+//! use ancomplex::matrix::CMatrix;
+//!
+//! // Pauli Y has eigenvalues ±1
+//! let y = CMatrix::new(2, 2, vec![c64(0.0, 0.0), c64(0.0, -1.0), c64(0.0, 1.0), c64(0.0, 0.0)]);
+//! let eigen = y.eigh();
+//! assert!((eigen.values[0] + 1.0).abs() < 1e-15);
+//! assert!((eigen.values[1] - 1.0).abs() < 1e-15);
+//! ```
+use crate::cast::from_f64;
+use crate::matrix::CMatrix;
+use crate::{complex, Complex};
+use num_traits::Float;
+
+/// Iterations allowed per eigenvalue before QR iteration gives up
+const MAX_QR_ITERATIONS: usize = 60;
+const MAX_SWEEPS: usize = 60;
+
+fn zero<T: Float>() -> Complex<T> {
+    complex(T::zero(), T::zero())
+}
+
+/// z/|z|, or 1 for zero
+fn phase<T: Float>(z: Complex<T>) -> Complex<T> {
+    let r = z.norm();
+    if r == T::zero() {
+        complex(T::one(), T::zero())
+    } else {
+        z.unscale(r)
+    }
+}
+
+/// A = QTQᴴ with unitary Q and upper triangular T
+#[derive(Debug, Clone)]
+pub struct Schur<T> {
+    pub q: CMatrix<T>,
+    pub t: CMatrix<T>,
+}
+
+/// Eigenvalues with unit-norm eigenvectors stored as matrix columns
+#[derive(Debug, Clone)]
+pub struct Eigen<T> {
+    pub values: Vec<Complex<T>>,
+    pub vectors: CMatrix<T>,
+}
+
+/// Real eigenvalues in ascending order with orthonormal eigenvectors as columns
+#[derive(Debug, Clone)]
+pub struct HermitianEigen<T> {
+    pub values: Vec<T>,
+    pub vectors: CMatrix<T>,
+}
+
+/// Thin singular value decomposition A = U·diag(σ)·Vᴴ
+///
+/// For m×n matrix with k = min(m, n), U is m×k, V is n×k and the singular
+/// values are in descending order.
+#[derive(Debug, Clone)]
+pub struct Svd<T> {
+    pub u: CMatrix<T>,
+    pub singular_values: Vec<T>,
+    pub v: CMatrix<T>,
+}
+
+impl<T: Float> CMatrix<T> {
+    /// Complex Schur decomposition
+    ///
+    /// Returns `None` when the QR iteration does not converge, as for
+    /// matrices with non-finite entries.
+    pub fn schur(&self) -> Option<Schur<T>> {
+        assert!(self.is_square(), "Schur decomposition of non-square matrix");
+        let mut t = self.clone();
+        let mut q = CMatrix::identity(self.rows());
+        hessenberg(&mut t, &mut q);
+        if !shifted_qr(&mut t, &mut q) {
+            return None;
+        }
+        Some(Schur { q, t })
+    }
+
+    /// Eigenvalues in the order they appear on the Schur diagonal, `None`
+    /// when [`CMatrix::schur`] fails
+    pub fn eigenvalues(&self) -> Option<Vec<Complex<T>>> {
+        Some(self.schur()?.t.diagonal().into_vec())
+    }
+
+    /// Eigenvalues and eigenvectors of general square matrix, `None` when
+    /// [`CMatrix::schur`] fails
+    pub fn eig(&self) -> Option<Eigen<T>> {
+        let Schur { q, t } = self.schur()?;
+        let n = t.rows();
+        let tiny = t.frobenius_norm().max(T::min_positive_value()) * T::epsilon();
+        let mut vectors = CMatrix::zeros(n, n);
+        for k in 0..n {
+            let lambda = t[(k, k)];
+            // Back substitution for (T - λI)·y = 0 with y_k = 1
+            let mut y = vec![zero(); k + 1];
+            y[k] = complex(T::one(), T::zero());
+            for i in (0..k).rev() {
+                let sum = (i + 1..=k).fold(zero(), |acc, j| acc + t[(i, j)] * y[j]);
+                let mut pivot = t[(i, i)] - lambda;
+                if pivot.norm() < tiny {
+                    pivot = complex(tiny, T::zero());
+                }
+                y[i] = -sum / pivot;
+            }
+            let mut x: Vec<Complex<T>> = (0..n)
+                .map(|i| (0..=k).fold(zero(), |acc, j| acc + q[(i, j)] * y[j]))
+                .collect();
+            let norm = x
+                .iter()
+                .fold(T::zero(), |acc, v| acc + v.square_norm())
+                .sqrt();
+            for v in x.iter_mut() {
+                *v = v.unscale(norm);
+            }
+            for (i, v) in x.into_iter().enumerate() {
+                vectors[(i, k)] = v;
+            }
+        }
+        Some(Eigen {
+            values: t.diagonal().into_vec(),
+            vectors,
+        })
+    }
+
+    /// Eigen-decomposition of Hermitian matrix by cyclic Jacobi rotations
+    ///
+    /// Only the Hermitian part (A + Aᴴ)/2 is used.
+    pub fn eigh(&self) -> HermitianEigen<T> {
+        assert!(self.is_square(), "eigen-decomposition of non-square matrix");
+        let n = self.rows();
+        let half = T::one() / (T::one() + T::one());
+        let mut a = CMatrix::from_fn(n, n, |i, j| {
+            (self[(i, j)] + self[(j, i)].conj()).scale(half)
+        });
+        let mut v = CMatrix::identity(n);
+        let scale = a.frobenius_norm();
+        for _ in 0..MAX_SWEEPS {
+            let off = (0..n).fold(T::zero(), |acc, i| {
+                (0..n)
+                    .filter(|&j| j != i)
+                    .fold(acc, |acc, j| acc + a[(i, j)].square_norm())
+            });
+            if off.sqrt() <= T::epsilon() * scale {
+                break;
+            }
+            for p in 0..n {
+                for q in p + 1..n {
+                    let apq = a[(p, q)];
+                    if apq.norm() == T::zero() {
+                        continue;
+                    }
+                    let (c, s, e) = jacobi_rotation(a[(p, p)].real, a[(q, q)].real, apq);
+                    // A ← Uᴴ·A·U with U = [[c, s], [-s·e⁻ⁱᵠ, c·e⁻ⁱᵠ]] on (p, q)
+                    rotate_columns(&mut a, p, q, c, s, e);
+                    rotate_rows(&mut a, p, q, c, s, e);
+                    rotate_columns(&mut v, p, q, c, s, e);
+                    a[(p, q)] = zero();
+                    a[(q, p)] = zero();
+                    a[(p, p)] = complex(a[(p, p)].real, T::zero());
+                    a[(q, q)] = complex(a[(q, q)].real, T::zero());
+                }
+            }
+        }
+        let mut order: Vec<usize> = (0..n).collect();
+        order.sort_by(|&i, &j| {
+            a[(i, i)]
+                .real
+                .partial_cmp(&a[(j, j)].real)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        HermitianEigen {
+            values: order.iter().map(|&i| a[(i, i)].real).collect(),
+            vectors: CMatrix::from_fn(n, n, |i, j| v[(i, order[j])]),
+        }
+    }
+
+    /// Singular value decomposition by one-sided Jacobi rotations
+    pub fn svd(&self) -> Svd<T> {
+        if self.rows() < self.cols() {
+            let Svd {
+                u,
+                singular_values,
+                v,
+            } = self.hermitian().svd();
+            return Svd {
+                u: v,
+                singular_values,
+                v: u,
+            };
+        }
+        let (m, n) = (self.rows(), self.cols());
+        let mut u = self.clone();
+        let mut v = CMatrix::identity(n);
+        for _ in 0..MAX_SWEEPS {
+            let mut rotated = false;
+            for p in 0..n {
+                for q in p + 1..n {
+                    let (mut alpha, mut beta, mut gamma) = (T::zero(), T::zero(), zero());
+                    for i in 0..m {
+                        alpha = alpha + u[(i, p)].square_norm();
+                        beta = beta + u[(i, q)].square_norm();
+                        gamma = gamma + u[(i, p)].conj() * u[(i, q)];
+                    }
+                    if gamma.norm() <= T::epsilon() * (alpha * beta).sqrt()
+                        || gamma.norm() == T::zero()
+                    {
+                        continue;
+                    }
+                    rotated = true;
+                    let (c, s, e) = jacobi_rotation(alpha, beta, gamma);
+                    rotate_columns(&mut u, p, q, c, s, e);
+                    rotate_columns(&mut v, p, q, c, s, e);
+                }
+            }
+            if !rotated {
+                break;
+            }
+        }
+        let norms: Vec<T> = (0..n)
+            .map(|j| {
+                (0..m)
+                    .fold(T::zero(), |acc, i| acc + u[(i, j)].square_norm())
+                    .sqrt()
+            })
+            .collect();
+        let mut order: Vec<usize> = (0..n).collect();
+        order.sort_by(|&i, &j| {
+            norms[j]
+                .partial_cmp(&norms[i])
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        let largest = norms.iter().fold(T::zero(), |acc, &x| acc.max(x));
+        let cutoff = largest * T::epsilon() * from_f64::<T>(m as f64);
+        let mut left = CMatrix::zeros(m, n);
+        for (k, &j) in order.iter().enumerate() {
+            if norms[j] > cutoff {
+                for i in 0..m {
+                    left[(i, k)] = u[(i, j)].unscale(norms[j]);
+                }
+            } else {
+                complete_column(&mut left, k);
+            }
+        }
+        Svd {
+            u: left,
+            singular_values: order.iter().map(|&j| norms[j]).collect(),
+            v: CMatrix::from_fn(n, n, |i, k| v[(i, order[k])]),
+        }
+    }
+}
+
+/// Rotation (c, s, e^(iφ)) zeroing the off-diagonal entry `apq` = |apq|·e^(iφ)
+/// of Hermitian 2×2 block [[app, apq], [conj(apq), aqq]]
+fn jacobi_rotation<T: Float>(app: T, aqq: T, apq: Complex<T>) -> (T, T, Complex<T>) {
+    let magnitude = apq.norm();
+    let tau = (aqq - app) / (magnitude + magnitude);
+    let t = T::one().copysign(tau) / (tau.abs() + (T::one() + tau * tau).sqrt());
+    let c = T::one() / (T::one() + t * t).sqrt();
+    (c, t * c, phase(apq))
+}
+
+/// M ← M·U for U = [[c, s], [-s·ē, c·ē]] acting on columns p and q
+fn rotate_columns<T: Float>(m: &mut CMatrix<T>, p: usize, q: usize, c: T, s: T, e: Complex<T>) {
+    let e_conj = e.conj();
+    for k in 0..m.rows() {
+        let (x, y) = (m[(k, p)], m[(k, q)]);
+        m[(k, p)] = x.scale(c) - (y * e_conj).scale(s);
+        m[(k, q)] = x.scale(s) + (y * e_conj).scale(c);
+    }
+}
+
+/// M ← Uᴴ·M for the rotation of [`rotate_columns`], acting on rows p and q
+fn rotate_rows<T: Float>(m: &mut CMatrix<T>, p: usize, q: usize, c: T, s: T, e: Complex<T>) {
+    for k in 0..m.cols() {
+        let (x, y) = (m[(p, k)], m[(q, k)]);
+        m[(p, k)] = x.scale(c) - (y * e).scale(s);
+        m[(q, k)] = x.scale(s) + (y * e).scale(c);
+    }
+}
+
+/// Fills column `k` with a unit vector orthogonal to columns before it
+fn complete_column<T: Float>(m: &mut CMatrix<T>, k: usize) {
+    let rows = m.rows();
+    for candidate in 0..rows {
+        let mut x = vec![zero(); rows];
+        x[candidate] = complex(T::one(), T::zero());
+        for j in 0..k {
+            let dot = (0..rows).fold(zero(), |acc, i| acc + m[(i, j)].conj() * x[i]);
+            for (i, xi) in x.iter_mut().enumerate() {
+                *xi = *xi - m[(i, j)] * dot;
+            }
+        }
+        let norm = x
+            .iter()
+            .fold(T::zero(), |acc, v| acc + v.square_norm())
+            .sqrt();
+        if norm > T::one() / (T::one() + T::one()) {
+            for (i, xi) in x.into_iter().enumerate() {
+                m[(i, k)] = xi.unscale(norm);
+            }
+            return;
+        }
+    }
+}
+
+/// Reduces `a` to upper Hessenberg form by Householder reflections, accumulated into `q`
+fn hessenberg<T: Float>(a: &mut CMatrix<T>, q: &mut CMatrix<T>) {
+    let n = a.rows();
+    for k in 0..n.saturating_sub(2) {
+        let mut v: Vec<Complex<T>> = (k + 1..n).map(|i| a[(i, k)]).collect();
+        let norm = v
+            .iter()
+            .fold(T::zero(), |acc, x| acc + x.square_norm())
+            .sqrt();
+        if norm == T::zero() {
+            continue;
+        }
+        let alpha = -phase(v[0]).scale(norm);
+        v[0] = v[0] - alpha;
+        let vv = v.iter().fold(T::zero(), |acc, x| acc + x.square_norm());
+        if vv == T::zero() {
+            continue;
+        }
+        let two = T::one() + T::one();
+        // Left: A ← H·A on rows k+1..n
+        for j in 0..n {
+            let dot = v
+                .iter()
+                .enumerate()
+                .fold(zero(), |acc, (i, vi)| acc + vi.conj() * a[(k + 1 + i, j)]);
+            let factor = dot.scale(two / vv);
+            for (i, vi) in v.iter().enumerate() {
+                a[(k + 1 + i, j)] = a[(k + 1 + i, j)] - *vi * factor;
+            }
+        }
+        // Right: A ← A·H and Q ← Q·H on columns k+1..n
+        for m in [&mut *a, &mut *q] {
+            for i in 0..n {
+                let dot = v
+                    .iter()
+                    .enumerate()
+                    .fold(zero(), |acc, (j, vj)| acc + m[(i, k + 1 + j)] * *vj);
+                let factor = dot.scale(two / vv);
+                for (j, vj) in v.iter().enumerate() {
+                    m[(i, k + 1 + j)] = m[(i, k + 1 + j)] - factor * vj.conj();
+                }
+            }
+        }
+        for i in k + 2..n {
+            a[(i, k)] = zero();
+        }
+    }
+}
+
+/// Complex Givens rotation G = [[c, s], [-s̄, c]] with G·[x, y]ᵀ = [r, 0]ᵀ
+fn givens<T: Float>(x: Complex<T>, y: Complex<T>) -> (T, Complex<T>) {
+    let (ax, ay) = (x.norm(), y.norm());
+    if ay == T::zero() {
+        return (T::one(), zero());
+    }
+    if ax == T::zero() {
+        return (T::zero(), phase(y).conj());
+    }
+    let rho = ax.hypot(ay);
+    (ax / rho, (phase(x) * y.conj()).unscale(rho))
+}
+
+/// Single-shift QR iteration driving Hessenberg `h` to upper triangular form,
+/// returns false if some eigenvalue did not converge
+fn shifted_qr<T: Float>(h: &mut CMatrix<T>, q: &mut CMatrix<T>) -> bool {
+    let n = h.rows();
+    if n == 0 {
+        return true;
+    }
+    let mut hi = n - 1;
+    let mut iterations = 0;
+    while hi > 0 {
+        // Deflate at the lowest negligible subdiagonal entry
+        let mut lo = hi;
+        while lo > 0 {
+            let scale = h[(lo - 1, lo - 1)].l1_norm() + h[(lo, lo)].l1_norm();
+            if h[(lo, lo - 1)].l1_norm() <= T::epsilon() * scale
+                || h[(lo, lo - 1)].norm() < T::min_positive_value()
+            {
+                h[(lo, lo - 1)] = zero();
+                break;
+            }
+            lo -= 1;
+        }
+        if lo == hi {
+            hi -= 1;
+            iterations = 0;
+            continue;
+        }
+        iterations += 1;
+        if iterations > MAX_QR_ITERATIONS {
+            return false;
+        }
+        let shift = if iterations % 11 == 0 {
+            // Exceptional shift breaks cycles of the Wilkinson shift
+            h[(hi, hi)] + complex(h[(hi, hi - 1)].norm(), T::zero())
+        } else {
+            wilkinson_shift(
+                h[(hi - 1, hi - 1)],
+                h[(hi - 1, hi)],
+                h[(hi, hi - 1)],
+                h[(hi, hi)],
+            )
+        };
+        let mut x = h[(lo, lo)] - shift;
+        let mut y = h[(lo + 1, lo)];
+        for k in lo..hi {
+            let (c, s) = givens(x, y);
+            // Rows k, k+1 from the left
+            for j in (if k > lo { k - 1 } else { lo })..n {
+                let (a, b) = (h[(k, j)], h[(k + 1, j)]);
+                h[(k, j)] = a.scale(c) + s * b;
+                h[(k + 1, j)] = b.scale(c) - s.conj() * a;
+            }
+            if k > lo {
+                h[(k + 1, k - 1)] = zero();
+            }
+            // Columns k, k+1 from the right, on the block and the accumulated basis
+            let last = (k + 2).min(hi);
+            for i in 0..=last {
+                let (a, b) = (h[(i, k)], h[(i, k + 1)]);
+                h[(i, k)] = a.scale(c) + b * s.conj();
+                h[(i, k + 1)] = b.scale(c) - a * s;
+            }
+            for i in 0..n {
+                let (a, b) = (q[(i, k)], q[(i, k + 1)]);
+                q[(i, k)] = a.scale(c) + b * s.conj();
+                q[(i, k + 1)] = b.scale(c) - a * s;
+            }
+            if k + 1 < hi {
+                x = h[(k + 1, k)];
+                y = h[(k + 2, k)];
+            }
+        }
+    }
+    // Subdiagonal entries were zeroed on deflation, clear the rounding below
+    for j in 0..n {
+        for i in j + 2..n {
+            h[(i, j)] = zero();
+        }
+    }
+    true
+}
+
+/// Eigenvalue of [[a, b], [c, d]] closer to d
+fn wilkinson_shift<T: Float>(
+    a: Complex<T>,
+    b: Complex<T>,
+    c: Complex<T>,
+    d: Complex<T>,
+) -> Complex<T> {
+    let half = T::one() / (T::one() + T::one());
+    let mean = (a + d).scale(half);
+    let diff = (a - d).scale(half);
+    let root = (diff * diff + b * c).sqrt();
+    let (l1, l2) = (mean + root, mean - root);
+    if (l1 - d).norm() <= (l2 - d).norm() {
+        l1
+    } else {
+        l2
+    }
+}
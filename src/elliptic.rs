@@ -0,0 +1,366 @@
+//! Elliptic integrals, Jacobi elliptic functions and theta functions
+//!
+//! Legendre forms are computed from Carlson symmetric integrals, which stay
+//! valid for complex arguments. Theta functions take the nome `q` and
+//! panic unless |q| < 1, where their series converge.
+//! ```rust
+//! use ancomplex::*;
+//! use ancomplex::elliptic::*;
+//!
+//! let k = ellipk(c64(0.5, 0.0));
+//! let (sn, cn, dn) = ellipj(c64(0.3, 0.2), 0.5);
+//! assert!((sn * sn + cn * cn - c64(1.0, 0.0)).norm() < 1e-12);
+//! assert!((dn * dn + sn * sn * 0.5 - c64(1.0, 0.0)).norm() < 1e-12);
+//! assert!((k.real - 1.854_074_677_301_372).abs() < 1e-12);
+//! ```
+use crate::cast::from_f64;
+use crate::{complex, Complex};
+use num_traits::{Float, FloatConst};
+
+/// Upper bound for duplication steps and series terms
+const MAX_ITERATIONS: usize = 1_000;
+
+fn real<T: Float>(x: T) -> Complex<T> {
+    complex(x, T::zero())
+}
+
+/// Relative size of the remainder at which duplication can stop
+fn duplication_tolerance<T: Float>() -> T {
+    (T::epsilon() / from_f64::<T>(4.0)).powf(from_f64::<T>(1.0 / 6.0))
+}
+
+/// Carlson symmetric integral of the first kind
+///
+/// R_F(x, y, z) = ½∫₀^∞ dt / √((t+x)(t+y)(t+z))
+pub fn rf<T: Float>(x: Complex<T>, y: Complex<T>, z: Complex<T>) -> Complex<T> {
+    let (mut x, mut y, mut z) = (x, y, z);
+    let quarter = from_f64::<T>(0.25);
+    let tol = duplication_tolerance::<T>();
+    let mut a = (x + y + z) / from_f64::<T>(3.0);
+    for _ in 0..MAX_ITERATIONS {
+        let (dx, dy, dz) = (
+            real(T::one()) - x / a,
+            real(T::one()) - y / a,
+            real(T::one()) - z / a,
+        );
+        if dx.norm().max(dy.norm()).max(dz.norm()) < tol {
+            break;
+        }
+        let (sx, sy, sz) = (x.sqrt(), y.sqrt(), z.sqrt());
+        let lambda = sx * sy + sy * sz + sz * sx;
+        x = (x + lambda) * quarter;
+        y = (y + lambda) * quarter;
+        z = (z + lambda) * quarter;
+        a = (a + lambda) * quarter;
+    }
+    let dx = real(T::one()) - x / a;
+    let dy = real(T::one()) - y / a;
+    let dz = -(dx + dy);
+    let e2 = dx * dy - dz * dz;
+    let e3 = dx * dy * dz;
+    let series = real(T::one()) - e2 / from_f64::<T>(10.0)
+        + e3 / from_f64::<T>(14.0)
+        + e2 * e2 / from_f64::<T>(24.0)
+        - e2 * e3 * from_f64::<T>(3.0 / 44.0);
+    series / a.sqrt()
+}
+
+/// Degenerate Carlson integral R_C(x, y) = R_F(x, y, y)
+pub fn rc<T: Float>(x: Complex<T>, y: Complex<T>) -> Complex<T> {
+    rf(x, y, y)
+}
+
+/// Carlson symmetric integral of the second kind
+///
+/// R_D(x, y, z) = 3/2∫₀^∞ dt / ((t+z)√((t+x)(t+y)(t+z)))
+pub fn rd<T: Float>(x: Complex<T>, y: Complex<T>, z: Complex<T>) -> Complex<T> {
+    let (mut x, mut y, mut z) = (x, y, z);
+    let quarter = from_f64::<T>(0.25);
+    let tol = duplication_tolerance::<T>();
+    let mut a = (x + y + z * from_f64::<T>(3.0)) / from_f64::<T>(5.0);
+    let mut sum = real(T::zero());
+    let mut factor = T::one();
+    for _ in 0..MAX_ITERATIONS {
+        let (dx, dy, dz) = (
+            real(T::one()) - x / a,
+            real(T::one()) - y / a,
+            real(T::one()) - z / a,
+        );
+        if dx.norm().max(dy.norm()).max(dz.norm()) < tol {
+            break;
+        }
+        let (sx, sy, sz) = (x.sqrt(), y.sqrt(), z.sqrt());
+        let lambda = sx * sy + sy * sz + sz * sx;
+        sum = sum + real(factor) / (sz * (z + lambda));
+        factor = factor * quarter;
+        x = (x + lambda) * quarter;
+        y = (y + lambda) * quarter;
+        z = (z + lambda) * quarter;
+        a = (a + lambda) * quarter;
+    }
+    let dx = real(T::one()) - x / a;
+    let dy = real(T::one()) - y / a;
+    let dz = -(dx + dy) / from_f64::<T>(3.0);
+    let xy = dx * dy;
+    let z2 = dz * dz;
+    let e2 = xy - z2 * from_f64::<T>(6.0);
+    let e3 = (xy * from_f64::<T>(3.0) - z2 * from_f64::<T>(8.0)) * dz;
+    let e4 = (xy - z2) * z2 * from_f64::<T>(3.0);
+    let e5 = xy * z2 * dz;
+    let series = carlson_series(e2, e3, e4, e5);
+    sum * from_f64::<T>(3.0) + series * factor / (a * a.sqrt())
+}
+
+/// Carlson symmetric integral of the third kind
+///
+/// R_J(x, y, z, p) = 3/2∫₀^∞ dt / ((t+p)√((t+x)(t+y)(t+z)))
+pub fn rj<T: Float>(x: Complex<T>, y: Complex<T>, z: Complex<T>, p: Complex<T>) -> Complex<T> {
+    let (mut x, mut y, mut z, mut p) = (x, y, z, p);
+    let quarter = from_f64::<T>(0.25);
+    let tol = duplication_tolerance::<T>();
+    let mut a = (x + y + z + p * from_f64::<T>(2.0)) / from_f64::<T>(5.0);
+    let delta = (p - x) * (p - y) * (p - z);
+    let mut sum = real(T::zero());
+    let mut factor = T::one();
+    for _ in 0..MAX_ITERATIONS {
+        let deviation = [x, y, z, p].iter().fold(T::zero(), |acc, &v| {
+            acc.max((real(T::one()) - v / a).norm())
+        });
+        if deviation < tol {
+            break;
+        }
+        let (sx, sy, sz, sp) = (x.sqrt(), y.sqrt(), z.sqrt(), p.sqrt());
+        let lambda = sx * sy + sy * sz + sz * sx;
+        let d = (sp + sx) * (sp + sy) * (sp + sz);
+        let e = delta * factor * factor * factor / (d * d);
+        sum = sum + rc(real(T::one()), e + T::one()) * factor / d;
+        factor = factor * quarter;
+        x = (x + lambda) * quarter;
+        y = (y + lambda) * quarter;
+        z = (z + lambda) * quarter;
+        p = (p + lambda) * quarter;
+        a = (a + lambda) * quarter;
+    }
+    let dx = real(T::one()) - x / a;
+    let dy = real(T::one()) - y / a;
+    let dz = real(T::one()) - z / a;
+    let dp = -(dx + dy + dz) / from_f64::<T>(2.0);
+    let p2 = dp * dp;
+    let xyz = dx * dy * dz;
+    let e2 = dx * dy + dx * dz + dy * dz - p2 * from_f64::<T>(3.0);
+    let e3 = xyz + e2 * dp * from_f64::<T>(2.0) + p2 * dp * from_f64::<T>(4.0);
+    let e4 = (xyz * from_f64::<T>(2.0) + e2 * dp + p2 * dp * from_f64::<T>(3.0)) * dp;
+    let e5 = xyz * p2;
+    let series = carlson_series(e2, e3, e4, e5);
+    sum * from_f64::<T>(6.0) + series * factor / (a * a.sqrt())
+}
+
+/// Truncated series shared by R_D and R_J
+fn carlson_series<T: Float>(
+    e2: Complex<T>,
+    e3: Complex<T>,
+    e4: Complex<T>,
+    e5: Complex<T>,
+) -> Complex<T> {
+    real(T::one()) - e2 * from_f64::<T>(3.0 / 14.0)
+        + e3 / from_f64::<T>(6.0)
+        + e2 * e2 * from_f64::<T>(9.0 / 88.0)
+        - e4 * from_f64::<T>(3.0 / 22.0)
+        - e2 * e3 * from_f64::<T>(9.0 / 52.0)
+        + e5 * from_f64::<T>(3.0 / 26.0)
+}
+
+/// Complete elliptic integral of the first kind K(m), `m` is the parameter (k²)
+pub fn ellipk<T: Float>(m: Complex<T>) -> Complex<T> {
+    rf(real(T::zero()), real(T::one()) - m, real(T::one()))
+}
+
+/// Complete elliptic integral of the second kind E(m)
+pub fn ellipe<T: Float>(m: Complex<T>) -> Complex<T> {
+    let (zero, one) = (real(T::zero()), real(T::one()));
+    rf(zero, one - m, one) - m * rd(zero, one - m, one) / from_f64::<T>(3.0)
+}
+
+/// Incomplete elliptic integral of the first kind F(φ | m)
+pub fn ellipf<T: Float + FloatConst>(phi: Complex<T>, m: Complex<T>) -> Complex<T> {
+    let (phi, periods) = reduce_amplitude(phi);
+    let (s, c) = (phi.sin(), phi.cos());
+    let one = real(T::one());
+    let partial = s * rf(c * c, one - m * s * s, one);
+    if periods == T::zero() {
+        partial
+    } else {
+        partial + ellipk(m) * (periods + periods)
+    }
+}
+
+/// Incomplete elliptic integral of the second kind E(φ | m)
+pub fn ellipe_inc<T: Float + FloatConst>(phi: Complex<T>, m: Complex<T>) -> Complex<T> {
+    let (phi, periods) = reduce_amplitude(phi);
+    let (s, c) = (phi.sin(), phi.cos());
+    let one = real(T::one());
+    let (c2, delta2) = (c * c, one - m * s * s);
+    let partial =
+        s * rf(c2, delta2, one) - m * s * s * s * rd(c2, delta2, one) / from_f64::<T>(3.0);
+    if periods == T::zero() {
+        partial
+    } else {
+        partial + ellipe(m) * (periods + periods)
+    }
+}
+
+/// Shifts amplitude into |Re φ| ≤ π/2, returning the number of removed half-periods
+fn reduce_amplitude<T: Float + FloatConst>(phi: Complex<T>) -> (Complex<T>, T) {
+    let periods = (phi.real / T::PI()).round();
+    (phi - periods * T::PI(), periods)
+}
+
+/// Jacobi elliptic functions (sn, cn, dn) of complex argument `u` and real parameter `m`
+pub fn ellipj<T: Float + FloatConst>(u: Complex<T>, m: T) -> (Complex<T>, Complex<T>, Complex<T>) {
+    let one = T::one();
+    if m > one {
+        // Reciprocal parameter transformation
+        let k = m.sqrt();
+        let (sn, cn, dn) = ellipj_unit(u * k, one / m);
+        return (sn / k, dn, cn);
+    }
+    if m < T::zero() {
+        // Imaginary parameter transformation to μ = -m/(1-m)
+        let k = (one - m).sqrt();
+        let (sn, cn, dn) = ellipj_unit(u * k, -m / (one - m));
+        return (sn / (dn * k), cn / dn, dn.inv());
+    }
+    ellipj_unit(u, m)
+}
+
+/// Jacobi sn(u | m)
+pub fn sn<T: Float + FloatConst>(u: Complex<T>, m: T) -> Complex<T> {
+    ellipj(u, m).0
+}
+
+/// Jacobi cn(u | m)
+pub fn cn<T: Float + FloatConst>(u: Complex<T>, m: T) -> Complex<T> {
+    ellipj(u, m).1
+}
+
+/// Jacobi dn(u | m)
+pub fn dn<T: Float + FloatConst>(u: Complex<T>, m: T) -> Complex<T> {
+    ellipj(u, m).2
+}
+
+/// Jacobi functions for 0 ≤ m ≤ 1 using the addition theorem on real and imaginary parts
+fn ellipj_unit<T: Float>(u: Complex<T>, m: T) -> (Complex<T>, Complex<T>, Complex<T>) {
+    let (s, c, d) = ellipj_real(u.real, m);
+    if u.imag == T::zero() {
+        return (real(s), real(c), real(d));
+    }
+    // Jacobi imaginary transformation gives sn(iy|m) from functions of y with 1-m
+    let (s1, c1, d1) = ellipj_real(u.imag, T::one() - m);
+    let denom = c1 * c1 + m * s * s * s1 * s1;
+    let sn = complex(s * d1, c * d * s1 * c1) / denom;
+    let cn = complex(c * c1, -s * d * s1 * d1) / denom;
+    let dn = complex(d * c1 * d1, -m * s * c * s1) / denom;
+    (sn, cn, dn)
+}
+
+/// Jacobi functions of real argument by the arithmetic-geometric mean
+fn ellipj_real<T: Float>(u: T, m: T) -> (T, T, T) {
+    let one = T::one();
+    if m < T::epsilon() {
+        return (u.sin(), u.cos(), one);
+    }
+    if one - m < T::epsilon() {
+        let sech = one / u.cosh();
+        return (u.tanh(), sech, sech);
+    }
+    let two = from_f64::<T>(2.0);
+    let mut a = vec![one];
+    let mut c = vec![m.sqrt()];
+    let mut b = (one - m).sqrt();
+    while c[c.len() - 1].abs() > T::epsilon() && a.len() < MAX_ITERATIONS {
+        let last = a[a.len() - 1];
+        c.push((last - b) / two);
+        a.push((last + b) / two);
+        b = (last * b).sqrt();
+    }
+    let n = a.len() - 1;
+    let mut phi = from_f64::<T>((1u64 << n) as f64) * a[n] * u;
+    let mut previous = phi;
+    for k in (1..=n).rev() {
+        previous = phi;
+        phi = (phi + (c[k] / a[k] * phi.sin()).asin()) / two;
+    }
+    (phi.sin(), phi.cos(), phi.cos() / (previous - phi).cos())
+}
+
+/// Sums q^(ν²)·term(n, z) with ν = n or n+½ until the terms become negligible
+fn theta_series<T: Float, F>(
+    q: Complex<T>,
+    z: Complex<T>,
+    half_integer: bool,
+    term: F,
+) -> Complex<T>
+where
+    F: Fn(usize, Complex<T>) -> Complex<T>,
+{
+    assert!(
+        q.norm() < T::one(),
+        "theta functions need a nome with |q| < 1"
+    );
+    let growth = z.imag.abs();
+    let q2 = q * q;
+    // q^(ν²) by repeated multiplication, so that q = 0 needs no logarithm
+    let (mut weight, mut step) = if half_integer {
+        (q.sqrt().sqrt(), q2)
+    } else {
+        (real(T::one()), q)
+    };
+    let mut sum = real(T::zero());
+    for n in 0..MAX_ITERATIONS {
+        let index = if half_integer {
+            from_f64::<T>(n as f64 + 0.5)
+        } else {
+            from_f64::<T>(n as f64)
+        };
+        let bound = weight.norm() * (growth * (index + index)).exp();
+        sum = sum + weight * term(n, z);
+        weight *= step;
+        step *= q2;
+        if n > 0 && bound <= T::epsilon() * sum.norm() {
+            break;
+        }
+    }
+    sum
+}
+
+/// Jacobi theta function ϑ₁(z, q) = 2Σ (-1)ⁿ q^((n+½)²) sin((2n+1)z)
+pub fn theta1<T: Float>(z: Complex<T>, q: Complex<T>) -> Complex<T> {
+    theta_series(q, z, true, |n, z| {
+        let sign = if n % 2 == 0 { T::one() } else { -T::one() };
+        (z * from_f64::<T>((2 * n + 1) as f64)).sin() * sign
+    }) * from_f64::<T>(2.0)
+}
+
+/// Jacobi theta function ϑ₂(z, q) = 2Σ q^((n+½)²) cos((2n+1)z)
+pub fn theta2<T: Float>(z: Complex<T>, q: Complex<T>) -> Complex<T> {
+    theta_series(q, z, true, |n, z| {
+        (z * from_f64::<T>((2 * n + 1) as f64)).cos()
+    }) * from_f64::<T>(2.0)
+}
+
+/// Jacobi theta function ϑ₃(z, q) = 1 + 2Σ qⁿ² cos(2nz)
+pub fn theta3<T: Float>(z: Complex<T>, q: Complex<T>) -> Complex<T> {
+    theta_series(q, z, false, |n, z| {
+        let scale = if n == 0 { 0.5 } else { 1.0 };
+        (z * from_f64::<T>((2 * n) as f64)).cos() * from_f64::<T>(scale)
+    }) * from_f64::<T>(2.0)
+}
+
+/// Jacobi theta function ϑ₄(z, q) = 1 + 2Σ (-1)ⁿ qⁿ² cos(2nz)
+pub fn theta4<T: Float>(z: Complex<T>, q: Complex<T>) -> Complex<T> {
+    theta_series(q, z, false, |n, z| {
+        let scale = if n == 0 { 0.5 } else { 1.0 };
+        let sign = if n % 2 == 0 { scale } else { -scale };
+        (z * from_f64::<T>((2 * n) as f64)).cos() * from_f64::<T>(sign)
+    }) * from_f64::<T>(2.0)
+}
@@ -0,0 +1,312 @@
+//! Closed-form roots of quadratic, cubic and quartic equations
+//!
+//! Coefficients may be real or complex. When all of them are real, real
+//! roots come out with exactly zero imaginary part and complex roots as exact
+//! conjugate pairs.
+//! ```rust
+//! use ancomplex::*;
+//! use ancomplex::equations::{solve_cubic, solve_quadratic};
+//!
+//! let [x1, x2]: [c64; 2] = solve_quadratic(1.0, -1e8, 1.0);
+//! assert_eq!((x1.imag, x2.imag), (0.0, 0.0));
+//! assert!((x2.real - 1e-8).abs() < 1e-22);
+//!
+//! // (x - i)³
+//! let roots = solve_cubic(c64(1.0, 0.0), c64(0.0, -3.0), c64(-3.0, 0.0), c64(0.0, 1.0));
+//! assert!(roots.iter().all(|x| (*x - c64(0.0, 1.0)).norm() < 1e-4));
+//! ```
+use crate::cast::from_f64;
+use crate::{complex, Complex};
+use num_traits::{Float, FloatConst};
+
+fn real<T: Float>(x: T) -> Complex<T> {
+    complex(x, T::zero())
+}
+
+fn is_real<T: Float>(coeffs: &[Complex<T>]) -> bool {
+    coeffs.iter().all(|c| c.imag == T::zero())
+}
+
+/// Principal cube root
+fn cbrt<T: Float>(z: Complex<T>) -> Complex<T> {
+    let (r, theta) = z.to_polar();
+    Complex::from_polar(r.cbrt(), theta / from_f64::<T>(3.0))
+}
+
+/// Newton steps on monic polynomial with ascending coefficients, kept while the residual drops
+fn polish<T: Float>(monic: &[Complex<T>], mut x: Complex<T>) -> Complex<T> {
+    let eval = |x: Complex<T>| {
+        monic
+            .iter()
+            .rev()
+            .fold((real(T::zero()), real(T::zero())), |(p, dp), &c| {
+                (p * x + c, dp * x + p)
+            })
+    };
+    let (mut p, mut dp) = eval(x);
+    for _ in 0..4 {
+        if p.norm() == T::zero() || dp.norm() == T::zero() {
+            break;
+        }
+        let candidate = x - p / dp;
+        let (cp, cdp) = eval(candidate);
+        if cp.norm() >= p.norm() {
+            break;
+        }
+        x = candidate;
+        p = cp;
+        dp = cdp;
+    }
+    x
+}
+
+/// Same as [`polish`] for real root of real monic polynomial
+fn polish_real<T: Float>(monic: &[T], mut x: T) -> T {
+    let eval = |x: T| {
+        monic
+            .iter()
+            .rev()
+            .fold((T::zero(), T::zero()), |(p, dp), &c| {
+                (p * x + c, dp * x + p)
+            })
+    };
+    let (mut p, mut dp) = eval(x);
+    for _ in 0..4 {
+        if p == T::zero() || dp == T::zero() {
+            break;
+        }
+        let candidate = x - p / dp;
+        let (cp, cdp) = eval(candidate);
+        if cp.abs() >= p.abs() {
+            break;
+        }
+        x = candidate;
+        p = cp;
+        dp = cdp;
+    }
+    x
+}
+
+/// Roots of a·x² + b·x + c = 0
+///
+/// The larger root comes from -(b ± √Δ)/2 with the sign that avoids
+/// cancellation and the other one from Vieta's c/q (citardauq formula).
+/// Panics if `a` is zero.
+pub fn solve_quadratic<T: Float, C: Into<Complex<T>>>(a: C, b: C, c: C) -> [Complex<T>; 2] {
+    let (a, b, c) = (a.into(), b.into(), c.into());
+    assert!(a.norm() > T::zero(), "leading coefficient is zero");
+    if is_real(&[a, b, c]) {
+        return quadratic_real(a.real, b.real, c.real);
+    }
+    let d = (b * b - a * c * from_f64::<T>(4.0)).sqrt();
+    // Sign of the square root that makes |b ± d| largest
+    let sum = if (b.conj() * d).real >= T::zero() {
+        b + d
+    } else {
+        b - d
+    };
+    let q = -sum / from_f64::<T>(2.0);
+    if q.norm() == T::zero() {
+        return [q, q];
+    }
+    [q / a, c / q]
+}
+
+fn quadratic_real<T: Float>(a: T, b: T, c: T) -> [Complex<T>; 2] {
+    let two = from_f64::<T>(2.0);
+    let disc = b.mul_add(b, -from_f64::<T>(4.0) * a * c);
+    if disc < T::zero() {
+        let re = -b / (two * a);
+        let im = (-disc).sqrt() / (two * a).abs();
+        return [complex(re, im), complex(re, -im)];
+    }
+    let q = -(b + disc.sqrt().copysign(b)) / two;
+    if q == T::zero() {
+        return [real(T::zero()); 2];
+    }
+    [real(q / a), real(c / q)]
+}
+
+/// Roots of a·x³ + b·x² + c·x + d = 0
+///
+/// Real coefficients use the trigonometric form when all three roots are
+/// real and Cardano's formula otherwise. Complex coefficients use Cardano
+/// with the cube root taken from the larger of the two candidates. Panics if
+/// `a` is zero.
+pub fn solve_cubic<T: Float + FloatConst, C: Into<Complex<T>>>(
+    a: C,
+    b: C,
+    c: C,
+    d: C,
+) -> [Complex<T>; 3] {
+    let (a, b, c, d) = (a.into(), b.into(), c.into(), d.into());
+    assert!(a.norm() > T::zero(), "leading coefficient is zero");
+    if is_real(&[a, b, c, d]) {
+        return cubic_real(b.real / a.real, c.real / a.real, d.real / a.real);
+    }
+    let (b, c, d) = (b / a, c / a, d / a);
+    let three = from_f64::<T>(3.0);
+    let delta0 = b * b - c * three;
+    let delta1 =
+        b * b * b * from_f64::<T>(2.0) - b * c * from_f64::<T>(9.0) + d * from_f64::<T>(27.0);
+    let root = (delta1 * delta1 - delta0 * delta0 * delta0 * from_f64::<T>(4.0)).sqrt();
+    let sum = if (delta1.conj() * root).real >= T::zero() {
+        delta1 + root
+    } else {
+        delta1 - root
+    };
+    let cc = cbrt(sum / from_f64::<T>(2.0));
+    let monic = [d, c, b, real(T::one())];
+    let xi = Complex::from_polar(T::one(), (T::PI() + T::PI()) / three);
+    let mut roots = [real(T::zero()); 3];
+    let mut rotation = real(T::one());
+    for root in roots.iter_mut() {
+        let x = if cc.norm() == T::zero() {
+            -b / three
+        } else {
+            let u = rotation * cc;
+            -(b + u + delta0 / u) / three
+        };
+        *root = polish(&monic, x);
+        rotation *= xi;
+    }
+    roots
+}
+
+/// Roots of monic real cubic x³ + b·x² + c·x + d
+fn cubic_real<T: Float + FloatConst>(b: T, c: T, d: T) -> [Complex<T>; 3] {
+    let three = from_f64::<T>(3.0);
+    let shift = b / three;
+    let q = (b * b - three * c) / from_f64::<T>(9.0);
+    let r = (from_f64::<T>(2.0) * b * b * b - from_f64::<T>(9.0) * b * c + from_f64::<T>(27.0) * d)
+        / from_f64::<T>(54.0);
+    let monic = [d, c, b, T::one()];
+    let q3 = q * q * q;
+    if r * r < q3 {
+        let theta = (r / q3.sqrt()).max(-T::one()).min(T::one()).acos();
+        let scale = -from_f64::<T>(2.0) * q.sqrt();
+        let two_pi = T::PI() + T::PI();
+        let mut roots = [real(T::zero()); 3];
+        for (k, root) in roots.iter_mut().enumerate() {
+            let angle = (theta + two_pi * from_f64::<T>(k as f64)) / three;
+            *root = real(polish_real(&monic, scale * angle.cos() - shift));
+        }
+        return roots;
+    }
+    let big = -(r.abs() + (r * r - q3).sqrt()).cbrt().copysign(r);
+    let small = if big == T::zero() { T::zero() } else { q / big };
+    let x1 = polish_real(&monic, big + small - shift);
+    let re = -(big + small) / from_f64::<T>(2.0) - shift;
+    let im = three.sqrt() / from_f64::<T>(2.0) * (big - small);
+    if im == T::zero() {
+        // Double root, both remaining roots coincide on the real axis
+        return [real(x1), real(re), real(re)];
+    }
+    [real(x1), complex(re, im.abs()), complex(re, -im.abs())]
+}
+
+/// Roots of a·x⁴ + b·x³ + c·x² + d·x + e = 0
+///
+/// The depressed quartic is split into two quadratics by Ferrari's method,
+/// using the resolvent cubic root of largest magnitude. Real coefficients
+/// always have a positive resolvent root, which keeps both quadratics real.
+/// Panics if `a` is zero.
+pub fn solve_quartic<T: Float + FloatConst, C: Into<Complex<T>>>(
+    a: C,
+    b: C,
+    c: C,
+    d: C,
+    e: C,
+) -> [Complex<T>; 4] {
+    let (a, b, c, d, e) = (a.into(), b.into(), c.into(), d.into(), e.into());
+    assert!(a.norm() > T::zero(), "leading coefficient is zero");
+    if is_real(&[a, b, c, d, e]) {
+        let (a, b, c, d, e) = (a.real, b.real, c.real, d.real, e.real);
+        return quartic_real(b / a, c / a, d / a, e / a);
+    }
+    let (b, c, d, e) = (b / a, c / a, d / a, e / a);
+    let f = |x: f64| from_f64::<T>(x);
+    let shift = b / f(4.0);
+    let b2 = b * b;
+    let p = c - b2 * f(3.0 / 8.0);
+    let q = d - b * c / f(2.0) + b2 * b / f(8.0);
+    let r = e - b * d / f(4.0) + b2 * c / f(16.0) - b2 * b2 * f(3.0 / 256.0);
+    let monic = [e, d, c, b, real(T::one())];
+    let ys = if q.norm() <= T::epsilon() * (p.norm() + r.norm()) {
+        let [z1, z2] = solve_quadratic(real(T::one()), p, r);
+        let (s1, s2) = (z1.sqrt(), z2.sqrt());
+        [s1, -s1, s2, -s2]
+    } else {
+        let resolvent = solve_cubic(
+            real(f(8.0)),
+            p * f(8.0),
+            p * p * f(2.0) - r * f(8.0),
+            -q * q,
+        );
+        let m = resolvent.iter().copied().fold(real(T::zero()), |best, m| {
+            if m.norm() > best.norm() {
+                m
+            } else {
+                best
+            }
+        });
+        let s = (m * f(2.0)).sqrt();
+        let half = p / f(2.0) + m;
+        let t = q / (s * f(2.0));
+        let [y1, y2] = solve_quadratic(real(T::one()), -s, half + t);
+        let [y3, y4] = solve_quadratic(real(T::one()), s, half - t);
+        [y1, y2, y3, y4]
+    };
+    let mut roots = [real(T::zero()); 4];
+    for (root, y) in roots.iter_mut().zip(ys) {
+        *root = polish(&monic, y - shift);
+    }
+    roots
+}
+
+/// Roots of monic real quartic x⁴ + b·x³ + c·x² + d·x + e
+fn quartic_real<T: Float + FloatConst>(b: T, c: T, d: T, e: T) -> [Complex<T>; 4] {
+    let f = |x: f64| from_f64::<T>(x);
+    let shift = b / f(4.0);
+    let b2 = b * b;
+    let p = c - f(3.0 / 8.0) * b2;
+    let q = d - b * c / f(2.0) + b2 * b / f(8.0);
+    let r = e - b * d / f(4.0) + b2 * c / f(16.0) - f(3.0 / 256.0) * b2 * b2;
+    let monic = [e, d, c, b, T::one()];
+    let ys = if q.abs() <= T::epsilon() * (p.abs() + r.abs()) {
+        // Biquadratic y⁴ + p·y² + r
+        let [z1, z2] = quadratic_real(T::one(), p, r);
+        let root = |z: Complex<T>| {
+            if z.imag == T::zero() && z.real >= T::zero() {
+                real(z.real.sqrt())
+            } else if z.imag == T::zero() {
+                complex(T::zero(), (-z.real).sqrt())
+            } else {
+                z.sqrt()
+            }
+        };
+        let (s1, s2) = (root(z1), root(z2));
+        [s1, -s1, s2, -s2]
+    } else {
+        let resolvent = cubic_real(p, (p * p) / f(4.0) - r, -(q * q) / f(8.0));
+        let m = resolvent
+            .iter()
+            .filter(|m| m.imag == T::zero())
+            .fold(T::zero(), |best, m| best.max(m.real));
+        let s = (f(2.0) * m).sqrt();
+        let half = p / f(2.0) + m;
+        let t = q / (f(2.0) * s);
+        let [y1, y2] = quadratic_real(T::one(), -s, half + t);
+        let [y3, y4] = quadratic_real(T::one(), s, half - t);
+        [y1, y2, y3, y4]
+    };
+    let mut roots = [real(T::zero()); 4];
+    for (root, y) in roots.iter_mut().zip(ys) {
+        *root = if y.imag == T::zero() {
+            real(polish_real(&monic, y.real - shift))
+        } else {
+            complex(y.real - shift, y.imag)
+        };
+    }
+    roots
+}
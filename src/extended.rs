@@ -0,0 +1,223 @@
+//! Extended complex plane ℂ ∪ {∞} and the Riemann sphere
+//!
+//! Arithmetic follows the rules of the extended plane: z/0 = ∞ for z ≠ 0,
+//! z/∞ = 0, z + ∞ = ∞ and z·∞ = ∞ for z ≠ 0. The indeterminate forms
+//! ∞ ± ∞, 0·∞, 0/0 and ∞/∞ give a finite value with NaN components, like
+//! the corresponding float operations.
+//! ```rust
+//! use ancomplex::*;
+//! use ancomplex::extended::Extended;
+//!
+//! let z = Extended::Finite(c64(1.0, 1.0));
+//! let zero = Extended::Finite(c64(0.0, 0.0));
+//! assert_eq!(z / zero, Extended::Infinity);
+//! assert_eq!(z / Extended::Infinity, zero);
+//!
+//! // ∞ is the north pole, the unit circle is the equator
+//! assert_eq!(Extended::<f64>::Infinity.to_sphere(), [0.0, 0.0, 1.0]);
+//! let [_, _, height] = Extended::Finite(c64(0.0, 1.0)).to_sphere();
+//! assert_eq!(height, 0.0);
+//! assert_eq!(Extended::Finite(c64(0.0, 0.0)).chordal_distance(Extended::Infinity), 2.0);
+//! ```
+use crate::{complex, Complex};
+use num_traits::Float;
+use std::fmt::{self, Display};
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+/// Point of the extended complex plane
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Extended<T> {
+    Finite(Complex<T>),
+    Infinity,
+}
+
+use Extended::{Finite, Infinity};
+
+fn is_zero<T: Float>(z: Complex<T>) -> bool {
+    z.real == T::zero() && z.imag == T::zero()
+}
+
+fn indeterminate<T: Float>() -> Extended<T> {
+    Finite(complex(T::nan(), T::nan()))
+}
+
+impl<T: Float> Extended<T> {
+    pub fn is_infinite(&self) -> bool {
+        matches!(self, Infinity)
+    }
+
+    pub fn is_finite(&self) -> bool {
+        matches!(self, Finite(_))
+    }
+
+    /// True for the results of indeterminate forms
+    pub fn is_nan(&self) -> bool {
+        match self {
+            Finite(z) => z.real.is_nan() || z.imag.is_nan(),
+            Infinity => false,
+        }
+    }
+
+    /// The finite value, `None` at infinity
+    pub fn finite(&self) -> Option<Complex<T>> {
+        match *self {
+            Finite(z) => Some(z),
+            Infinity => None,
+        }
+    }
+
+    /// 1/z with 1/0 = ∞ and 1/∞ = 0
+    pub fn recip(&self) -> Self {
+        match *self {
+            Finite(z) if is_zero(z) => Infinity,
+            Finite(z) => Finite(z.inv()),
+            Infinity => Finite(complex(T::zero(), T::zero())),
+        }
+    }
+
+    /// Inverse stereographic projection onto the unit sphere in ℝ³ from the
+    /// north pole, z ↦ (2x, 2y, |z|² - 1)/(|z|² + 1)
+    pub fn to_sphere(&self) -> [T; 3] {
+        match *self {
+            Finite(z) => {
+                let r2 = z.square_norm();
+                if r2.is_infinite() {
+                    return [T::zero(), T::zero(), T::one()];
+                }
+                let scale = T::one() / (r2 + T::one());
+                [
+                    (z.real + z.real) * scale,
+                    (z.imag + z.imag) * scale,
+                    (r2 - T::one()) * scale,
+                ]
+            }
+            Infinity => [T::zero(), T::zero(), T::one()],
+        }
+    }
+
+    /// Stereographic projection of a point on the unit sphere, the north
+    /// pole (0, 0, 1) maps to ∞. The point is not renormalized.
+    pub fn from_sphere(point: [T; 3]) -> Self {
+        let [x, y, z] = point;
+        if z > T::zero() {
+            // (x + iy)/(1 - z) = (1 + z)/(x - iy) avoids cancellation near the pole
+            let denominator = complex(x, -y);
+            if is_zero(denominator) {
+                return Infinity;
+            }
+            Finite(complex(T::one() + z, T::zero()) / denominator)
+        } else {
+            Finite(complex(x, y).unscale(T::one() - z))
+        }
+    }
+
+    /// Euclidean distance between the images on the unit sphere, in [0, 2]
+    pub fn chordal_distance(&self, other: Self) -> T {
+        let two = T::one() + T::one();
+        match (*self, other) {
+            (Finite(z), Finite(w)) => {
+                let (rz, rw) = (z.norm(), w.norm());
+                // 2|z - w| / √((1 + |z|²)(1 + |w|²)), scaled to stay finite for huge z, w
+                two * (z - w).norm() / (T::one().hypot(rz) * T::one().hypot(rw))
+            }
+            (Finite(z), Infinity) | (Infinity, Finite(z)) => two / T::one().hypot(z.norm()),
+            (Infinity, Infinity) => T::zero(),
+        }
+    }
+}
+
+impl<T: Float> From<Complex<T>> for Extended<T> {
+    /// Complex numbers with an infinite component become ∞
+    fn from(z: Complex<T>) -> Self {
+        if z.real.is_infinite() || z.imag.is_infinite() {
+            Infinity
+        } else {
+            Finite(z)
+        }
+    }
+}
+
+impl<T: Float> From<Option<Complex<T>>> for Extended<T> {
+    /// `None` becomes ∞, the convention of [`crate::mobius`]
+    fn from(z: Option<Complex<T>>) -> Self {
+        z.map_or(Infinity, Extended::from)
+    }
+}
+
+impl<T: Float> From<Extended<T>> for Option<Complex<T>> {
+    fn from(z: Extended<T>) -> Self {
+        z.finite()
+    }
+}
+
+impl<T: Float> Add for Extended<T> {
+    type Output = Extended<T>;
+
+    fn add(self, rhs: Extended<T>) -> Extended<T> {
+        match (self, rhs) {
+            (Finite(a), Finite(b)) => Finite(a + b),
+            (Infinity, Infinity) => indeterminate(),
+            _ => Infinity,
+        }
+    }
+}
+
+impl<T: Float> Sub for Extended<T> {
+    type Output = Extended<T>;
+
+    fn sub(self, rhs: Extended<T>) -> Extended<T> {
+        self + -rhs
+    }
+}
+
+impl<T: Float> Mul for Extended<T> {
+    type Output = Extended<T>;
+
+    fn mul(self, rhs: Extended<T>) -> Extended<T> {
+        match (self, rhs) {
+            (Finite(a), Finite(b)) => Finite(a * b),
+            (Finite(z), Infinity) | (Infinity, Finite(z)) if is_zero(z) => indeterminate(),
+            _ => Infinity,
+        }
+    }
+}
+
+impl<T: Float> Div for Extended<T> {
+    type Output = Extended<T>;
+
+    fn div(self, rhs: Extended<T>) -> Extended<T> {
+        match (self, rhs) {
+            (Finite(a), Finite(b)) if is_zero(b) => {
+                if is_zero(a) {
+                    indeterminate()
+                } else {
+                    Infinity
+                }
+            }
+            (Finite(a), Finite(b)) => Finite(a / b),
+            (Finite(_), Infinity) => Finite(complex(T::zero(), T::zero())),
+            (Infinity, Finite(_)) => Infinity,
+            (Infinity, Infinity) => indeterminate(),
+        }
+    }
+}
+
+impl<T: Float> Neg for Extended<T> {
+    type Output = Extended<T>;
+
+    fn neg(self) -> Extended<T> {
+        match self {
+            Finite(z) => Finite(-z),
+            Infinity => Infinity,
+        }
+    }
+}
+
+impl<T: fmt::Debug + Clone> Display for Extended<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Finite(z) => write!(f, "{}", z),
+            Infinity => write!(f, "∞"),
+        }
+    }
+}
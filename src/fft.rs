@@ -0,0 +1,555 @@
+//! Fast Fourier Transform over slices of `Complex<T>`
+//!
+//! Lengths made of small factors use a mixed radix algorithm with radix-2 and
+//! radix-4 butterflies, other lengths go through Bluestein's algorithm.
+//! The inverse transform is scaled by `1/n`, so `inverse(forward(x)) == x`.
+//! ```rust
+//! use ancomplex::*;
+//! use ancomplex::fft::FftPlan;
+//!
+//! let plan = FftPlan::new(4);
+//! let mut data = vec![c64(1.0, 0.0), c64(2.0, 0.0), c64(3.0, 0.0), c64(4.0, 0.0)];
+//! plan.forward(&mut data);
+//! assert_eq!(data[0], c64(10.0, 0.0));
+//! plan.inverse(&mut data);
+//! assert!((data[3] - c64(4.0, 0.0)).norm() < 1e-12);
+//! ```
+use crate::cast::from_f64;
+use crate::{complex, Complex};
+use num_traits::{Float, FloatConst};
+
+/// Largest prime handled by the generic butterfly, bigger ones use Bluestein
+const MAX_DIRECT_RADIX: usize = 13;
+
+/// Precomputed Fast Fourier Transform of a fixed length
+#[derive(Debug, Clone)]
+pub struct FftPlan<T> {
+    len: usize,
+    algorithm: Algorithm<T>,
+}
+
+#[derive(Debug, Clone)]
+enum Algorithm<T> {
+    /// Recursive decimation in time over `(radix, remaining length)` pairs
+    MixedRadix {
+        factors: Vec<(usize, usize)>,
+        twiddles: Vec<Complex<T>>,
+    },
+    /// Chirp convolution through a power of two transform
+    Bluestein {
+        inner: Box<FftPlan<T>>,
+        chirp: Vec<Complex<T>>,
+        kernel: Vec<Complex<T>>,
+    },
+}
+
+/// e^(-2πi·k/n)
+pub(crate) fn root_of_unity<T: Float + FloatConst>(k: usize, n: usize) -> Complex<T> {
+    let theta = -(T::PI() + T::PI()) * from_f64::<T>(k as f64) / from_f64::<T>(n as f64);
+    Complex::from_polar(T::one(), theta)
+}
+
+/// Splits `n` into radices, preferring 4, then 2, then odd primes
+fn factorize(mut n: usize) -> Vec<usize> {
+    let mut radices = Vec::new();
+    while n % 4 == 0 {
+        radices.push(4);
+        n /= 4;
+    }
+    if n % 2 == 0 {
+        radices.push(2);
+        n /= 2;
+    }
+    let mut p = 3;
+    while n > 1 {
+        if p * p > n {
+            radices.push(n);
+            break;
+        }
+        while n % p == 0 {
+            radices.push(p);
+            n /= p;
+        }
+        p += 2;
+    }
+    radices
+}
+
+impl<T: Float + FloatConst> FftPlan<T> {
+    /// Creates plan for transforms of length `len`
+    pub fn new(len: usize) -> Self {
+        if len == 0 {
+            // Nothing to factor, transforms of empty buffers are no-ops
+            let algorithm = Algorithm::MixedRadix {
+                factors: Vec::new(),
+                twiddles: Vec::new(),
+            };
+            return FftPlan { len, algorithm };
+        }
+        let radices = factorize(len);
+        let algorithm = if radices.iter().all(|&p| p <= MAX_DIRECT_RADIX) {
+            let mut remaining = len;
+            let factors = radices
+                .into_iter()
+                .map(|p| {
+                    remaining /= p;
+                    (p, remaining)
+                })
+                .collect();
+            Algorithm::MixedRadix {
+                factors,
+                twiddles: (0..len).map(|k| root_of_unity(k, len)).collect(),
+            }
+        } else {
+            Self::bluestein(len)
+        };
+        FftPlan { len, algorithm }
+    }
+
+    fn bluestein(len: usize) -> Algorithm<T> {
+        let inner_len = (2 * len - 1).next_power_of_two();
+        let inner = FftPlan::new(inner_len);
+        // w_k = e^(-πi·k²/n), k² is reduced modulo 2n to keep the angle small
+        let chirp: Vec<Complex<T>> = (0..len)
+            .map(|k| root_of_unity((k * k) % (2 * len), 2 * len))
+            .collect();
+        let mut kernel = vec![complex(T::zero(), T::zero()); inner_len];
+        kernel[0] = chirp[0].conj();
+        for k in 1..len {
+            kernel[k] = chirp[k].conj();
+            kernel[inner_len - k] = chirp[k].conj();
+        }
+        inner.forward(&mut kernel);
+        Algorithm::Bluestein {
+            inner: Box::new(inner),
+            chirp,
+            kernel,
+        }
+    }
+
+    /// Length of transforms computed by this plan
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// In-place forward transform X_k = Σ x_j e^(-2πi·jk/n)
+    pub fn forward(&self, buffer: &mut [Complex<T>]) {
+        self.process(buffer, false);
+    }
+
+    /// In-place inverse transform x_j = 1/n Σ X_k e^(2πi·jk/n)
+    pub fn inverse(&self, buffer: &mut [Complex<T>]) {
+        self.process(buffer, true);
+        let scale = from_f64::<T>(self.len as f64);
+        for x in buffer.iter_mut() {
+            *x = *x / scale;
+        }
+    }
+
+    /// Out-of-place forward transform of `input` into `output`
+    pub fn forward_to(&self, input: &[Complex<T>], output: &mut [Complex<T>]) {
+        assert_eq!(input.len(), output.len(), "input and output lengths differ");
+        output.copy_from_slice(input);
+        self.forward(output);
+    }
+
+    /// Out-of-place inverse transform of `input` into `output`
+    pub fn inverse_to(&self, input: &[Complex<T>], output: &mut [Complex<T>]) {
+        assert_eq!(input.len(), output.len(), "input and output lengths differ");
+        output.copy_from_slice(input);
+        self.inverse(output);
+    }
+
+    /// Unnormalized transform, `inverse` flips the sign of the exponent
+    fn process(&self, buffer: &mut [Complex<T>], inverse: bool) {
+        assert_eq!(buffer.len(), self.len, "buffer length does not match plan");
+        if self.len <= 1 {
+            return;
+        }
+        match &self.algorithm {
+            Algorithm::MixedRadix { factors, twiddles } => {
+                let input = buffer.to_vec();
+                mixed_radix(buffer, &input, 1, factors, twiddles, inverse);
+            }
+            Algorithm::Bluestein {
+                inner,
+                chirp,
+                kernel,
+            } => {
+                if inverse {
+                    // Inverse through conjugation: conj(F(conj(x)))
+                    for x in buffer.iter_mut() {
+                        *x = x.conj();
+                    }
+                    self.bluestein_forward(buffer, inner, chirp, kernel);
+                    for x in buffer.iter_mut() {
+                        *x = x.conj();
+                    }
+                } else {
+                    self.bluestein_forward(buffer, inner, chirp, kernel);
+                }
+            }
+        }
+    }
+
+    fn bluestein_forward(
+        &self,
+        buffer: &mut [Complex<T>],
+        inner: &FftPlan<T>,
+        chirp: &[Complex<T>],
+        kernel: &[Complex<T>],
+    ) {
+        let mut work = vec![complex(T::zero(), T::zero()); inner.len()];
+        for (k, x) in buffer.iter().enumerate() {
+            work[k] = *x * chirp[k];
+        }
+        inner.forward(&mut work);
+        for (w, h) in work.iter_mut().zip(kernel) {
+            *w *= *h;
+        }
+        inner.inverse(&mut work);
+        for (k, x) in buffer.iter_mut().enumerate() {
+            *x = work[k] * chirp[k];
+        }
+    }
+}
+
+fn twiddle<T: Float>(twiddles: &[Complex<T>], index: usize, inverse: bool) -> Complex<T> {
+    if inverse {
+        twiddles[index].conj()
+    } else {
+        twiddles[index]
+    }
+}
+
+fn mixed_radix<T: Float>(
+    out: &mut [Complex<T>],
+    input: &[Complex<T>],
+    stride: usize,
+    factors: &[(usize, usize)],
+    twiddles: &[Complex<T>],
+    inverse: bool,
+) {
+    let (p, m) = factors[0];
+    if m == 1 {
+        for (k, x) in out.iter_mut().enumerate().take(p) {
+            *x = input[k * stride];
+        }
+    } else {
+        for k in 0..p {
+            mixed_radix(
+                &mut out[k * m..(k + 1) * m],
+                &input[k * stride..],
+                stride * p,
+                &factors[1..],
+                twiddles,
+                inverse,
+            );
+        }
+    }
+    match p {
+        2 => butterfly2(out, stride, m, twiddles, inverse),
+        4 => butterfly4(out, stride, m, twiddles, inverse),
+        _ => butterfly_generic(out, stride, p, m, twiddles, inverse),
+    }
+}
+
+fn butterfly2<T: Float>(
+    out: &mut [Complex<T>],
+    stride: usize,
+    m: usize,
+    twiddles: &[Complex<T>],
+    inverse: bool,
+) {
+    for k in 0..m {
+        let t = out[k + m] * twiddle(twiddles, k * stride, inverse);
+        out[k + m] = out[k] - t;
+        out[k] = out[k] + t;
+    }
+}
+
+fn butterfly4<T: Float>(
+    out: &mut [Complex<T>],
+    stride: usize,
+    m: usize,
+    twiddles: &[Complex<T>],
+    inverse: bool,
+) {
+    for k in 0..m {
+        let s0 = out[k + m] * twiddle(twiddles, k * stride, inverse);
+        let s1 = out[k + 2 * m] * twiddle(twiddles, 2 * k * stride, inverse);
+        let s2 = out[k + 3 * m] * twiddle(twiddles, 3 * k * stride, inverse);
+        let s5 = out[k] - s1;
+        let s3 = s0 + s2;
+        // -i·(s0 - s2) for forward transform, i·(s0 - s2) for inverse
+        let s4 = if inverse {
+            complex(s2.imag - s0.imag, s0.real - s2.real)
+        } else {
+            complex(s0.imag - s2.imag, s2.real - s0.real)
+        };
+        let s6 = out[k] + s1;
+        out[k] = s6 + s3;
+        out[k + 2 * m] = s6 - s3;
+        out[k + m] = s5 + s4;
+        out[k + 3 * m] = s5 - s4;
+    }
+}
+
+fn butterfly_generic<T: Float>(
+    out: &mut [Complex<T>],
+    stride: usize,
+    p: usize,
+    m: usize,
+    twiddles: &[Complex<T>],
+    inverse: bool,
+) {
+    let n = twiddles.len();
+    let mut scratch = vec![complex(T::zero(), T::zero()); p];
+    for u in 0..m {
+        for (q, s) in scratch.iter_mut().enumerate() {
+            *s = out[u + q * m];
+        }
+        for q1 in 0..p {
+            let k = u + q1 * m;
+            let step = (stride * k) % n;
+            let mut index = 0;
+            let mut acc = scratch[0];
+            for s in scratch.iter().skip(1) {
+                index = (index + step) % n;
+                acc = acc + *s * twiddle(twiddles, index, inverse);
+            }
+            out[k] = acc;
+        }
+    }
+}
+
+/// One-shot in-place forward transform, for repeated use build [`FftPlan`]
+pub fn fft<T: Float + FloatConst>(buffer: &mut [Complex<T>]) {
+    FftPlan::new(buffer.len()).forward(buffer);
+}
+
+/// One-shot in-place inverse transform, for repeated use build [`FftPlan`]
+pub fn ifft<T: Float + FloatConst>(buffer: &mut [Complex<T>]) {
+    FftPlan::new(buffer.len()).inverse(buffer);
+}
+
+/// Precomputed transform of real signals of a fixed length
+///
+/// Only the `n/2 + 1` non-redundant bins are produced, even lengths are
+/// packed into a complex transform of half the size.
+#[derive(Debug, Clone)]
+pub struct RealFftPlan<T> {
+    len: usize,
+    complex_plan: FftPlan<T>,
+    twiddles: Vec<Complex<T>>,
+}
+
+impl<T: Float + FloatConst> RealFftPlan<T> {
+    /// Creates plan for real signals of length `len`
+    pub fn new(len: usize) -> Self {
+        if len % 2 == 0 && len > 0 {
+            let half = len / 2;
+            RealFftPlan {
+                len,
+                complex_plan: FftPlan::new(half),
+                twiddles: (0..=half).map(|k| root_of_unity(k, len)).collect(),
+            }
+        } else {
+            RealFftPlan {
+                len,
+                complex_plan: FftPlan::new(len),
+                twiddles: Vec::new(),
+            }
+        }
+    }
+
+    /// Length of real signals accepted by this plan
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Number of bins in the spectrum, `n/2 + 1`
+    pub fn spectrum_len(&self) -> usize {
+        self.len / 2 + 1
+    }
+
+    /// Forward transform of real `input`, returns first `n/2 + 1` bins
+    pub fn forward(&self, input: &[T]) -> Vec<Complex<T>> {
+        assert_eq!(input.len(), self.len, "input length does not match plan");
+        if self.len == 0 {
+            return Vec::new();
+        }
+        if self.twiddles.is_empty() {
+            let mut buffer: Vec<Complex<T>> =
+                input.iter().map(|&x| complex(x, T::zero())).collect();
+            self.complex_plan.forward(&mut buffer);
+            buffer.truncate(self.spectrum_len());
+            return buffer;
+        }
+        let half = self.len / 2;
+        let mut packed: Vec<Complex<T>> = input
+            .chunks(2)
+            .map(|pair| complex(pair[0], pair[1]))
+            .collect();
+        self.complex_plan.forward(&mut packed);
+        let two = from_f64::<T>(2.0);
+        (0..=half)
+            .map(|k| {
+                let z = packed[k % half];
+                let zc = packed[(half - k) % half].conj();
+                let even = (z + zc) / two;
+                let diff = (z - zc) / two;
+                // odd = -i·diff
+                let odd = complex(diff.imag, -diff.real);
+                even + self.twiddles[k] * odd
+            })
+            .collect()
+    }
+
+    /// Inverse transform of `n/2 + 1` bins back into a real signal
+    pub fn inverse(&self, spectrum: &[Complex<T>]) -> Vec<T> {
+        assert_eq!(
+            spectrum.len(),
+            self.spectrum_len(),
+            "spectrum length does not match plan"
+        );
+        if self.len == 0 {
+            return Vec::new();
+        }
+        if self.twiddles.is_empty() {
+            let mut buffer = hermitian_extend(spectrum, self.len);
+            self.complex_plan.inverse(&mut buffer);
+            return buffer.iter().map(|x| x.real).collect();
+        }
+        let half = self.len / 2;
+        let two = from_f64::<T>(2.0);
+        let mut packed: Vec<Complex<T>> = (0..half)
+            .map(|k| {
+                let x = spectrum[k];
+                let xc = spectrum[half - k].conj();
+                let even = (x + xc) / two;
+                let odd = (x - xc) / two * self.twiddles[k].conj();
+                // even + i·odd
+                even + complex(-odd.imag, odd.real)
+            })
+            .collect();
+        self.complex_plan.inverse(&mut packed);
+        packed.iter().flat_map(|z| [z.real, z.imag]).collect()
+    }
+}
+
+/// Rebuilds full spectrum of a real signal from its first `n/2 + 1` bins
+fn hermitian_extend<T: Float>(spectrum: &[Complex<T>], len: usize) -> Vec<Complex<T>> {
+    (0..len)
+        .map(|k| {
+            if k < spectrum.len() {
+                spectrum[k]
+            } else {
+                spectrum[len - k].conj()
+            }
+        })
+        .collect()
+}
+
+/// One-shot transform of real signal, returns first `n/2 + 1` bins
+pub fn rfft<T: Float + FloatConst>(input: &[T]) -> Vec<Complex<T>> {
+    RealFftPlan::new(input.len()).forward(input)
+}
+
+/// One-shot inverse of [`rfft`], `len` is the length of the original signal
+pub fn irfft<T: Float + FloatConst>(spectrum: &[Complex<T>], len: usize) -> Vec<T> {
+    RealFftPlan::new(len).inverse(spectrum)
+}
+
+/// Precomputed transform of row-major N-dimensional arrays
+///
+/// The last axis is contiguous in memory, like C arrays and numpy defaults.
+#[derive(Debug, Clone)]
+pub struct FftPlanNd<T> {
+    shape: Vec<usize>,
+    plans: Vec<FftPlan<T>>,
+}
+
+impl<T: Float + FloatConst> FftPlanNd<T> {
+    /// Creates plan for arrays of given `shape`
+    pub fn new(shape: &[usize]) -> Self {
+        FftPlanNd {
+            shape: shape.to_vec(),
+            plans: shape.iter().map(|&n| FftPlan::new(n)).collect(),
+        }
+    }
+
+    /// Shape of arrays transformed by this plan
+    pub fn shape(&self) -> &[usize] {
+        &self.shape
+    }
+
+    /// In-place forward transform along every axis
+    pub fn forward(&self, buffer: &mut [Complex<T>]) {
+        self.process(buffer, false);
+    }
+
+    /// In-place inverse transform along every axis, scaled by `1/len`
+    pub fn inverse(&self, buffer: &mut [Complex<T>]) {
+        self.process(buffer, true);
+    }
+
+    fn process(&self, buffer: &mut [Complex<T>], inverse: bool) {
+        let total: usize = self.shape.iter().product();
+        assert_eq!(buffer.len(), total, "buffer length does not match shape");
+        if total == 0 {
+            return;
+        }
+        let mut stride = total;
+        for (axis, plan) in self.shape.iter().zip(&self.plans) {
+            let n = *axis;
+            stride /= n;
+            let mut line = vec![complex(T::zero(), T::zero()); n];
+            // Every line along this axis starts at `outer * n * stride + inner`
+            for outer in 0..total / (n * stride) {
+                for inner in 0..stride {
+                    let start = outer * n * stride + inner;
+                    for (k, x) in line.iter_mut().enumerate() {
+                        *x = buffer[start + k * stride];
+                    }
+                    if inverse {
+                        plan.inverse(&mut line);
+                    } else {
+                        plan.forward(&mut line);
+                    }
+                    for (k, x) in line.iter().enumerate() {
+                        buffer[start + k * stride] = *x;
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// One-shot forward transform of row-major `rows × cols` matrix
+pub fn fft2<T: Float + FloatConst>(buffer: &mut [Complex<T>], rows: usize, cols: usize) {
+    FftPlanNd::new(&[rows, cols]).forward(buffer);
+}
+
+/// One-shot inverse transform of row-major `rows × cols` matrix
+pub fn ifft2<T: Float + FloatConst>(buffer: &mut [Complex<T>], rows: usize, cols: usize) {
+    FftPlanNd::new(&[rows, cols]).inverse(buffer);
+}
+
+/// One-shot forward transform of row-major array with given `shape`
+pub fn fftn<T: Float + FloatConst>(buffer: &mut [Complex<T>], shape: &[usize]) {
+    FftPlanNd::new(shape).forward(buffer);
+}
+
+/// One-shot inverse transform of row-major array with given `shape`
+pub fn ifftn<T: Float + FloatConst>(buffer: &mut [Complex<T>], shape: &[usize]) {
+    FftPlanNd::new(shape).inverse(buffer);
+}
@@ -0,0 +1,258 @@
+//! Streaming FIR and IIR filters over complex samples
+//!
+//! Filters keep their state between calls to [`Filter::process`], so a long
+//! stream can be fed in blocks of any size.
+//! ```rust
+//! use ancomplex::*;
+//! use ancomplex::filter::{Filter, Fir};
+//!
+//! // Two-tap moving average
+//! let mut fir = Fir::new(vec![c64(0.5, 0.0), c64(0.5, 0.0)]);
+//! let out = fir.process(&[c64(2.0, 0.0), c64(4.0, 2.0)]);
+//! assert_eq!(out, vec![c64(1.0, 0.0), c64(3.0, 1.0)]);
+//! ```
+use crate::cast::from_f64;
+use crate::{complex, Complex};
+use num_traits::{Float, FloatConst};
+
+fn zero<T: Float>() -> Complex<T> {
+    complex(T::zero(), T::zero())
+}
+
+/// Evaluates Σ c_k·zᵏ by Horner's scheme
+fn horner<T: Float>(coeffs: &[Complex<T>], z: Complex<T>) -> Complex<T> {
+    coeffs.iter().rev().fold(zero(), |acc, &c| acc * z + c)
+}
+
+/// Common interface of stateful linear filters
+pub trait Filter<T: Float> {
+    /// Filters single sample
+    fn process_sample(&mut self, x: Complex<T>) -> Complex<T>;
+
+    /// Clears internal state as if no samples were processed
+    fn reset(&mut self);
+
+    /// Frequency response H(e^(iω)), `omega` in radians per sample
+    fn frequency_response(&self, omega: T) -> Complex<T>;
+
+    /// Filters block of samples, state is carried to the next call
+    fn process(&mut self, input: &[Complex<T>]) -> Vec<Complex<T>> {
+        input.iter().map(|&x| self.process_sample(x)).collect()
+    }
+}
+
+/// Frequency response at `points` frequencies ω_k = 2πk/points
+pub fn freqz<T: Float + FloatConst, F: Filter<T>>(filter: &F, points: usize) -> Vec<Complex<T>> {
+    let two_pi = T::PI() + T::PI();
+    (0..points)
+        .map(|k| {
+            let omega = two_pi * from_f64::<T>(k as f64) / from_f64::<T>(points as f64);
+            filter.frequency_response(omega)
+        })
+        .collect()
+}
+
+/// Finite impulse response filter with complex taps
+#[derive(Debug, Clone)]
+pub struct Fir<T> {
+    taps: Vec<Complex<T>>,
+    delay: Vec<Complex<T>>,
+    position: usize,
+}
+
+impl<T: Float> Fir<T> {
+    /// Creates filter y[n] = Σ h[k]·x[n-k]
+    pub fn new(taps: Vec<Complex<T>>) -> Self {
+        assert!(!taps.is_empty(), "filter needs at least one tap");
+        Fir {
+            delay: vec![zero(); taps.len()],
+            taps,
+            position: 0,
+        }
+    }
+
+    pub fn taps(&self) -> &[Complex<T>] {
+        &self.taps
+    }
+}
+
+impl<T: Float> Filter<T> for Fir<T> {
+    fn process_sample(&mut self, x: Complex<T>) -> Complex<T> {
+        let len = self.taps.len();
+        self.delay[self.position] = x;
+        let y = self.taps.iter().enumerate().fold(zero(), |acc, (k, &h)| {
+            acc + h * self.delay[(self.position + len - k) % len]
+        });
+        self.position = (self.position + 1) % len;
+        y
+    }
+
+    fn reset(&mut self) {
+        self.delay.iter_mut().for_each(|x| *x = zero());
+        self.position = 0;
+    }
+
+    fn frequency_response(&self, omega: T) -> Complex<T> {
+        horner(&self.taps, Complex::from_polar(T::one(), -omega))
+    }
+}
+
+/// Infinite impulse response filter in direct form II transposed
+///
+/// a₀·y[n] = Σ b_k·x[n-k] - Σ_(k≥1) a_k·y[n-k]
+#[derive(Debug, Clone)]
+pub struct Iir<T> {
+    b: Vec<Complex<T>>,
+    a: Vec<Complex<T>>,
+    state: Vec<Complex<T>>,
+}
+
+impl<T: Float> Iir<T> {
+    /// Creates filter from numerator `b` and denominator `a`, coefficients are normalized by a₀
+    pub fn new(b: Vec<Complex<T>>, a: Vec<Complex<T>>) -> Self {
+        assert!(
+            !b.is_empty() && !a.is_empty(),
+            "coefficients must not be empty"
+        );
+        let a0 = a[0];
+        assert!(
+            a0.norm() > T::zero(),
+            "leading denominator coefficient is zero"
+        );
+        let order = b.len().max(a.len());
+        let normalize = |c: &[Complex<T>]| {
+            let mut out: Vec<Complex<T>> = c.iter().map(|&x| x / a0).collect();
+            out.resize(order, zero());
+            out
+        };
+        Iir {
+            b: normalize(&b),
+            a: normalize(&a),
+            state: vec![zero(); order - 1],
+        }
+    }
+
+    /// Normalized numerator coefficients
+    pub fn numerator(&self) -> &[Complex<T>] {
+        &self.b
+    }
+
+    /// Normalized denominator coefficients, first one is 1
+    pub fn denominator(&self) -> &[Complex<T>] {
+        &self.a
+    }
+}
+
+impl<T: Float> Filter<T> for Iir<T> {
+    fn process_sample(&mut self, x: Complex<T>) -> Complex<T> {
+        let order = self.state.len();
+        if order == 0 {
+            return self.b[0] * x;
+        }
+        let y = self.b[0] * x + self.state[0];
+        for k in 0..order {
+            let next = if k + 1 < order {
+                self.state[k + 1]
+            } else {
+                zero()
+            };
+            self.state[k] = self.b[k + 1] * x - self.a[k + 1] * y + next;
+        }
+        y
+    }
+
+    fn reset(&mut self) {
+        self.state.iter_mut().for_each(|x| *x = zero());
+    }
+
+    fn frequency_response(&self, omega: T) -> Complex<T> {
+        let z_inv = Complex::from_polar(T::one(), -omega);
+        horner(&self.b, z_inv) / horner(&self.a, z_inv)
+    }
+}
+
+/// Second order section in direct form II transposed
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Biquad<T> {
+    /// Numerator b₀, b₁, b₂
+    pub b: [Complex<T>; 3],
+    /// Denominator a₁, a₂ (a₀ is normalized to 1)
+    pub a: [Complex<T>; 2],
+    state: [Complex<T>; 2],
+}
+
+impl<T: Float> Biquad<T> {
+    /// Creates section from numerator `b` and denominator `a`, normalized by a₀
+    pub fn new(b: [Complex<T>; 3], a: [Complex<T>; 3]) -> Self {
+        assert!(
+            a[0].norm() > T::zero(),
+            "leading denominator coefficient is zero"
+        );
+        Biquad {
+            b: [b[0] / a[0], b[1] / a[0], b[2] / a[0]],
+            a: [a[1] / a[0], a[2] / a[0]],
+            state: [zero(); 2],
+        }
+    }
+
+    /// Creates section from real coefficients
+    pub fn from_real(b: [T; 3], a: [T; 3]) -> Self {
+        let c = |x: T| complex(x, T::zero());
+        Self::new([c(b[0]), c(b[1]), c(b[2])], [c(a[0]), c(a[1]), c(a[2])])
+    }
+}
+
+impl<T: Float> Filter<T> for Biquad<T> {
+    fn process_sample(&mut self, x: Complex<T>) -> Complex<T> {
+        let y = self.b[0] * x + self.state[0];
+        self.state[0] = self.b[1] * x - self.a[0] * y + self.state[1];
+        self.state[1] = self.b[2] * x - self.a[1] * y;
+        y
+    }
+
+    fn reset(&mut self) {
+        self.state = [zero(); 2];
+    }
+
+    fn frequency_response(&self, omega: T) -> Complex<T> {
+        let z_inv = Complex::from_polar(T::one(), -omega);
+        let one = complex(T::one(), T::zero());
+        horner(&self.b, z_inv) / horner(&[one, self.a[0], self.a[1]], z_inv)
+    }
+}
+
+/// Cascade of second order sections
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sos<T> {
+    sections: Vec<Biquad<T>>,
+}
+
+impl<T: Float> Sos<T> {
+    pub fn new(sections: Vec<Biquad<T>>) -> Self {
+        Sos { sections }
+    }
+
+    pub fn sections(&self) -> &[Biquad<T>] {
+        &self.sections
+    }
+}
+
+impl<T: Float> Filter<T> for Sos<T> {
+    fn process_sample(&mut self, x: Complex<T>) -> Complex<T> {
+        self.sections
+            .iter_mut()
+            .fold(x, |acc, section| section.process_sample(acc))
+    }
+
+    fn reset(&mut self) {
+        self.sections.iter_mut().for_each(|s| s.reset());
+    }
+
+    fn frequency_response(&self, omega: T) -> Complex<T> {
+        self.sections
+            .iter()
+            .fold(complex(T::one(), T::zero()), |acc, s| {
+                acc * s.frequency_response(omega)
+            })
+    }
+}
@@ -0,0 +1,221 @@
+//! Plane geometry with complex numbers as points and vectors
+//!
+//! For vectors u and v, Re(ū·v) is the dot product and Im(ū·v) the cross
+//! product. Rotation by θ is multiplication by e^{iθ}. Reflection uses
+//! conjugation.
+//! ```rust
+//! use ancomplex::*;
+//! use ancomplex::geometry::{convex_hull, signed_area, Circle, Line};
+//!
+//! let circle = Circle::through(c64(1.0, 0.0), c64(0.0, 1.0), c64(-1.0, 0.0)).unwrap();
+//! assert!(circle.center.norm() < 1e-15 && (circle.radius - 1.0).abs() < 1e-15);
+//!
+//! let diagonal = Line::through(c64(0.0, 0.0), c64(1.0, 1.0));
+//! assert!((diagonal.reflect(c64(2.0, 0.0)) - c64(0.0, 2.0)).norm() < 1e-15);
+//!
+//! let square = [c64(0.0, 0.0), c64(1.0, 0.0), c64(0.5, 0.5), c64(1.0, 1.0), c64(0.0, 1.0)];
+//! let hull = convex_hull(&square);
+//! assert_eq!(hull.len(), 4);
+//! assert_eq!(signed_area(&hull), 1.0);
+//! ```
+use crate::{complex, Complex};
+use num_traits::Float;
+use std::cmp::Ordering;
+
+/// Im(ū·v), positive when v is counterclockwise from u
+fn cross<T: Float>(u: Complex<T>, v: Complex<T>) -> T {
+    u.real * v.imag - u.imag * v.real
+}
+
+/// Re(ū·v)
+fn dot<T: Float>(u: Complex<T>, v: Complex<T>) -> T {
+    u.real * v.real + u.imag * v.imag
+}
+
+/// `z` rotated by `angle` radians counterclockwise about `center`
+pub fn rotate_about<T: Float>(z: Complex<T>, center: Complex<T>, angle: T) -> Complex<T> {
+    center + (z - center) * Complex::from_polar(T::one(), angle)
+}
+
+/// Infinite line through `point` in direction `direction`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Line<T> {
+    pub point: Complex<T>,
+    pub direction: Complex<T>,
+}
+
+impl<T: Float> Line<T> {
+    /// Line through two points, panics if they coincide
+    pub fn through(p: Complex<T>, q: Complex<T>) -> Self {
+        assert!(p != q, "line through coincident points");
+        Line {
+            point: p,
+            direction: q - p,
+        }
+    }
+
+    /// Orthogonal projection of `z` onto the line
+    pub fn project(&self, z: Complex<T>) -> Complex<T> {
+        let t = dot(self.direction, z - self.point) / self.direction.square_norm();
+        self.point + self.direction.scale(t)
+    }
+
+    /// Mirror image p + (d/d̄)·conj(z - p) of `z`
+    pub fn reflect(&self, z: Complex<T>) -> Complex<T> {
+        let d = self.direction;
+        self.point + (d * d).unscale(d.square_norm()) * (z - self.point).conj()
+    }
+
+    /// Signed distance, positive to the left of the direction
+    pub fn signed_distance(&self, z: Complex<T>) -> T {
+        cross(self.direction, z - self.point) / self.direction.norm()
+    }
+
+    /// Intersection point, `None` for parallel lines
+    pub fn intersect(&self, other: &Line<T>) -> Option<Complex<T>> {
+        let denominator = cross(self.direction, other.direction);
+        if denominator == T::zero() {
+            return None;
+        }
+        let t = cross(other.point - self.point, other.direction) / denominator;
+        Some(self.point + self.direction.scale(t))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Circle<T> {
+    pub center: Complex<T>,
+    pub radius: T,
+}
+
+impl<T: Float> Circle<T> {
+    pub fn new(center: Complex<T>, radius: T) -> Self {
+        Circle { center, radius }
+    }
+
+    /// Circumscribed circle, `None` if the points are collinear
+    pub fn through(a: Complex<T>, b: Complex<T>, c: Complex<T>) -> Option<Self> {
+        // With a at the origin the center is (|b|²c - |c|²b)/(b̄c - bc̄)
+        let (b, c) = (b - a, c - a);
+        let denominator = cross(b, c);
+        if denominator == T::zero() {
+            return None;
+        }
+        let numerator = c.scale(b.square_norm()) - b.scale(c.square_norm());
+        let offset = numerator * complex(T::zero(), -T::one() / (denominator + denominator));
+        Some(Circle {
+            center: a + offset,
+            radius: offset.norm(),
+        })
+    }
+
+    /// True for points inside or on the circle
+    pub fn contains(&self, z: Complex<T>) -> bool {
+        (z - self.center).norm() <= self.radius
+    }
+
+    /// Inversion center + r²/conj(z - center), the reflection across the
+    /// circle. The center itself gives non-finite components.
+    pub fn invert(&self, z: Complex<T>) -> Complex<T> {
+        let r2 = self.radius * self.radius;
+        self.center + (z - self.center).conj().inv().scale(r2)
+    }
+
+    /// Points where `line` meets the circle, ordered along its direction
+    pub fn intersect_line(&self, line: &Line<T>) -> Vec<Complex<T>> {
+        let u = line.direction.unscale(line.direction.norm());
+        let w = line.point - self.center;
+        // Foot of the perpendicular from the center, and half the chord length
+        let foot = line.point - u.scale(dot(u, w));
+        let offset = cross(u, w).abs();
+        let discriminant = (self.radius - offset) * (self.radius + offset);
+        if discriminant < T::zero() {
+            return vec![];
+        }
+        if discriminant == T::zero() {
+            return vec![foot];
+        }
+        let half_chord = u.scale(discriminant.sqrt());
+        vec![foot - half_chord, foot + half_chord]
+    }
+}
+
+/// Signed area of the polygon with vertices `vertices` in order, positive
+/// for counterclockwise orientation
+pub fn signed_area<T: Float>(vertices: &[Complex<T>]) -> T {
+    let n = vertices.len();
+    let twice = (0..n).fold(T::zero(), |acc, k| {
+        acc + cross(vertices[k], vertices[(k + 1) % n])
+    });
+    twice / (T::one() + T::one())
+}
+
+/// Winding number of the closed polygon `vertices` around `z`
+///
+/// Counterclockwise turns count as positive. Points on the boundary give
+/// either neighbouring value.
+pub fn winding_number<T: Float>(z: Complex<T>, vertices: &[Complex<T>]) -> i32 {
+    let n = vertices.len();
+    let mut winding = 0;
+    for k in 0..n {
+        let (a, b) = (vertices[k], vertices[(k + 1) % n]);
+        let side = cross(b - a, z - a);
+        if a.imag <= z.imag {
+            if b.imag > z.imag && side > T::zero() {
+                winding += 1;
+            }
+        } else if b.imag <= z.imag && side < T::zero() {
+            winding -= 1;
+        }
+    }
+    winding
+}
+
+/// True if `z` lies inside the polygon by the nonzero winding rule, which
+/// also handles self-intersecting polygons
+pub fn point_in_polygon<T: Float>(z: Complex<T>, vertices: &[Complex<T>]) -> bool {
+    winding_number(z, vertices) != 0
+}
+
+/// Convex hull by Andrew's monotone chain
+///
+/// Vertices are returned counterclockwise starting from the leftmost
+/// (then lowest) point, with collinear points on edges left out.
+pub fn convex_hull<T: Float>(points: &[Complex<T>]) -> Vec<Complex<T>> {
+    let mut sorted = points.to_vec();
+    sorted.sort_by(|a, b| {
+        a.real
+            .partial_cmp(&b.real)
+            .unwrap_or(Ordering::Equal)
+            .then(a.imag.partial_cmp(&b.imag).unwrap_or(Ordering::Equal))
+    });
+    sorted.dedup();
+    if sorted.len() < 3 {
+        return sorted;
+    }
+    // Lower chain left to right, then upper chain right to left
+    let mut hull = Vec::with_capacity(2 * sorted.len());
+    extend_chain(&mut hull, sorted.iter());
+    extend_chain(&mut hull, sorted.iter().rev());
+    hull
+}
+
+/// Appends the convex chain through `points` to `hull`, leaving out the last
+/// point, which starts the next chain
+fn extend_chain<'a, T: Float + 'a>(
+    hull: &mut Vec<Complex<T>>,
+    points: impl Iterator<Item = &'a Complex<T>>,
+) {
+    let start = hull.len();
+    for &p in points {
+        while hull.len() >= start + 2 {
+            let (a, b) = (hull[hull.len() - 2], hull[hull.len() - 1]);
+            if cross(b - a, p - b) > T::zero() {
+                break;
+            }
+            hull.pop();
+        }
+        hull.push(p);
+    }
+    hull.pop();
+}
@@ -0,0 +1,116 @@
+//! Analytic signal, Hilbert transform and instantaneous amplitude, phase and frequency
+//!
+//! ```rust
+//! use ancomplex::hilbert::*;
+//!
+//! let signal: Vec<f64> = (0..64).map(|n| (0.5 * n as f64).cos()).collect();
+//! let analytic = analytic_signal(&signal);
+//! let frequency = instantaneous_frequency(&analytic, 1.0);
+//! assert!((frequency[30] - 0.5 / (2.0 * std::f64::consts::PI)).abs() < 1e-3);
+//! ```
+use crate::cast::from_f64;
+use crate::fft::FftPlan;
+use crate::phase::unwrap_phase;
+use crate::window::Window;
+use crate::{complex, Complex};
+use num_traits::{Float, FloatConst};
+
+/// Analytic signal x + i·H{x} computed with FFT
+///
+/// Negative frequencies are removed and positive ones doubled, which treats
+/// `signal` as one period of a periodic sequence.
+pub fn analytic_signal<T: Float + FloatConst>(signal: &[T]) -> Vec<Complex<T>> {
+    let n = signal.len();
+    let plan = FftPlan::new(n);
+    let mut buffer: Vec<Complex<T>> = signal.iter().map(|&x| complex(x, T::zero())).collect();
+    plan.forward(&mut buffer);
+    let two = from_f64::<T>(2.0);
+    for (k, x) in buffer.iter_mut().enumerate().skip(1) {
+        if 2 * k < n {
+            *x = *x * two;
+        } else if 2 * k > n {
+            *x = complex(T::zero(), T::zero());
+        }
+    }
+    plan.inverse(&mut buffer);
+    buffer
+}
+
+/// Hilbert transform H{x}, imaginary part of [`analytic_signal`]
+pub fn hilbert<T: Float + FloatConst>(signal: &[T]) -> Vec<T> {
+    analytic_signal(signal).iter().map(|z| z.imag).collect()
+}
+
+/// Taps of Hamming windowed FIR Hilbert transformer of odd length `num_taps`
+///
+/// h[n] = 2/(πn) for odd n and 0 for even n, n counted from the center tap.
+pub fn hilbert_fir_taps<T: Float + FloatConst>(num_taps: usize) -> Vec<T> {
+    assert!(
+        num_taps % 2 == 1,
+        "Hilbert transformer needs odd number of taps"
+    );
+    let center = (num_taps / 2) as isize;
+    let window = Window::Hamming.symmetric(num_taps);
+    (0..num_taps)
+        .map(|k| {
+            let n = k as isize - center;
+            if n % 2 == 0 {
+                return T::zero();
+            }
+            from_f64::<T>(2.0) / (T::PI() * from_f64::<T>(n as f64)) * window[k]
+        })
+        .collect()
+}
+
+/// Analytic signal computed with FIR Hilbert transformer of `num_taps` taps
+///
+/// The filter delay is compensated, so output is aligned with `signal`.
+/// Samples closer than `num_taps / 2` to the edges see zero padding.
+pub fn analytic_signal_fir<T: Float + FloatConst>(
+    signal: &[T],
+    num_taps: usize,
+) -> Vec<Complex<T>> {
+    let taps = hilbert_fir_taps::<T>(num_taps);
+    let delay = (num_taps / 2) as isize;
+    let len = signal.len() as isize;
+    (0..len)
+        .map(|n| {
+            let imag = taps.iter().enumerate().fold(T::zero(), |acc, (k, &h)| {
+                let index = n + delay - k as isize;
+                if index >= 0 && index < len {
+                    acc + h * signal[index as usize]
+                } else {
+                    acc
+                }
+            });
+            complex(signal[n as usize], imag)
+        })
+        .collect()
+}
+
+/// Instantaneous amplitude |z_n| (envelope) of analytic signal
+pub fn instantaneous_amplitude<T: Float>(analytic: &[Complex<T>]) -> Vec<T> {
+    analytic.iter().map(|z| z.norm()).collect()
+}
+
+/// Instantaneous phase arg(z_n), wrapped into (-π, π]
+pub fn instantaneous_phase<T: Float>(analytic: &[Complex<T>]) -> Vec<T> {
+    analytic.iter().map(|z| z.arg()).collect()
+}
+
+/// Continuous instantaneous phase without 2π jumps
+pub fn unwrapped_phase<T: Float + FloatConst>(analytic: &[Complex<T>]) -> Vec<T> {
+    unwrap_phase(analytic, T::PI())
+}
+
+/// Instantaneous frequency in units of the sample rate `fs`
+///
+/// Uses the phase increment arg(z_(n+1)·conj(z_n)), so it never sees phase
+/// wrapping. Result has one element less than `analytic`.
+pub fn instantaneous_frequency<T: Float + FloatConst>(analytic: &[Complex<T>], fs: T) -> Vec<T> {
+    let scale = fs / (T::PI() + T::PI());
+    analytic
+        .windows(2)
+        .map(|pair| (pair[1] * pair[0].conj()).arg() * scale)
+        .collect()
+}
@@ -50,6 +50,7 @@
 //! }
 //! ```
 //!
+#![allow(clippy::needless_doctest_main)]
 #[cfg(test)]
 mod tests;
 
@@ -60,7 +61,33 @@ pub(crate) mod complex_assing_ops;
 pub(crate) mod complex_ops;
 pub(crate) mod complexc;
 pub(crate) mod complexfmt;
+pub mod conformal;
+pub mod contour;
+pub mod convolution;
+pub mod decomposition;
+pub mod design;
+pub mod differentiation;
+pub mod eigen;
+pub mod elliptic;
+pub mod equations;
+pub mod extended;
+pub mod fft;
+pub mod filter;
+pub mod geometry;
+pub mod hilbert;
+pub mod matfun;
+pub mod matrix;
+pub mod mobius;
 pub(crate) mod num_traits_impl;
+pub mod ode;
+pub mod phase;
+pub mod polynomial;
+pub mod roots;
+pub mod series;
+pub mod special;
+pub mod spectral;
+pub mod stft;
+pub mod window;
 pub use complexc::Complex;
 
 /// Function to init `Complex` struct (*Recomed way to init complex numbers*)
@@ -0,0 +1,273 @@
+//! Matrix exponential, logarithm and square root
+//!
+//! The exponential uses scaling and squaring with a Padé approximant. The
+//! logarithm and square root work on the complex Schur form, where the
+//! diagonal is handled by the scalar `ln` and `sqrt`.
+//! ```rust
+//! use ancomplex::*;
+//! use ancomplex::matrix::CMatrix;
+//!
+//! // Time evolution under Pauli X for t = π/2 gives U = -iX
+//! let x = CMatrix::new(2, 2, vec![c64(0.0, 0.0), c64(1.0, 0.0), c64(1.0, 0.0), c64(0.0, 0.0)]);
+//! let t = std::f64::consts::FRAC_PI_2;
+//! let u = x.scale(c64(0.0, -t)).expm();
+//! assert!((u[(0, 1)] - c64(0.0, -1.0)).norm() < 1e-14);
+//! assert!(u[(0, 0)].norm() < 1e-14);
+//! ```
+use crate::cast::from_f64;
+use crate::eigen::Schur;
+use crate::matrix::CMatrix;
+use crate::{complex, Complex};
+use num_traits::Float;
+
+/// Padé coefficients b₀..b_m of degree m for e^x, with the largest ‖A‖₁
+/// each one is accurate to double precision for (Higham 2005)
+const PADE: [(f64, &[f64]); 4] = [
+    (1.495_585_217_958_292e-2, &[120.0, 60.0, 12.0, 1.0]),
+    (
+        2.539_398_330_063_23e-1,
+        &[30240.0, 15120.0, 3360.0, 420.0, 30.0, 1.0],
+    ),
+    (
+        9.504_178_996_162_932e-1,
+        &[
+            17297280.0, 8648640.0, 1995840.0, 277200.0, 25200.0, 1512.0, 56.0, 1.0,
+        ],
+    ),
+    (
+        2.097_847_961_257_068,
+        &[
+            17643225600.0,
+            8821612800.0,
+            2075673600.0,
+            302702400.0,
+            30270240.0,
+            2162160.0,
+            110880.0,
+            3960.0,
+            90.0,
+            1.0,
+        ],
+    ),
+];
+
+const THETA_13: f64 = 5.371_920_351_148_152;
+
+const PADE_13: [f64; 14] = [
+    64764752532480000.0,
+    32382376266240000.0,
+    7771770303897600.0,
+    1187353796428800.0,
+    129060195264000.0,
+    10559470521600.0,
+    670442572800.0,
+    33522128640.0,
+    1323241920.0,
+    40840800.0,
+    960960.0,
+    16380.0,
+    182.0,
+    1.0,
+];
+
+/// Positive Gauss–Legendre nodes and weights on [-1, 1], 8 points
+const GAUSS_LEGENDRE_8: [(f64, f64); 4] = [
+    (0.183_434_642_495_649_8, 0.362_683_783_378_362),
+    (0.525_532_409_916_329, 0.313_706_645_877_887_3),
+    (0.796_666_477_413_626_7, 0.222_381_034_453_374_5),
+    (0.960_289_856_497_536_2, 0.101_228_536_290_376_26),
+];
+
+/// ‖T - I‖₁ below which the logarithm's Padé approximant is accurate
+const LOG_PADE_RADIUS: f64 = 0.25;
+const MAX_SQUARE_ROOTS: usize = 64;
+
+fn real<T: Float>(x: T) -> Complex<T> {
+    complex(x, T::zero())
+}
+
+/// Σ cₖ·Mₖ
+fn combine<T: Float>(terms: &[(f64, &CMatrix<T>)]) -> CMatrix<T> {
+    let (rows, cols) = (terms[0].1.rows(), terms[0].1.cols());
+    terms
+        .iter()
+        .fold(CMatrix::zeros(rows, cols), |acc, (c, m)| {
+            &acc + &m.scale(real(from_f64::<T>(*c)))
+        })
+}
+
+fn nan_matrix<T: Float>(n: usize) -> CMatrix<T> {
+    CMatrix::from_fn(n, n, |_, _| complex(T::nan(), T::nan()))
+}
+
+/// r(A) = (V - U)⁻¹(V + U) for numerator U + V split into odd and even parts
+///
+/// The denominator is well conditioned in the range of each approximant, so
+/// a singular one only comes from NaN entries and gives a NaN matrix.
+fn pade_quotient<T: Float>(u: &CMatrix<T>, v: &CMatrix<T>) -> CMatrix<T> {
+    (v - u)
+        .lu()
+        .solve_matrix(&(v + u))
+        .unwrap_or_else(|| nan_matrix(u.rows()))
+}
+
+/// Padé approximant of degree 3 to 9 with coefficients `b`
+fn pade_low<T: Float>(a: &CMatrix<T>, b: &[f64]) -> CMatrix<T> {
+    let n = a.rows();
+    let a2 = a * a;
+    let mut powers = vec![CMatrix::identity(n), a2.clone()];
+    while powers.len() < b.len() / 2 {
+        let next = powers
+            .last()
+            .map(|p| p * &a2)
+            .expect("powers are not empty");
+        powers.push(next);
+    }
+    let odd: Vec<(f64, &CMatrix<T>)> = powers
+        .iter()
+        .enumerate()
+        .map(|(k, p)| (b[2 * k + 1], p))
+        .collect();
+    let even: Vec<(f64, &CMatrix<T>)> = powers
+        .iter()
+        .enumerate()
+        .map(|(k, p)| (b[2 * k], p))
+        .collect();
+    pade_quotient(&(a * &combine(&odd)), &combine(&even))
+}
+
+/// Padé approximant of degree 13, evaluated with six matrix products
+fn pade_13<T: Float>(a: &CMatrix<T>) -> CMatrix<T> {
+    let b = PADE_13;
+    let identity = CMatrix::identity(a.rows());
+    let a2 = a * a;
+    let a4 = &a2 * &a2;
+    let a6 = &a2 * &a4;
+    let u_high = &a6 * &combine(&[(b[13], &a6), (b[11], &a4), (b[9], &a2)]);
+    let u_low = combine(&[(b[7], &a6), (b[5], &a4), (b[3], &a2), (b[1], &identity)]);
+    let u = a * &(&u_high + &u_low);
+    let v_high = &a6 * &combine(&[(b[12], &a6), (b[10], &a4), (b[8], &a2)]);
+    let v_low = combine(&[(b[6], &a6), (b[4], &a4), (b[2], &a2), (b[0], &identity)]);
+    pade_quotient(&u, &(&v_high + &v_low))
+}
+
+/// Principal square root of upper triangular matrix, `None` if it does not exist
+fn triangular_sqrt<T: Float>(t: &CMatrix<T>) -> Option<CMatrix<T>> {
+    let n = t.rows();
+    let mut r = CMatrix::zeros(n, n);
+    for i in 0..n {
+        r[(i, i)] = t[(i, i)].sqrt();
+    }
+    for j in 1..n {
+        for i in (0..j).rev() {
+            let sum = (i + 1..j).fold(real(T::zero()), |acc, k| acc + r[(i, k)] * r[(k, j)]);
+            let numerator = t[(i, j)] - sum;
+            let denominator = r[(i, i)] + r[(j, j)];
+            if denominator.norm() == T::zero() {
+                if numerator.norm() == T::zero() {
+                    continue;
+                }
+                return None;
+            }
+            r[(i, j)] = numerator / denominator;
+        }
+    }
+    Some(r)
+}
+
+impl<T: Float> CMatrix<T> {
+    /// Matrix exponential e^A
+    ///
+    /// Scaling and squaring with Padé approximants of degree 3 to 13
+    /// (Higham 2005). For upper triangular A the diagonal of the result is
+    /// set to the scalar exponentials of the diagonal of A. Matrices with
+    /// infinite or NaN entries give a matrix of NaN.
+    pub fn expm(&self) -> CMatrix<T> {
+        assert!(self.is_square(), "exponential of non-square matrix");
+        let finite = |x: &Complex<T>| x.real.is_finite() && x.imag.is_finite();
+        if !self.as_slice().iter().all(finite) {
+            return nan_matrix(self.rows());
+        }
+        let norm = self.norm_one();
+        let low = PADE.iter().find(|(theta, _)| norm <= from_f64::<T>(*theta));
+        let mut result = match low {
+            Some((_, b)) => pade_low(self, b),
+            None => {
+                let squarings = (norm / from_f64::<T>(THETA_13))
+                    .log2()
+                    .ceil()
+                    .max(T::zero());
+                let squarings = squarings.to_i32().expect("finite norm gives few squarings");
+                let mut result = pade_13(&self.scale(real(from_f64::<T>(2.0).powi(-squarings))));
+                for _ in 0..squarings {
+                    result = &result * &result;
+                }
+                result
+            }
+        };
+        let n = self.rows();
+        if (0..n).all(|j| (j + 1..n).all(|i| self[(i, j)].norm() == T::zero())) {
+            for i in 0..n {
+                result[(i, i)] = self[(i, i)].exp();
+            }
+        }
+        result
+    }
+
+    /// Principal matrix square root by the Schur method (Björck–Hammarling)
+    ///
+    /// Returns `None` when the matrix has no square root that is a function
+    /// of it, e.g. a singular Jordan block, or when the Schur decomposition
+    /// fails. Eigenvalues on the negative real axis get the root with
+    /// positive imaginary part.
+    pub fn sqrtm(&self) -> Option<CMatrix<T>> {
+        assert!(self.is_square(), "square root of non-square matrix");
+        let Schur { q, t } = self.schur()?;
+        let r = triangular_sqrt(&t)?;
+        Some(&(&q * &r) * &q.hermitian())
+    }
+
+    /// Principal matrix logarithm by inverse scaling and squaring
+    ///
+    /// Square roots of the Schur factor are taken until it is close to the
+    /// identity, then log(I + X) is approximated by 8-point Gauss–Legendre
+    /// quadrature of ∫₀¹ X(I + tX)⁻¹ dt, which equals the [8/8] Padé
+    /// approximant. Returns `None` for singular matrices and when the Schur
+    /// decomposition fails.
+    pub fn logm(&self) -> Option<CMatrix<T>> {
+        assert!(self.is_square(), "logarithm of non-square matrix");
+        let n = self.rows();
+        let Schur { q, t } = self.schur()?;
+        if (0..n).any(|i| t[(i, i)].norm() == T::zero()) {
+            return None;
+        }
+        let identity = CMatrix::identity(n);
+        let mut r = t.clone();
+        let mut roots = 0;
+        while (&r - &identity).norm_one() > from_f64::<T>(LOG_PADE_RADIUS) {
+            if roots == MAX_SQUARE_ROOTS {
+                return None;
+            }
+            r = triangular_sqrt(&r)?;
+            roots += 1;
+        }
+        let x = &r - &identity;
+        let half = from_f64::<T>(0.5);
+        let mut log = CMatrix::zeros(n, n);
+        for &(node, weight) in GAUSS_LEGENDRE_8.iter() {
+            for node in [
+                half - from_f64::<T>(node) * half,
+                half + from_f64::<T>(node) * half,
+            ] {
+                let shifted = &identity + &x.scale(real(node));
+                let term = shifted.lu().solve_matrix(&x)?;
+                log = &log + &term.scale(real(from_f64::<T>(weight) * half));
+            }
+        }
+        let mut log = log.scale(real(from_f64::<T>(2.0).powi(roots as i32)));
+        for i in 0..n {
+            log[(i, i)] = t[(i, i)].ln();
+        }
+        Some(&(&q * &log) * &q.hermitian())
+    }
+}
@@ -0,0 +1,460 @@
+//! Dense complex vectors and matrices with BLAS-style kernels
+//!
+//! Matrices are stored row-major. Level 2 and 3 operations follow BLAS
+//! conventions: [`gemv`] computes y ← αAx + βy and [`gemm`] computes
+//! C ← αAB + βC, with β = 0 overwriting the output regardless of its content.
+//! ```rust
+//! use ancomplex::*;
+//! use ancomplex::matrix::{CMatrix, CVector};
+//!
+//! let a = CMatrix::new(2, 2, vec![c64(1.0, 0.0), c64(0.0, 1.0), c64(0.0, -1.0), c64(2.0, 0.0)]);
+//! assert_eq!(a.hermitian(), a);
+//! let x = CVector::new(vec![c64(1.0, 0.0), c64(0.0, 1.0)]);
+//! let y = &a * &x;
+//! assert_eq!(y[0], c64(0.0, 0.0));
+//! assert_eq!(y[1], c64(0.0, 1.0));
+//! assert_eq!(x.dotc(&y), c64(1.0, 0.0));
+//! ```
+use crate::{complex, Complex};
+use num_traits::Float;
+use std::ops::{Add, Index, IndexMut, Mul, Neg, Sub};
+
+/// Edge of the square tiles [`gemm`] works on
+const BLOCK: usize = 64;
+
+fn zero<T: Float>() -> Complex<T> {
+    complex(T::zero(), T::zero())
+}
+
+/// √(Σ|xᵢ|²), scaled by the largest component so big entries do not overflow
+fn euclidean_norm<T: Float>(values: &[Complex<T>]) -> T {
+    let scale = values
+        .iter()
+        .fold(T::zero(), |acc, x| acc.max(x.real.abs()).max(x.imag.abs()));
+    if scale == T::zero() {
+        return T::zero();
+    }
+    let sum = values
+        .iter()
+        .fold(T::zero(), |acc, x| acc + x.unscale(scale).square_norm());
+    scale * sum.sqrt()
+}
+
+/// Dense column vector of complex numbers
+#[derive(Debug, Clone, PartialEq)]
+pub struct CVector<T> {
+    data: Vec<Complex<T>>,
+}
+
+impl<T: Float> CVector<T> {
+    pub fn new(data: Vec<Complex<T>>) -> Self {
+        CVector { data }
+    }
+
+    pub fn zeros(len: usize) -> Self {
+        CVector {
+            data: vec![zero(); len],
+        }
+    }
+
+    /// Vector from real entries
+    pub fn from_real(data: &[T]) -> Self {
+        Self::new(data.iter().map(|&x| complex(x, T::zero())).collect())
+    }
+
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    pub fn as_slice(&self) -> &[Complex<T>] {
+        &self.data
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [Complex<T>] {
+        &mut self.data
+    }
+
+    pub fn into_vec(self) -> Vec<Complex<T>> {
+        self.data
+    }
+
+    /// Conjugated dot product Σ conj(xᵢ)·yᵢ
+    pub fn dotc(&self, other: &Self) -> Complex<T> {
+        assert_eq!(self.len(), other.len(), "vector lengths differ");
+        self.data
+            .iter()
+            .zip(&other.data)
+            .fold(zero(), |acc, (x, y)| acc + x.conj() * *y)
+    }
+
+    /// Unconjugated dot product Σ xᵢ·yᵢ
+    pub fn dotu(&self, other: &Self) -> Complex<T> {
+        assert_eq!(self.len(), other.len(), "vector lengths differ");
+        self.data
+            .iter()
+            .zip(&other.data)
+            .fold(zero(), |acc, (x, y)| acc + *x * *y)
+    }
+
+    /// self ← α·x + self
+    pub fn axpy(&mut self, alpha: Complex<T>, x: &Self) {
+        assert_eq!(self.len(), x.len(), "vector lengths differ");
+        for (y, &x) in self.data.iter_mut().zip(&x.data) {
+            *y = *y + alpha * x;
+        }
+    }
+
+    /// Euclidean norm
+    pub fn norm(&self) -> T {
+        euclidean_norm(&self.data)
+    }
+
+    pub fn scale(&self, factor: Complex<T>) -> Self {
+        Self::new(self.data.iter().map(|&x| x * factor).collect())
+    }
+
+    pub fn conj(&self) -> Self {
+        Self::new(self.data.iter().map(|x| x.conj()).collect())
+    }
+}
+
+impl<T> Index<usize> for CVector<T> {
+    type Output = Complex<T>;
+    fn index(&self, index: usize) -> &Complex<T> {
+        &self.data[index]
+    }
+}
+
+impl<T> IndexMut<usize> for CVector<T> {
+    fn index_mut(&mut self, index: usize) -> &mut Complex<T> {
+        &mut self.data[index]
+    }
+}
+
+/// Dense row-major matrix of complex numbers
+#[derive(Debug, Clone, PartialEq)]
+pub struct CMatrix<T> {
+    rows: usize,
+    cols: usize,
+    data: Vec<Complex<T>>,
+}
+
+impl<T: Float> CMatrix<T> {
+    /// Creates matrix from row-major `data`
+    pub fn new(rows: usize, cols: usize, data: Vec<Complex<T>>) -> Self {
+        assert_eq!(data.len(), rows * cols, "data does not match matrix shape");
+        CMatrix { rows, cols, data }
+    }
+
+    pub fn zeros(rows: usize, cols: usize) -> Self {
+        Self::new(rows, cols, vec![zero(); rows * cols])
+    }
+
+    pub fn identity(n: usize) -> Self {
+        Self::from_fn(n, n, |i, j| {
+            if i == j {
+                complex(T::one(), T::zero())
+            } else {
+                zero()
+            }
+        })
+    }
+
+    /// Matrix with entry (i, j) equal to `f(i, j)`
+    pub fn from_fn<F: FnMut(usize, usize) -> Complex<T>>(
+        rows: usize,
+        cols: usize,
+        mut f: F,
+    ) -> Self {
+        let data = (0..rows * cols).map(|k| f(k / cols, k % cols)).collect();
+        CMatrix { rows, cols, data }
+    }
+
+    /// Square matrix with `diagonal` on its main diagonal
+    pub fn from_diagonal(diagonal: &[Complex<T>]) -> Self {
+        let n = diagonal.len();
+        Self::from_fn(n, n, |i, j| if i == j { diagonal[i] } else { zero() })
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn is_square(&self) -> bool {
+        self.rows == self.cols
+    }
+
+    /// Row-major entries
+    pub fn as_slice(&self) -> &[Complex<T>] {
+        &self.data
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [Complex<T>] {
+        &mut self.data
+    }
+
+    pub fn row(&self, i: usize) -> &[Complex<T>] {
+        &self.data[i * self.cols..(i + 1) * self.cols]
+    }
+
+    pub fn column(&self, j: usize) -> CVector<T> {
+        CVector::new((0..self.rows).map(|i| self[(i, j)]).collect())
+    }
+
+    pub fn diagonal(&self) -> CVector<T> {
+        CVector::new(
+            (0..self.rows.min(self.cols))
+                .map(|i| self[(i, i)])
+                .collect(),
+        )
+    }
+
+    pub fn transpose(&self) -> Self {
+        Self::from_fn(self.cols, self.rows, |i, j| self[(j, i)])
+    }
+
+    pub fn conj(&self) -> Self {
+        Self::new(
+            self.rows,
+            self.cols,
+            self.data.iter().map(|x| x.conj()).collect(),
+        )
+    }
+
+    /// Conjugate transpose Aᴴ
+    pub fn hermitian(&self) -> Self {
+        Self::from_fn(self.cols, self.rows, |i, j| self[(j, i)].conj())
+    }
+
+    /// Checks A = Aᴴ with entries compared up to `tol`
+    pub fn is_hermitian(&self, tol: T) -> bool {
+        self.is_square()
+            && (0..self.rows)
+                .all(|i| (i..self.cols).all(|j| (self[(i, j)] - self[(j, i)].conj()).norm() <= tol))
+    }
+
+    pub fn scale(&self, factor: Complex<T>) -> Self {
+        Self::new(
+            self.rows,
+            self.cols,
+            self.data.iter().map(|&x| x * factor).collect(),
+        )
+    }
+
+    /// Frobenius norm √(Σ|aᵢⱼ|²)
+    pub fn frobenius_norm(&self) -> T {
+        euclidean_norm(&self.data)
+    }
+
+    /// Maximum absolute column sum ‖A‖₁
+    pub fn norm_one(&self) -> T {
+        (0..self.cols).fold(T::zero(), |acc, j| {
+            acc.max((0..self.rows).fold(T::zero(), |sum, i| sum + self[(i, j)].norm()))
+        })
+    }
+
+    /// Maximum absolute row sum ‖A‖∞
+    pub fn norm_inf(&self) -> T {
+        (0..self.rows).fold(T::zero(), |acc, i| {
+            acc.max(self.row(i).iter().fold(T::zero(), |sum, x| sum + x.norm()))
+        })
+    }
+
+    /// Sum of diagonal entries
+    pub fn trace(&self) -> Complex<T> {
+        assert!(self.is_square(), "trace of non-square matrix");
+        (0..self.rows).fold(zero(), |acc, i| acc + self[(i, i)])
+    }
+
+    /// Kronecker product A ⊗ B
+    pub fn kron(&self, other: &Self) -> Self {
+        let (p, q) = (other.rows, other.cols);
+        Self::from_fn(self.rows * p, self.cols * q, |i, j| {
+            self[(i / p, j / q)] * other[(i % p, j % q)]
+        })
+    }
+}
+
+impl<T> Index<(usize, usize)> for CMatrix<T> {
+    type Output = Complex<T>;
+    fn index(&self, (i, j): (usize, usize)) -> &Complex<T> {
+        assert!(i < self.rows && j < self.cols, "index out of bounds");
+        &self.data[i * self.cols + j]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for CMatrix<T> {
+    fn index_mut(&mut self, (i, j): (usize, usize)) -> &mut Complex<T> {
+        assert!(i < self.rows && j < self.cols, "index out of bounds");
+        &mut self.data[i * self.cols + j]
+    }
+}
+
+/// y ← α·A·x + β·y
+pub fn gemv<T: Float>(
+    alpha: Complex<T>,
+    a: &CMatrix<T>,
+    x: &CVector<T>,
+    beta: Complex<T>,
+    y: &mut CVector<T>,
+) {
+    assert_eq!(a.cols, x.len(), "matrix columns do not match vector length");
+    assert_eq!(a.rows, y.len(), "matrix rows do not match output length");
+    for (i, y) in y.data.iter_mut().enumerate() {
+        let dot = a
+            .row(i)
+            .iter()
+            .zip(&x.data)
+            .fold(zero(), |acc, (aij, xj)| acc + *aij * *xj);
+        let scaled = if beta.norm() == T::zero() {
+            zero()
+        } else {
+            beta * *y
+        };
+        *y = alpha * dot + scaled;
+    }
+}
+
+/// C ← α·A·B + β·C, computed on cache-sized tiles
+pub fn gemm<T: Float>(
+    alpha: Complex<T>,
+    a: &CMatrix<T>,
+    b: &CMatrix<T>,
+    beta: Complex<T>,
+    c: &mut CMatrix<T>,
+) {
+    assert_eq!(a.cols, b.rows, "inner matrix dimensions differ");
+    assert!(
+        a.rows == c.rows && b.cols == c.cols,
+        "output shape does not match product"
+    );
+    if beta.norm() == T::zero() {
+        c.data.iter_mut().for_each(|x| *x = zero());
+    } else {
+        c.data.iter_mut().for_each(|x| *x *= beta);
+    }
+    let (m, n, p) = (a.rows, b.cols, a.cols);
+    for i0 in (0..m).step_by(BLOCK) {
+        for k0 in (0..p).step_by(BLOCK) {
+            for j0 in (0..n).step_by(BLOCK) {
+                for i in i0..(i0 + BLOCK).min(m) {
+                    let c_row = &mut c.data[i * n..(i + 1) * n];
+                    for k in k0..(k0 + BLOCK).min(p) {
+                        let aik = alpha * a.data[i * p + k];
+                        let b_row = &b.data[k * n..(k + 1) * n];
+                        for j in j0..(j0 + BLOCK).min(n) {
+                            c_row[j] = c_row[j] + aik * b_row[j];
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl<T: Float> Mul<&CMatrix<T>> for &CMatrix<T> {
+    type Output = CMatrix<T>;
+    fn mul(self, rhs: &CMatrix<T>) -> CMatrix<T> {
+        let mut out = CMatrix::zeros(self.rows, rhs.cols);
+        let one = complex(T::one(), T::zero());
+        gemm(one, self, rhs, zero(), &mut out);
+        out
+    }
+}
+
+impl<T: Float> Mul<&CVector<T>> for &CMatrix<T> {
+    type Output = CVector<T>;
+    fn mul(self, rhs: &CVector<T>) -> CVector<T> {
+        let mut out = CVector::zeros(self.rows);
+        gemv(complex(T::one(), T::zero()), self, rhs, zero(), &mut out);
+        out
+    }
+}
+
+impl<T: Float> Add<&CMatrix<T>> for &CMatrix<T> {
+    type Output = CMatrix<T>;
+    fn add(self, rhs: &CMatrix<T>) -> CMatrix<T> {
+        assert!(
+            self.rows == rhs.rows && self.cols == rhs.cols,
+            "matrix shapes differ"
+        );
+        let data = self
+            .data
+            .iter()
+            .zip(&rhs.data)
+            .map(|(x, y)| *x + *y)
+            .collect();
+        CMatrix::new(self.rows, self.cols, data)
+    }
+}
+
+impl<T: Float> Sub<&CMatrix<T>> for &CMatrix<T> {
+    type Output = CMatrix<T>;
+    fn sub(self, rhs: &CMatrix<T>) -> CMatrix<T> {
+        assert!(
+            self.rows == rhs.rows && self.cols == rhs.cols,
+            "matrix shapes differ"
+        );
+        let data = self
+            .data
+            .iter()
+            .zip(&rhs.data)
+            .map(|(x, y)| *x - *y)
+            .collect();
+        CMatrix::new(self.rows, self.cols, data)
+    }
+}
+
+impl<T: Float> Neg for &CMatrix<T> {
+    type Output = CMatrix<T>;
+    fn neg(self) -> CMatrix<T> {
+        CMatrix::new(
+            self.rows,
+            self.cols,
+            self.data.iter().map(|&x| -x).collect(),
+        )
+    }
+}
+
+impl<T: Float> Add<&CVector<T>> for &CVector<T> {
+    type Output = CVector<T>;
+    fn add(self, rhs: &CVector<T>) -> CVector<T> {
+        assert_eq!(self.len(), rhs.len(), "vector lengths differ");
+        CVector::new(
+            self.data
+                .iter()
+                .zip(&rhs.data)
+                .map(|(x, y)| *x + *y)
+                .collect(),
+        )
+    }
+}
+
+impl<T: Float> Sub<&CVector<T>> for &CVector<T> {
+    type Output = CVector<T>;
+    fn sub(self, rhs: &CVector<T>) -> CVector<T> {
+        assert_eq!(self.len(), rhs.len(), "vector lengths differ");
+        CVector::new(
+            self.data
+                .iter()
+                .zip(&rhs.data)
+                .map(|(x, y)| *x - *y)
+                .collect(),
+        )
+    }
+}
+
+impl<T: Float> Neg for &CVector<T> {
+    type Output = CVector<T>;
+    fn neg(self) -> CVector<T> {
+        CVector::new(self.data.iter().map(|&x| -x).collect())
+    }
+}
@@ -0,0 +1,269 @@
+//! Möbius transformations z ↦ (az + b)/(cz + d) of the extended complex plane
+//!
+//! Points are `Option<Complex<T>>`, where `None` is the point at infinity.
+//! The `_extended` variants take and return [`Extended`] points instead.
+//! Composition is matrix multiplication of the coefficients and is also
+//! available as `f * g`, which applies `g` first.
+//! ```rust
+//! use ancomplex::*;
+//! use ancomplex::mobius::{Mobius, MobiusKind};
+//!
+//! // Cayley transform maps the upper half-plane onto the unit disk
+//! let cayley = Mobius::new(c64(1.0, 0.0), c64(0.0, -1.0), c64(1.0, 0.0), c64(0.0, 1.0));
+//! assert_eq!(cayley.apply(Some(c64(0.0, 1.0))), Some(c64(0.0, 0.0)));
+//! assert_eq!(cayley.apply(Some(c64(0.0, -1.0))), None);
+//! assert_eq!(cayley.apply(None), Some(c64(1.0, 0.0)));
+//!
+//! let rotation = Mobius::new(c64(0.0, 1.0), c64(0.0, 0.0), c64(0.0, 0.0), c64(1.0, 0.0));
+//! assert_eq!(rotation.classify(1e-12), MobiusKind::Elliptic);
+//! ```
+use crate::cast::from_f64;
+use crate::extended::Extended;
+use crate::{complex, Complex};
+use num_traits::Float;
+use std::ops::Mul;
+
+fn zero<T: Float>() -> Complex<T> {
+    complex(T::zero(), T::zero())
+}
+
+fn one<T: Float>() -> Complex<T> {
+    complex(T::one(), T::zero())
+}
+
+/// Conjugacy class of a Möbius transformation, decided by tr² of the
+/// normalized coefficient matrix
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MobiusKind {
+    Identity,
+    /// tr² real in [0, 4), conjugate to a rotation
+    Elliptic,
+    /// tr² = 4, conjugate to a translation
+    Parabolic,
+    /// tr² real and greater than 4, conjugate to a real dilation
+    Hyperbolic,
+    /// Any other tr², conjugate to a dilation combined with a rotation
+    Loxodromic,
+}
+
+/// Möbius transformation (az + b)/(cz + d) with ad - bc ≠ 0
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Mobius<T> {
+    pub a: Complex<T>,
+    pub b: Complex<T>,
+    pub c: Complex<T>,
+    pub d: Complex<T>,
+}
+
+impl<T: Float> Mobius<T> {
+    /// Panics if ad - bc = 0
+    pub fn new(a: Complex<T>, b: Complex<T>, c: Complex<T>, d: Complex<T>) -> Self {
+        let map = Mobius { a, b, c, d };
+        assert!(
+            map.det().norm() != T::zero(),
+            "degenerate Möbius transformation"
+        );
+        map
+    }
+
+    pub fn identity() -> Self {
+        Mobius {
+            a: one(),
+            b: zero(),
+            c: zero(),
+            d: one(),
+        }
+    }
+
+    /// z ↦ z + shift
+    pub fn translation(shift: Complex<T>) -> Self {
+        Mobius {
+            b: shift,
+            ..Self::identity()
+        }
+    }
+
+    /// z ↦ factor·z, panics if factor is zero
+    pub fn dilation(factor: Complex<T>) -> Self {
+        Self::new(factor, zero(), zero(), one())
+    }
+
+    /// z ↦ 1/z
+    pub fn reciprocal() -> Self {
+        Mobius {
+            a: zero(),
+            b: one(),
+            c: one(),
+            d: zero(),
+        }
+    }
+
+    /// Automorphism z ↦ e^{iθ}(z - p)/(1 - p̄z) of the unit disk, an isometry
+    /// of the Poincaré disk moving `p` to the origin. Panics if |p| = 1.
+    pub fn disk_automorphism(theta: T, p: Complex<T>) -> Self {
+        let rotation = Complex::from_polar(T::one(), theta);
+        Self::new(rotation, -rotation * p, -p.conj(), one())
+    }
+
+    /// Determinant ad - bc of the coefficient matrix
+    pub fn det(&self) -> Complex<T> {
+        self.a * self.d - self.b * self.c
+    }
+
+    /// Same transformation with coefficients scaled to ad - bc = 1
+    pub fn normalize(&self) -> Self {
+        let s = self.det().sqrt();
+        Mobius {
+            a: self.a / s,
+            b: self.b / s,
+            c: self.c / s,
+            d: self.d / s,
+        }
+    }
+
+    /// Image of `z`, with `None` standing for the point at infinity
+    pub fn apply(&self, z: Option<Complex<T>>) -> Option<Complex<T>> {
+        match z {
+            Some(z) => {
+                let denominator = self.c * z + self.d;
+                if denominator.norm() == T::zero() {
+                    None
+                } else {
+                    Some((self.a * z + self.b) / denominator)
+                }
+            }
+            None if self.c.norm() == T::zero() => None,
+            None => Some(self.a / self.c),
+        }
+    }
+
+    /// self ∘ inner, the map applying `inner` first
+    pub fn compose(&self, inner: &Self) -> Self {
+        Mobius {
+            a: self.a * inner.a + self.b * inner.c,
+            b: self.a * inner.b + self.b * inner.d,
+            c: self.c * inner.a + self.d * inner.c,
+            d: self.c * inner.b + self.d * inner.d,
+        }
+    }
+
+    pub fn inverse(&self) -> Self {
+        Mobius {
+            a: self.d,
+            b: -self.b,
+            c: -self.c,
+            d: self.a,
+        }
+    }
+
+    /// Square of the normalized trace, (a + d)²/(ad - bc)
+    pub fn trace_squared(&self) -> Complex<T> {
+        let trace = self.a + self.d;
+        trace * trace / self.det()
+    }
+
+    /// Classifies the transformation, treating values within `tol` of the
+    /// boundaries (identity, tr² = 4, real tr²) as on them
+    pub fn classify(&self, tol: T) -> MobiusKind {
+        let normalized = self.normalize();
+        if normalized.b.norm() <= tol
+            && normalized.c.norm() <= tol
+            && (normalized.a - normalized.d).norm() <= tol
+        {
+            return MobiusKind::Identity;
+        }
+        let sigma = self.trace_squared();
+        let four = from_f64::<T>(4.0);
+        if (sigma - complex(four, T::zero())).norm() <= tol {
+            MobiusKind::Parabolic
+        } else if sigma.imag.abs() > tol || sigma.real < -tol {
+            MobiusKind::Loxodromic
+        } else if sigma.real < four {
+            MobiusKind::Elliptic
+        } else {
+            MobiusKind::Hyperbolic
+        }
+    }
+
+    /// Fixed points, with `None` for infinity
+    ///
+    /// Parabolic maps have one fixed point and the others two. The identity
+    /// fixes every point and returns an empty vector.
+    pub fn fixed_points(&self) -> Vec<Option<Complex<T>>> {
+        let difference = self.a - self.d;
+        if self.c.norm() == T::zero() {
+            return match (difference.norm() == T::zero(), self.b.norm() == T::zero()) {
+                (true, true) => vec![],
+                (true, false) => vec![None],
+                (false, _) => vec![None, Some(self.b / -difference)],
+            };
+        }
+        // cz² - (a - d)z - b = 0 with the larger root taken without cancellation
+        let discriminant = difference * difference + self.b * self.c * from_f64::<T>(4.0);
+        let s = discriminant.sqrt();
+        let q = if (difference + s).norm() >= (difference - s).norm() {
+            difference + s
+        } else {
+            difference - s
+        };
+        let first = q / (self.c + self.c);
+        if discriminant.norm() == T::zero() {
+            return vec![Some(first)];
+        }
+        let second = -(self.b + self.b) / q;
+        vec![Some(first), Some(second)]
+    }
+
+    /// Unique transformation mapping `from[k]` to `to[k]` for k = 0, 1, 2
+    ///
+    /// Returns `None` unless both triples consist of distinct points.
+    pub fn from_points(from: [Option<Complex<T>>; 3], to: [Option<Complex<T>>; 3]) -> Option<Self> {
+        let source = to_zero_infinity_one(from)?;
+        let target = to_zero_infinity_one(to)?;
+        Some(target.inverse().compose(&source))
+    }
+
+    /// [`Mobius::apply`] on the extended plane
+    pub fn apply_extended(&self, z: Extended<T>) -> Extended<T> {
+        self.apply(z.finite()).into()
+    }
+
+    /// [`Mobius::fixed_points`] on the extended plane
+    pub fn fixed_points_extended(&self) -> Vec<Extended<T>> {
+        self.fixed_points()
+            .into_iter()
+            .map(Extended::from)
+            .collect()
+    }
+
+    /// [`Mobius::from_points`] with triples on the extended plane
+    pub fn from_extended_points(from: [Extended<T>; 3], to: [Extended<T>; 3]) -> Option<Self> {
+        Self::from_points(from.map(|z| z.finite()), to.map(|z| z.finite()))
+    }
+}
+
+/// Map sending the three points to 0, ∞ and 1, `None` unless they are distinct
+fn to_zero_infinity_one<T: Float>(points: [Option<Complex<T>>; 3]) -> Option<Mobius<T>> {
+    let (a, b, c, d) = match points {
+        [Some(z1), Some(z2), Some(z3)] => (z3 - z2, -z1 * (z3 - z2), z3 - z1, -z2 * (z3 - z1)),
+        [None, Some(z2), Some(z3)] => (zero(), z3 - z2, one(), -z2),
+        [Some(z1), None, Some(z3)] => (one(), -z1, zero(), z3 - z1),
+        [Some(z1), Some(z2), None] => (one(), -z1, one(), -z2),
+        _ => return None,
+    };
+    let map = Mobius { a, b, c, d };
+    if map.det().norm() == T::zero() {
+        None
+    } else {
+        Some(map)
+    }
+}
+
+impl<T: Float> Mul for Mobius<T> {
+    type Output = Mobius<T>;
+
+    /// Composition, `f * g` applies `g` first
+    fn mul(self, rhs: Mobius<T>) -> Mobius<T> {
+        self.compose(&rhs)
+    }
+}
@@ -0,0 +1,492 @@
+//! Initial value problems y' = f(t, y) for complex state vectors
+//!
+//! [`rk4`] takes fixed steps, [`dormand_prince`] adapts its steps to an
+//! error target and [`implicit_midpoint`] is symplectic and conserves |y|²
+//! whenever Re⟨y, f(t, y)⟩ = 0, as for the Schrödinger equation
+//! y' = -iH(t)y with Hermitian H. All return a [`Solution`] with dense
+//! output and event location; Dormand–Prince steps use their own
+//! fourth-order continuous extension, the fixed-step methods cubic Hermite
+//! interpolation.
+//! ```rust
+//! use ancomplex::*;
+//! use ancomplex::ode::{dormand_prince, OdeConfig};
+//!
+//! // Rabi oscillation of a two-level system driven at frequency Ω = 2
+//! let rabi = |_t: f64, y: &[c64]| vec![-c64(0.0, 1.0) * y[1], -c64(0.0, 1.0) * y[0]];
+//! let config = OdeConfig { abs_tol: 1e-12, rel_tol: 1e-12, ..OdeConfig::default() };
+//! let solution = dormand_prince(rabi, 0.0, &[c64(1.0, 0.0), c64(0.0, 0.0)], 3.0, &config);
+//! assert!(solution.finished);
+//! let y = solution.final_state();
+//! assert!((y[1] - c64(0.0, -3f64.sin())).norm() < 1e-10);
+//!
+//! // The excited population first reaches one half at t = π/4
+//! let crossings = solution.events(|_t, y| y[1].square_norm() - 0.5, 1e-12);
+//! assert!((crossings[0] - std::f64::consts::FRAC_PI_4).abs() < 1e-8);
+//! ```
+use crate::cast::from_f64;
+use crate::{complex, Complex};
+use num_traits::Float;
+
+/// Dormand–Prince nodes cᵢ
+const DOPRI_C: [f64; 7] = [0.0, 0.2, 0.3, 0.8, 8.0 / 9.0, 1.0, 1.0];
+
+/// Dormand–Prince stage coefficients aᵢⱼ, the last row is the fifth-order
+/// solution and also the first stage of the next step
+const DOPRI_A: [&[f64]; 6] = [
+    &[0.2],
+    &[3.0 / 40.0, 9.0 / 40.0],
+    &[44.0 / 45.0, -56.0 / 15.0, 32.0 / 9.0],
+    &[
+        19372.0 / 6561.0,
+        -25360.0 / 2187.0,
+        64448.0 / 6561.0,
+        -212.0 / 729.0,
+    ],
+    &[
+        9017.0 / 3168.0,
+        -355.0 / 33.0,
+        46732.0 / 5247.0,
+        49.0 / 176.0,
+        -5103.0 / 18656.0,
+    ],
+    &[
+        35.0 / 384.0,
+        0.0,
+        500.0 / 1113.0,
+        125.0 / 192.0,
+        -2187.0 / 6784.0,
+        11.0 / 84.0,
+    ],
+];
+
+/// Difference between the fifth- and fourth-order weights
+const DOPRI_ERROR: [f64; 7] = [
+    71.0 / 57600.0,
+    0.0,
+    -71.0 / 16695.0,
+    71.0 / 1920.0,
+    -17253.0 / 339200.0,
+    22.0 / 525.0,
+    -1.0 / 40.0,
+];
+
+/// Weights dᵢ of the fourth-order dense output of Dormand–Prince (Hairer's
+/// DOPRI5), the second stage does not contribute
+const DOPRI_DENSE: [f64; 7] = [
+    -12715105075.0 / 11282082432.0,
+    0.0,
+    87487479700.0 / 32700410799.0,
+    -10690763975.0 / 1880347072.0,
+    701980252875.0 / 199316789632.0,
+    -1453857185.0 / 822651844.0,
+    69997945.0 / 29380423.0,
+];
+
+/// Safety factor and bounds on the step size ratio
+const SAFETY: f64 = 0.9;
+const MIN_FACTOR: f64 = 0.2;
+const MAX_FACTOR: f64 = 5.0;
+/// Fixed-point iterations allowed per implicit midpoint step
+const MAX_MIDPOINT_ITERATIONS: usize = 100;
+/// Bisections allowed when locating an event
+const MAX_EVENT_BISECTIONS: usize = 200;
+
+fn zero<T: Float>() -> Complex<T> {
+    complex(T::zero(), T::zero())
+}
+
+/// max |vᵢ|
+fn max_norm<T: Float>(v: &[Complex<T>]) -> T {
+    v.iter().fold(T::zero(), |acc, z| acc.max(z.norm()))
+}
+
+/// y + h·Σ cₖ·kₖ
+fn combine<T: Float>(y: &[Complex<T>], h: T, terms: &[(f64, &[Complex<T>])]) -> Vec<Complex<T>> {
+    let mut out = y.to_vec();
+    for &(c, k) in terms {
+        if c == 0.0 {
+            continue;
+        }
+        let weight = h * from_f64::<T>(c);
+        for (o, &z) in out.iter_mut().zip(k) {
+            *o = *o + z.scale(weight);
+        }
+    }
+    out
+}
+
+/// Tolerances and step limits of the adaptive integrator
+///
+/// A step is accepted when every component of its error estimate is within
+/// `abs_tol + rel_tol·|yᵢ|`. The first step is estimated from f(t₀, y₀)
+/// unless `initial_step` is given.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OdeConfig<T> {
+    pub abs_tol: T,
+    pub rel_tol: T,
+    pub initial_step: Option<T>,
+    pub max_step: T,
+    pub max_steps: usize,
+}
+
+impl<T: Float> Default for OdeConfig<T> {
+    /// Tolerances of 10⁻⁸, unbounded steps and at most 100000 of them
+    fn default() -> Self {
+        OdeConfig {
+            abs_tol: from_f64(1e-8),
+            rel_tol: from_f64(1e-8),
+            initial_step: None,
+            max_step: T::infinity(),
+            max_steps: 100_000,
+        }
+    }
+}
+
+/// Accepted steps of an integration with the derivative at every node
+#[derive(Debug, Clone, PartialEq)]
+pub struct Solution<T> {
+    /// Increasing times, starting at t₀
+    pub times: Vec<T>,
+    pub states: Vec<Vec<Complex<T>>>,
+    /// Calls of f
+    pub evaluations: usize,
+    /// False when the integration stopped before the final time
+    pub finished: bool,
+    derivatives: Vec<Vec<Complex<T>>>,
+    /// h·Σ dᵢkᵢ of every Dormand–Prince step, empty for the fixed-step methods
+    dense: Vec<Vec<Complex<T>>>,
+}
+
+impl<T: Float> Solution<T> {
+    fn start<F>(f: &F, t0: T, y0: &[Complex<T>]) -> Self
+    where
+        F: Fn(T, &[Complex<T>]) -> Vec<Complex<T>>,
+    {
+        Solution {
+            times: vec![t0],
+            states: vec![y0.to_vec()],
+            evaluations: 1,
+            finished: false,
+            derivatives: vec![f(t0, y0)],
+            dense: Vec::new(),
+        }
+    }
+
+    fn push(&mut self, t: T, y: Vec<Complex<T>>, dy: Vec<Complex<T>>) {
+        self.times.push(t);
+        self.states.push(y);
+        self.derivatives.push(dy);
+    }
+
+    /// State at the last accepted time
+    pub fn final_state(&self) -> &[Complex<T>] {
+        self.states.last().expect("solution has an initial state")
+    }
+
+    /// y'(t) = f(t, y) at the accepted times
+    pub fn derivatives(&self) -> &[Vec<Complex<T>>] {
+        &self.derivatives
+    }
+
+    /// State at time `t` interpolated between the neighbouring steps
+    ///
+    /// Dormand–Prince steps use the fourth-order continuous extension built
+    /// from their stages, the fixed-step methods cubic Hermite interpolation
+    /// of the states and derivatives, which is third order. Both match the
+    /// states and derivatives at the steps. Panics if `t` is outside the
+    /// integrated interval.
+    pub fn interpolate(&self, t: T) -> Vec<Complex<T>> {
+        let last = self.times.len() - 1;
+        assert!(
+            t >= self.times[0] && t <= self.times[last],
+            "time outside the integrated interval"
+        );
+        let i = self
+            .times
+            .partition_point(|&s| s <= t)
+            .clamp(1, last.max(1))
+            - 1;
+        if i == last {
+            return self.states[last].clone();
+        }
+        let h = self.times[i + 1] - self.times[i];
+        let s = (t - self.times[i]) / h;
+        let (y0, y1) = (&self.states[i], &self.states[i + 1]);
+        let (d0, d1) = (&self.derivatives[i], &self.derivatives[i + 1]);
+        if let Some(dense) = self.dense.get(i) {
+            // y0 + s(Δ + (1-s)(b + s(Δ - h·k₇ - b + (1-s)·h·Σ dᵢkᵢ))) with b = h·k₁ - Δ
+            let r = T::one() - s;
+            return (0..y0.len())
+                .map(|k| {
+                    let diff = y1[k] - y0[k];
+                    let b = d0[k].scale(h) - diff;
+                    let c = diff - d1[k].scale(h) - b;
+                    y0[k] + (diff + (b + (c + dense[k].scale(r)).scale(s)).scale(r)).scale(s)
+                })
+                .collect();
+        }
+        let (two, three) = (from_f64::<T>(2.0), from_f64::<T>(3.0));
+        let s2 = s * s;
+        let s3 = s2 * s;
+        let h00 = two * s3 - three * s2 + T::one();
+        let h10 = (s3 - two * s2 + s) * h;
+        let h01 = three * s2 - two * s3;
+        let h11 = (s3 - s2) * h;
+        (0..y0.len())
+            .map(|k| y0[k].scale(h00) + d0[k].scale(h10) + y1[k].scale(h01) + d1[k].scale(h11))
+            .collect()
+    }
+
+    /// Times where the real event function g(t, y) changes sign, located on
+    /// the dense output to within `tol`
+    ///
+    /// Sign changes are looked for between accepted steps, so a pair of
+    /// crossings inside one step is missed. Zeros of g at a node are
+    /// reported once.
+    pub fn events<G>(&self, g: G, tol: T) -> Vec<T>
+    where
+        G: Fn(T, &[Complex<T>]) -> T,
+    {
+        let half = from_f64::<T>(0.5);
+        let mut crossings = vec![];
+        let mut previous = g(self.times[0], &self.states[0]);
+        if previous == T::zero() {
+            crossings.push(self.times[0]);
+        }
+        for i in 1..self.times.len() {
+            let current = g(self.times[i], &self.states[i]);
+            if current == T::zero() {
+                crossings.push(self.times[i]);
+            } else if previous != T::zero() && (previous < T::zero()) != (current < T::zero()) {
+                let (mut a, mut b) = (self.times[i - 1], self.times[i]);
+                let mut left = previous;
+                for _ in 0..MAX_EVENT_BISECTIONS {
+                    if b - a <= tol {
+                        break;
+                    }
+                    let middle = (a + b) * half;
+                    let value = g(middle, &self.interpolate(middle));
+                    if value == T::zero() {
+                        a = middle;
+                        b = middle;
+                    } else if (value < T::zero()) == (left < T::zero()) {
+                        a = middle;
+                        left = value;
+                    } else {
+                        b = middle;
+                    }
+                }
+                crossings.push((a + b) * half);
+            }
+            previous = current;
+        }
+        crossings
+    }
+}
+
+/// Classical fourth-order Runge–Kutta with `steps` equal steps from `t0`
+/// to `t1`
+pub fn rk4<T, F>(f: F, t0: T, y0: &[Complex<T>], t1: T, steps: usize) -> Solution<T>
+where
+    T: Float,
+    F: Fn(T, &[Complex<T>]) -> Vec<Complex<T>>,
+{
+    assert!(
+        t1 > t0 && steps > 0,
+        "rk4 needs t1 > t0 and at least one step"
+    );
+    let h = (t1 - t0) / from_f64::<T>(steps as f64);
+    let half = h * from_f64::<T>(0.5);
+    let mut solution = Solution::start(&f, t0, y0);
+    for n in 0..steps {
+        let t = t0 + h * from_f64::<T>(n as f64);
+        let y = &solution.states[n];
+        let k1 = &solution.derivatives[n];
+        let k2 = f(t + half, &combine(y, h, &[(0.5, k1)]));
+        let k3 = f(t + half, &combine(y, h, &[(0.5, &k2)]));
+        let k4 = f(t + h, &combine(y, h, &[(1.0, &k3)]));
+        let next = combine(
+            y,
+            h,
+            &[
+                (1.0 / 6.0, k1),
+                (1.0 / 3.0, &k2),
+                (1.0 / 3.0, &k3),
+                (1.0 / 6.0, &k4),
+            ],
+        );
+        let t_next = if n + 1 == steps { t1 } else { t + h };
+        let dy = f(t_next, &next);
+        solution.evaluations += 4;
+        solution.push(t_next, next, dy);
+    }
+    solution.finished = true;
+    solution
+}
+
+/// Adaptive Dormand–Prince 5(4) from `t0` to `t1`
+///
+/// Steps are controlled by the embedded fourth-order estimate and the
+/// fifth-order solution is kept. The last stage is f at the new point, so
+/// an accepted step costs six evaluations. The integration stops early,
+/// with `finished` false, when the step falls below the rounding level of
+/// t, `max_steps` is reached or the state stops being finite.
+pub fn dormand_prince<T, F>(
+    f: F,
+    t0: T,
+    y0: &[Complex<T>],
+    t1: T,
+    config: &OdeConfig<T>,
+) -> Solution<T>
+where
+    T: Float,
+    F: Fn(T, &[Complex<T>]) -> Vec<Complex<T>>,
+{
+    assert!(t1 > t0, "dormand_prince needs t1 > t0");
+    let mut solution = Solution::start(&f, t0, y0);
+    let scale = |y: &[Complex<T>], k: usize| config.abs_tol + config.rel_tol * y[k].norm();
+    let mut h = match config.initial_step {
+        Some(h) => h,
+        None => {
+            // Step along which f moves y by about 1% of its size
+            let dy = &solution.derivatives[0];
+            let size = (0..y0.len()).fold(T::zero(), |acc, k| acc.max(y0[k].norm() / scale(y0, k)));
+            let speed =
+                (0..y0.len()).fold(T::zero(), |acc, k| acc.max(dy[k].norm() / scale(y0, k)));
+            let tiny = from_f64::<T>(1e-5);
+            if size < tiny || speed < tiny {
+                from_f64::<T>(1e-6)
+            } else {
+                from_f64::<T>(0.01) * size / speed
+            }
+        }
+    };
+    let (safety, min_factor, max_factor) = (
+        from_f64::<T>(SAFETY),
+        from_f64::<T>(MIN_FACTOR),
+        from_f64::<T>(MAX_FACTOR),
+    );
+    let exponent = from_f64::<T>(-0.2);
+    let mut t = t0;
+    let mut accepted = 0;
+    while t < t1 && accepted < config.max_steps {
+        h = h.min(config.max_step).min(t1 - t);
+        if h.is_nan() || h <= T::epsilon() * t.abs().max(T::one()) * from_f64::<T>(16.0) {
+            return solution;
+        }
+        let y = solution.final_state().to_vec();
+        let mut stages = vec![solution
+            .derivatives
+            .last()
+            .expect("initial derivative")
+            .clone()];
+        let mut next = y.clone();
+        for (i, row) in DOPRI_A.iter().enumerate() {
+            let terms: Vec<(f64, &[Complex<T>])> = row
+                .iter()
+                .zip(&stages)
+                .map(|(&a, k)| (a, k.as_slice()))
+                .collect();
+            next = combine(&y, h, &terms);
+            stages.push(f(t + h * from_f64::<T>(DOPRI_C[i + 1]), &next));
+        }
+        solution.evaluations += 6;
+        let terms: Vec<(f64, &[Complex<T>])> = DOPRI_ERROR
+            .iter()
+            .zip(&stages)
+            .map(|(&e, k)| (e, k.as_slice()))
+            .collect();
+        let estimate = combine(&vec![zero(); y.len()], h, &terms);
+        let error = (0..y.len()).fold(T::zero(), |acc, k| {
+            let tol = scale(&y, k).max(scale(&next, k));
+            acc.max(estimate[k].norm() / tol)
+        });
+        if !error.is_finite() || !max_norm(&next).is_finite() {
+            return solution;
+        }
+        let factor = if error == T::zero() {
+            max_factor
+        } else {
+            (safety * error.powf(exponent))
+                .max(min_factor)
+                .min(max_factor)
+        };
+        if error <= T::one() {
+            t = if t1 - t <= h { t1 } else { t + h };
+            let terms: Vec<(f64, &[Complex<T>])> = DOPRI_DENSE
+                .iter()
+                .zip(&stages)
+                .map(|(&d, k)| (d, k.as_slice()))
+                .collect();
+            solution
+                .dense
+                .push(combine(&vec![zero(); y.len()], h, &terms));
+            let dy = stages.pop().expect("last stage");
+            solution.push(t, next, dy);
+            accepted += 1;
+            h = h * factor;
+        } else {
+            h = h * factor.min(T::one());
+        }
+    }
+    solution.finished = t >= t1;
+    solution
+}
+
+/// Implicit midpoint rule y₊ = y + h·f(t + h/2, (y + y₊)/2) with `steps`
+/// equal steps from `t0` to `t1`
+///
+/// Second order, symplectic and time-reversible. Quadratic invariants are
+/// kept exactly, so the norm of a Schrödinger state is conserved to
+/// rounding for any step size, also for nonlinear terms such as g|y|²y.
+/// For linear f this is the Crank–Nicolson scheme. The implicit equation
+/// is solved by fixed-point iteration, which needs h·L < 2 for a Lipschitz
+/// constant L of f. If the iteration does not converge the integration
+/// stops with `finished` false.
+pub fn implicit_midpoint<T, F>(f: F, t0: T, y0: &[Complex<T>], t1: T, steps: usize) -> Solution<T>
+where
+    T: Float,
+    F: Fn(T, &[Complex<T>]) -> Vec<Complex<T>>,
+{
+    assert!(
+        t1 > t0 && steps > 0,
+        "implicit_midpoint needs t1 > t0 and at least one step"
+    );
+    let h = (t1 - t0) / from_f64::<T>(steps as f64);
+    let half = h * from_f64::<T>(0.5);
+    let tol = T::epsilon() * from_f64::<T>(16.0);
+    let mut solution = Solution::start(&f, t0, y0);
+    for n in 0..steps {
+        let t = t0 + h * from_f64::<T>(n as f64);
+        let y = solution.states[n].clone();
+        // Explicit Euler half step as the first guess of the midpoint
+        let mut midpoint = combine(&y, h, &[(0.5, &solution.derivatives[n])]);
+        let mut converged = false;
+        for _ in 0..MAX_MIDPOINT_ITERATIONS {
+            let slope = f(t + half, &midpoint);
+            solution.evaluations += 1;
+            let update = combine(&y, h, &[(0.5, &slope)]);
+            let change = (0..y.len()).fold(T::zero(), |acc, k| {
+                acc.max((update[k] - midpoint[k]).norm())
+            });
+            midpoint = update;
+            if change <= tol * max_norm(&midpoint).max(T::one()) {
+                converged = true;
+                break;
+            }
+            if !change.is_finite() {
+                break;
+            }
+        }
+        if !converged {
+            return solution;
+        }
+        let next: Vec<Complex<T>> = midpoint.iter().zip(&y).map(|(&m, &v)| m + m - v).collect();
+        let t_next = if n + 1 == steps { t1 } else { t + h };
+        let dy = f(t_next, &next);
+        solution.evaluations += 1;
+        solution.push(t_next, next, dy);
+    }
+    solution.finished = true;
+    solution
+}
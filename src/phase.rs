@@ -0,0 +1,118 @@
+//! Phase unwrapping and circular statistics over complex sequences
+//!
+//! Statistics treat every sample as a unit phasor z/|z|, samples with zero
+//! magnitude have no phase and are skipped. Without any phasor left, as for
+//! empty input, the statistics are NaN.
+//! ```rust
+//! use ancomplex::*;
+//! use ancomplex::phase::*;
+//!
+//! let samples = [c64(1.0, 0.0), c64(0.0, 2.0), c64(3.0, 3.0)];
+//! let mean = circular_mean(&samples);
+//! assert!((mean - std::f64::consts::FRAC_PI_4).abs() < 1e-12);
+//! ```
+use crate::cast::from_f64;
+use crate::{complex, Complex};
+use num_traits::{Float, FloatConst};
+
+/// Removes jumps between consecutive angles larger than `discont` by adding multiples of 2π
+///
+/// `discont` below π behaves like π, as in numpy's `unwrap`.
+pub fn unwrap_angles<T: Float + FloatConst>(angles: &[T], discont: T) -> Vec<T> {
+    let two_pi = T::PI() + T::PI();
+    let mut correction = T::zero();
+    let mut result = Vec::with_capacity(angles.len());
+    for (k, &angle) in angles.iter().enumerate() {
+        if k > 0 {
+            let delta = angle - angles[k - 1];
+            if delta.abs() >= discont {
+                let shifted = delta + T::PI();
+                let mut wrapped = shifted - (shifted / two_pi).floor() * two_pi - T::PI();
+                if wrapped == -T::PI() && delta > T::zero() {
+                    wrapped = T::PI();
+                }
+                correction = correction + wrapped - delta;
+            }
+        }
+        result.push(angle + correction);
+    }
+    result
+}
+
+/// Continuous phase of complex sequence, jumps larger than `discont` are unwrapped
+pub fn unwrap_phase<T: Float + FloatConst>(samples: &[Complex<T>], discont: T) -> Vec<T> {
+    let angles: Vec<T> = samples.iter().map(|z| z.arg()).collect();
+    unwrap_angles(&angles, discont)
+}
+
+/// Unit phasors of non-zero samples
+fn phasors<T: Float>(samples: &[Complex<T>]) -> impl Iterator<Item = Complex<T>> + '_ {
+    samples
+        .iter()
+        .filter(|z| z.norm() > T::zero())
+        .map(|z| z.unscale(z.norm()))
+}
+
+/// Mean resultant vector of unit phasors
+///
+/// Both components are NaN when no sample has a phase, including empty input.
+pub fn mean_resultant<T: Float>(samples: &[Complex<T>]) -> Complex<T> {
+    let (sum, count) = phasors(samples).fold(
+        (complex(T::zero(), T::zero()), 0usize),
+        |(sum, count), z| (sum + z, count + 1),
+    );
+    sum / from_f64::<T>(count as f64)
+}
+
+/// Mean resultant length R̄ in [0, 1], 1 means all phases are equal
+pub fn resultant_length<T: Float>(samples: &[Complex<T>]) -> T {
+    mean_resultant(samples).norm()
+}
+
+/// Circular mean of phases in (-π, π]
+pub fn circular_mean<T: Float>(samples: &[Complex<T>]) -> T {
+    mean_resultant(samples).arg()
+}
+
+/// Circular variance 1 - R̄
+pub fn circular_variance<T: Float>(samples: &[Complex<T>]) -> T {
+    T::one() - resultant_length(samples)
+}
+
+/// Circular standard deviation √(-2 ln R̄)
+pub fn circular_std<T: Float>(samples: &[Complex<T>]) -> T {
+    (-from_f64::<T>(2.0) * resultant_length(samples).ln()).sqrt()
+}
+
+/// Result of the Rayleigh test for non-uniformity of phases
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RayleighTest<T> {
+    /// Rayleigh's Z = n·R̄²
+    pub statistic: T,
+    /// Probability of getting at least this Z from uniformly distributed phases
+    pub p_value: T,
+}
+
+/// Rayleigh test of the null hypothesis that phases are uniformly distributed
+///
+/// p-value uses Zar's approximation, accurate for n ≥ 10.
+pub fn rayleigh_test<T: Float>(samples: &[Complex<T>]) -> RayleighTest<T> {
+    let n = from_f64::<T>(phasors(samples).count() as f64);
+    let r_bar = resultant_length(samples);
+    let r = n * r_bar;
+    let (one, two, four) = (T::one(), from_f64::<T>(2.0), from_f64::<T>(4.0));
+    let exponent = (one + four * n + four * (n * n - r * r)).sqrt() - (one + two * n);
+    RayleighTest {
+        statistic: n * r_bar * r_bar,
+        p_value: exponent.exp().min(one),
+    }
+}
+
+/// Phase-locking value |⟨e^(i(φ_a - φ_b))⟩| between two sequences
+///
+/// Pairs where either sample is zero are skipped.
+pub fn phase_locking_value<T: Float>(a: &[Complex<T>], b: &[Complex<T>]) -> T {
+    assert_eq!(a.len(), b.len(), "sequences must have equal length");
+    let differences: Vec<Complex<T>> = a.iter().zip(b).map(|(x, y)| *x * y.conj()).collect();
+    resultant_length(&differences)
+}
@@ -0,0 +1,357 @@
+//! Polynomials with complex coefficients and simultaneous root finding
+//!
+//! Coefficients are stored in ascending order, `coeffs()[k]` multiplies xᵏ.
+//! ```rust
+//! use ancomplex::*;
+//! use ancomplex::polynomial::Polynomial;
+//!
+//! // (x - 1)(x - i) = x² - (1 + i)x + i
+//! let p = Polynomial::new(vec![c64(0.0, 1.0), c64(-1.0, -1.0), c64(1.0, 0.0)]);
+//! let (value, slope) = p.eval_with_derivative(c64(1.0, 0.0));
+//! assert_eq!(value, c64(0.0, 0.0));
+//! assert_eq!(slope, c64(1.0, -1.0));
+//! let mut roots = p.roots();
+//! roots.sort_by(|a, b| a.imag.partial_cmp(&b.imag).unwrap());
+//! assert!((roots[0] - c64(1.0, 0.0)).norm() < 1e-12);
+//! assert!((roots[1] - c64(0.0, 1.0)).norm() < 1e-12);
+//! ```
+use crate::cast::from_f64;
+use crate::{complex, Complex};
+use num_traits::{Float, FloatConst};
+use std::ops::{Add, Mul, Neg, Sub};
+
+const MAX_ITERATIONS: usize = 500;
+
+fn zero<T: Float>() -> Complex<T> {
+    complex(T::zero(), T::zero())
+}
+
+fn one<T: Float>() -> Complex<T> {
+    complex(T::one(), T::zero())
+}
+
+/// Polynomial Σ c_k·xᵏ with complex coefficients
+#[derive(Debug, Clone, PartialEq)]
+pub struct Polynomial<T> {
+    coeffs: Vec<Complex<T>>,
+}
+
+impl<T: Float> Polynomial<T> {
+    /// Creates polynomial from ascending coefficients, trailing zeros are dropped
+    pub fn new(mut coeffs: Vec<Complex<T>>) -> Self {
+        while coeffs.len() > 1 && coeffs.last().map_or(false, |c| c.norm() == T::zero()) {
+            coeffs.pop();
+        }
+        if coeffs.is_empty() {
+            coeffs.push(zero());
+        }
+        Polynomial { coeffs }
+    }
+
+    /// Creates polynomial from real ascending coefficients
+    pub fn from_real(coeffs: &[T]) -> Self {
+        Self::new(coeffs.iter().map(|&c| complex(c, T::zero())).collect())
+    }
+
+    /// Monic polynomial Π(x - rᵢ)
+    pub fn from_roots(roots: &[Complex<T>]) -> Self {
+        let mut coeffs = vec![one()];
+        for &r in roots {
+            coeffs.push(zero());
+            for k in (0..coeffs.len()).rev() {
+                let lower = if k > 0 { coeffs[k - 1] } else { zero() };
+                coeffs[k] = lower - r * coeffs[k];
+            }
+        }
+        Polynomial { coeffs }
+    }
+
+    pub fn zero() -> Self {
+        Polynomial {
+            coeffs: vec![zero()],
+        }
+    }
+
+    pub fn one() -> Self {
+        Polynomial {
+            coeffs: vec![one()],
+        }
+    }
+
+    pub fn coeffs(&self) -> &[Complex<T>] {
+        &self.coeffs
+    }
+
+    /// Degree, the zero polynomial has degree 0
+    pub fn degree(&self) -> usize {
+        self.coeffs.len() - 1
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.coeffs.len() == 1 && self.coeffs[0].norm() == T::zero()
+    }
+
+    /// Value at `z` by Horner's scheme
+    pub fn eval(&self, z: Complex<T>) -> Complex<T> {
+        self.coeffs.iter().rev().fold(zero(), |acc, &c| acc * z + c)
+    }
+
+    /// Value and first derivative at `z` in a single Horner pass
+    pub fn eval_with_derivative(&self, z: Complex<T>) -> (Complex<T>, Complex<T>) {
+        self.coeffs
+            .iter()
+            .rev()
+            .fold((zero(), zero()), |(p, dp), &c| (p * z + c, dp * z + p))
+    }
+
+    pub fn derivative(&self) -> Self {
+        let coeffs = self
+            .coeffs
+            .iter()
+            .enumerate()
+            .skip(1)
+            .map(|(k, &c)| c * from_f64::<T>(k as f64))
+            .collect();
+        Self::new(coeffs)
+    }
+
+    /// Antiderivative with value `constant` at 0
+    pub fn integral(&self, constant: Complex<T>) -> Self {
+        let mut coeffs = Vec::with_capacity(self.coeffs.len() + 1);
+        coeffs.push(constant);
+        coeffs.extend(
+            self.coeffs
+                .iter()
+                .enumerate()
+                .map(|(k, &c)| c / from_f64::<T>((k + 1) as f64)),
+        );
+        Self::new(coeffs)
+    }
+
+    /// Composition p(q(x))
+    pub fn compose(&self, inner: &Self) -> Self {
+        self.coeffs.iter().rev().fold(Self::zero(), |acc, &c| {
+            &(&acc * inner) + &Self::new(vec![c])
+        })
+    }
+
+    /// Multiplies every coefficient by `factor`
+    pub fn scale(&self, factor: Complex<T>) -> Self {
+        Self::new(self.coeffs.iter().map(|&c| c * factor).collect())
+    }
+
+    /// Quotient and remainder of polynomial long division
+    pub fn div_rem(&self, divisor: &Self) -> (Self, Self) {
+        assert!(!divisor.is_zero(), "division by zero polynomial");
+        let n = divisor.degree();
+        if self.degree() < n {
+            return (Self::zero(), self.clone());
+        }
+        let lead = divisor.coeffs[n];
+        let mut remainder = self.coeffs.clone();
+        let mut quotient = vec![zero(); self.degree() - n + 1];
+        for k in (0..quotient.len()).rev() {
+            let q = remainder[k + n] / lead;
+            quotient[k] = q;
+            for (j, &d) in divisor.coeffs.iter().enumerate() {
+                remainder[k + j] = remainder[k + j] - q * d;
+            }
+        }
+        remainder.truncate(n.max(1));
+        (Self::new(quotient), Self::new(remainder))
+    }
+}
+
+impl<T: Float + FloatConst> Polynomial<T> {
+    /// All roots repeated by multiplicity, found by Aberth–Ehrlich iteration
+    ///
+    /// Every approximation is polished by Newton steps on the polynomial,
+    /// a step is kept only when it reduces the residual.
+    pub fn roots(&self) -> Vec<Complex<T>> {
+        let leading_zeros = self
+            .coeffs
+            .iter()
+            .take_while(|c| c.norm() == T::zero())
+            .count()
+            .min(self.degree());
+        let reduced = Self::new(self.coeffs[leading_zeros..].to_vec());
+        let mut roots = vec![zero(); leading_zeros];
+        let mut found = aberth(&reduced);
+        for z in found.iter_mut() {
+            *z = reduced.polish(*z);
+        }
+        roots.extend(found);
+        roots
+    }
+
+    /// Distinct roots with multiplicities
+    ///
+    /// Approximations closer than `tol·(1 + |z|)` form a cluster of a multiple
+    /// root, its mean is refined by Newton on the derivative of order m - 1,
+    /// where the multiple root is simple.
+    pub fn roots_with_multiplicity(&self, tol: T) -> Vec<(Complex<T>, usize)> {
+        let mut pending = self.roots();
+        let mut result = Vec::new();
+        while let Some(seed) = pending.pop() {
+            let mut cluster = vec![seed];
+            let mut k = 0;
+            while k < pending.len() {
+                let close = cluster
+                    .iter()
+                    .any(|z| (pending[k] - *z).norm() <= tol * (T::one() + z.norm()));
+                if close {
+                    cluster.push(pending.swap_remove(k));
+                    k = 0;
+                } else {
+                    k += 1;
+                }
+            }
+            let m = cluster.len();
+            let mean = cluster.iter().fold(zero(), |acc, &z| acc + z) / from_f64::<T>(m as f64);
+            let derivative = (1..m).fold(self.clone(), |p, _| p.derivative());
+            result.push((derivative.polish(mean), m));
+        }
+        result
+    }
+
+    /// Newton refinement of a simple root approximation
+    fn polish(&self, mut z: Complex<T>) -> Complex<T> {
+        let mut residual = self.eval(z).norm();
+        for _ in 0..8 {
+            let (p, dp) = self.eval_with_derivative(z);
+            if residual == T::zero() || dp.norm() == T::zero() {
+                break;
+            }
+            let candidate = z - p / dp;
+            let candidate_residual = self.eval(candidate).norm();
+            if candidate_residual >= residual {
+                break;
+            }
+            z = candidate;
+            residual = candidate_residual;
+        }
+        z
+    }
+}
+
+/// Simultaneous Aberth–Ehrlich iteration for polynomial with nonzero constant term
+fn aberth<T: Float + FloatConst>(p: &Polynomial<T>) -> Vec<Complex<T>> {
+    let n = p.degree();
+    if n == 0 {
+        return Vec::new();
+    }
+    let lead = p.coeffs[n];
+    let radius = (p.coeffs[0] / lead)
+        .norm()
+        .powf(T::one() / from_f64::<T>(n as f64));
+    let center = -p.coeffs[n - 1] / (lead * from_f64::<T>(n as f64));
+    // Offset angle keeps starting points off symmetry axes of real polynomials
+    let mut roots: Vec<Complex<T>> = (0..n)
+        .map(|k| {
+            let angle =
+                (T::PI() + T::PI()) * from_f64::<T>(k as f64 + 0.25) / from_f64::<T>(n as f64);
+            center + Complex::from_polar(radius, angle)
+        })
+        .collect();
+    let mut converged = vec![false; n];
+    for _ in 0..MAX_ITERATIONS {
+        for i in 0..n {
+            if converged[i] {
+                continue;
+            }
+            let (value, slope) = p.eval_with_derivative(roots[i]);
+            if value.norm() == T::zero() {
+                converged[i] = true;
+                continue;
+            }
+            let ratio = value / slope;
+            let repulsion = (0..n)
+                .filter(|&j| j != i)
+                .fold(zero(), |acc, j| acc + (roots[i] - roots[j]).inv());
+            let step = ratio / (one() - ratio * repulsion);
+            if !step.real.is_finite() || !step.imag.is_finite() {
+                continue;
+            }
+            roots[i] = roots[i] - step;
+            if step.norm() <= T::epsilon() * roots[i].norm() {
+                converged[i] = true;
+            }
+        }
+        if converged.iter().all(|&c| c) {
+            break;
+        }
+    }
+    roots
+}
+
+impl<T: Float> Add<&Polynomial<T>> for &Polynomial<T> {
+    type Output = Polynomial<T>;
+    fn add(self, rhs: &Polynomial<T>) -> Polynomial<T> {
+        let len = self.coeffs.len().max(rhs.coeffs.len());
+        let at = |p: &Polynomial<T>, k: usize| p.coeffs.get(k).copied().unwrap_or_else(zero);
+        Polynomial::new((0..len).map(|k| at(self, k) + at(rhs, k)).collect())
+    }
+}
+
+impl<T: Float> Sub<&Polynomial<T>> for &Polynomial<T> {
+    type Output = Polynomial<T>;
+    fn sub(self, rhs: &Polynomial<T>) -> Polynomial<T> {
+        self + &(-rhs)
+    }
+}
+
+impl<T: Float> Mul<&Polynomial<T>> for &Polynomial<T> {
+    type Output = Polynomial<T>;
+    fn mul(self, rhs: &Polynomial<T>) -> Polynomial<T> {
+        let mut coeffs = vec![zero(); self.coeffs.len() + rhs.coeffs.len() - 1];
+        for (i, &a) in self.coeffs.iter().enumerate() {
+            for (j, &b) in rhs.coeffs.iter().enumerate() {
+                coeffs[i + j] = coeffs[i + j] + a * b;
+            }
+        }
+        Polynomial::new(coeffs)
+    }
+}
+
+impl<T: Float> Neg for &Polynomial<T> {
+    type Output = Polynomial<T>;
+    fn neg(self) -> Polynomial<T> {
+        Polynomial::new(self.coeffs.iter().map(|&c| -c).collect())
+    }
+}
+
+impl<T: Float> Neg for Polynomial<T> {
+    type Output = Polynomial<T>;
+    fn neg(self) -> Polynomial<T> {
+        -&self
+    }
+}
+
+macro_rules! forward_owned_ops {
+    ($($imp:ident::$method:ident),*) => {
+        $(
+            impl<T: Float> $imp<Polynomial<T>> for Polynomial<T> {
+                type Output = Polynomial<T>;
+                fn $method(self, rhs: Polynomial<T>) -> Polynomial<T> {
+                    (&self).$method(&rhs)
+                }
+            }
+
+            impl<'a, T: Float> $imp<&'a Polynomial<T>> for Polynomial<T> {
+                type Output = Polynomial<T>;
+                fn $method(self, rhs: &'a Polynomial<T>) -> Polynomial<T> {
+                    (&self).$method(rhs)
+                }
+            }
+
+            impl<'a, T: Float> $imp<Polynomial<T>> for &'a Polynomial<T> {
+                type Output = Polynomial<T>;
+                fn $method(self, rhs: Polynomial<T>) -> Polynomial<T> {
+                    self.$method(&rhs)
+                }
+            }
+        )*
+    };
+}
+
+forward_owned_ops!(Add::add, Sub::sub, Mul::mul);
@@ -0,0 +1,214 @@
+//! Iterative root finding for analytic functions
+//!
+//! All solvers share [`RootConfig`] and report a [`RootResult`]. Newton and
+//! Halley need derivatives. The secant method and Muller's method only
+//! evaluate the function, and Muller's method leaves the real axis on its
+//! own, so it finds complex roots of real functions from real starting points.
+//! ```rust
+//! use ancomplex::*;
+//! use ancomplex::roots::{muller, newton, RootConfig};
+//!
+//! // Lambert W: w·e^w = 1
+//! let config = RootConfig::default();
+//! let f = |w: c64| w * w.exp() - c64(1.0, 0.0);
+//! let df = |w: c64| (w + c64(1.0, 0.0)) * w.exp();
+//! let result = newton(f, df, c64(1.0, 0.0), &config);
+//! assert!(result.converged);
+//! assert!((result.root.real - 0.567_143_290_409_783_8).abs() < 1e-15);
+//!
+//! // z² + 1 from real starting points
+//! let result = muller(|z: c64| z * z + c64(1.0, 0.0), [c64(-1.0, 0.0), c64(0.0, 0.0), c64(1.0, 0.0)], &config);
+//! assert!(result.converged && (result.root.imag.abs() - 1.0).abs() < 1e-15);
+//! ```
+use crate::cast::from_f64;
+use crate::Complex;
+use num_traits::Float;
+
+fn is_finite<T: Float>(z: Complex<T>) -> bool {
+    z.real.is_finite() && z.imag.is_finite()
+}
+
+fn is_zero<T: Float>(z: Complex<T>) -> bool {
+    z.real == T::zero() && z.imag == T::zero()
+}
+
+/// Stopping rules shared by the solvers
+///
+/// Iteration stops with success when a step is no larger than
+/// `abs_tol + rel_tol·|z|` or when |f(z)| ≤ `residual_tol`, and with
+/// failure after `max_iterations` steps or at a non-finite iterate.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RootConfig<T> {
+    pub abs_tol: T,
+    pub rel_tol: T,
+    pub residual_tol: T,
+    pub max_iterations: usize,
+}
+
+impl<T: Float> Default for RootConfig<T> {
+    /// Steps down to a few ulps of the root, no residual test and 100 iterations
+    fn default() -> Self {
+        RootConfig {
+            abs_tol: T::min_positive_value(),
+            rel_tol: T::epsilon() * from_f64::<T>(4.0),
+            residual_tol: T::zero(),
+            max_iterations: 100,
+        }
+    }
+}
+
+impl<T: Float> RootConfig<T> {
+    fn small_step(&self, step: Complex<T>, z: Complex<T>) -> bool {
+        step.norm() <= self.abs_tol + self.rel_tol * z.norm()
+    }
+
+    fn small_residual(&self, value: Complex<T>) -> bool {
+        value.norm() <= self.residual_tol
+    }
+}
+
+/// Outcome of an iterative solver
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RootResult<T> {
+    /// Last iterate, the root when `converged`
+    pub root: Complex<T>,
+    /// |f(root)|
+    pub residual: T,
+    pub iterations: usize,
+    pub converged: bool,
+}
+
+/// Iterates z ← z - step(z) until the configured stopping rules apply and
+/// reports the result with residual |f(z)|
+///
+/// `step` returns the correction and f(z), or `None` when it is undefined.
+fn iterate<T, F, S>(f: F, mut z: Complex<T>, config: &RootConfig<T>, mut step: S) -> RootResult<T>
+where
+    T: Float,
+    F: Fn(Complex<T>) -> Complex<T>,
+    S: FnMut(Complex<T>) -> Option<(Complex<T>, Complex<T>)>,
+{
+    let mut iterations = 0;
+    let converged = loop {
+        let (correction, value) = match step(z) {
+            Some(pair) => pair,
+            None => break false,
+        };
+        if is_zero(value) || config.small_residual(value) {
+            break true;
+        }
+        if iterations == config.max_iterations || !is_finite(correction) {
+            break false;
+        }
+        z = z - correction;
+        iterations += 1;
+        if config.small_step(correction, z) {
+            break true;
+        }
+    };
+    RootResult {
+        root: z,
+        residual: f(z).norm(),
+        iterations,
+        converged,
+    }
+}
+
+/// Newton's method z ← z - f(z)/f'(z), quadratically convergent at simple roots
+pub fn newton<T, F, D>(f: F, df: D, z0: Complex<T>, config: &RootConfig<T>) -> RootResult<T>
+where
+    T: Float,
+    F: Fn(Complex<T>) -> Complex<T>,
+    D: Fn(Complex<T>) -> Complex<T>,
+{
+    iterate(&f, z0, config, |z| {
+        let value = f(z);
+        let slope = df(z);
+        if !is_finite(value) || (is_zero(slope) && !is_zero(value)) {
+            return None;
+        }
+        Some((value / slope, value))
+    })
+}
+
+/// Halley's method z ← z - 2ff'/(2f'² - ff''), cubically convergent at
+/// simple roots
+pub fn halley<T, F, D, D2>(
+    f: F,
+    df: D,
+    d2f: D2,
+    z0: Complex<T>,
+    config: &RootConfig<T>,
+) -> RootResult<T>
+where
+    T: Float,
+    F: Fn(Complex<T>) -> Complex<T>,
+    D: Fn(Complex<T>) -> Complex<T>,
+    D2: Fn(Complex<T>) -> Complex<T>,
+{
+    iterate(&f, z0, config, |z| {
+        let value = f(z);
+        let slope = df(z);
+        let curvature = d2f(z);
+        let numerator = value * slope;
+        let denominator = slope * slope - value * curvature.scale(from_f64::<T>(0.5));
+        if !is_finite(value) || (is_zero(denominator) && !is_zero(value)) {
+            return None;
+        }
+        Some((numerator / denominator, value))
+    })
+}
+
+/// Secant method from two starting points, superlinear with order 1.618
+pub fn secant<T, F>(f: F, z0: Complex<T>, z1: Complex<T>, config: &RootConfig<T>) -> RootResult<T>
+where
+    T: Float,
+    F: Fn(Complex<T>) -> Complex<T>,
+{
+    let mut previous = (z0, f(z0));
+    iterate(&f, z1, config, |z| {
+        let value = f(z);
+        let (z_prev, f_prev) = previous;
+        let difference = value - f_prev;
+        if !is_finite(value) || (is_zero(difference) && !is_zero(value)) {
+            return None;
+        }
+        previous = (z, value);
+        Some((value * (z - z_prev) / difference, value))
+    })
+}
+
+/// Muller's method through the parabola interpolating the last three
+/// iterates, with order 1.84 and no derivatives
+pub fn muller<T, F>(f: F, start: [Complex<T>; 3], config: &RootConfig<T>) -> RootResult<T>
+where
+    T: Float,
+    F: Fn(Complex<T>) -> Complex<T>,
+{
+    let [z0, z1, z2] = start;
+    let mut history = [(z0, f(z0)), (z1, f(z1))];
+    iterate(&f, z2, config, |z| {
+        let value = f(z);
+        if !is_finite(value) {
+            return None;
+        }
+        let [(x0, f0), (x1, f1)] = history;
+        history = [(x1, f1), (z, value)];
+        // Divided differences of the interpolating parabola around z
+        let d01 = (f1 - f0) / (x1 - x0);
+        let d12 = (value - f1) / (z - x1);
+        let a = (d12 - d01) / (z - x0);
+        let b = d12 + a * (z - x1);
+        let root = (b * b - a * value.scale(from_f64::<T>(4.0))).sqrt();
+        // Denominator of larger magnitude picks the root nearer to z
+        let denominator = if (b + root).norm() >= (b - root).norm() {
+            b + root
+        } else {
+            b - root
+        };
+        if is_zero(denominator) && !is_zero(value) {
+            return None;
+        }
+        Some((value.scale(from_f64::<T>(2.0)) / denominator, value))
+    })
+}
@@ -0,0 +1,490 @@
+//! Truncated power series, Laurent series and Padé approximants
+//!
+//! A [`PowerSeries`] with precision n knows the coefficients of 1, x, …,
+//! xⁿ⁻¹ and stands for Σ c_k·xᵏ + O(xⁿ). Arithmetic keeps the smaller
+//! precision of its operands, so results never claim more terms than are
+//! known.
+//! ```rust
+//! use ancomplex::*;
+//! use ancomplex::series::PowerSeries;
+//!
+//! // exp(x) from its differential equation, then log(exp(x)) = x
+//! let x = PowerSeries::<f64>::variable(8);
+//! let e = x.exp();
+//! assert!((e.coeffs()[5] - c64(1.0 / 120.0, 0.0)).norm() < 1e-17);
+//! let back = e.ln().unwrap();
+//! assert!((back.coeffs()[1] - c64(1.0, 0.0)).norm() < 1e-15);
+//! assert!(back.coeffs()[4].norm() < 1e-15);
+//!
+//! // [2/2] Padé approximant of eˣ is (12 + 6x + x²)/(12 - 6x + x²)
+//! let pade = e.pade(2, 2).unwrap();
+//! let z = c64(1.0, 0.0);
+//! assert!((pade.eval(z) - c64(19.0 / 7.0, 0.0)).norm() < 1e-14);
+//! ```
+use crate::cast::from_f64;
+use crate::matrix::{CMatrix, CVector};
+use crate::polynomial::Polynomial;
+use crate::{complex, Complex};
+use num_traits::Float;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+fn zero<T: Float>() -> Complex<T> {
+    complex(T::zero(), T::zero())
+}
+
+fn one<T: Float>() -> Complex<T> {
+    complex(T::one(), T::zero())
+}
+
+fn is_zero<T: Float>(z: Complex<T>) -> bool {
+    z.real == T::zero() && z.imag == T::zero()
+}
+
+/// Power series Σ c_k·xᵏ + O(xⁿ) with complex coefficients
+#[derive(Debug, Clone, PartialEq)]
+pub struct PowerSeries<T> {
+    coeffs: Vec<Complex<T>>,
+}
+
+impl<T: Float> PowerSeries<T> {
+    /// Series with the given ascending coefficients, the precision is their number
+    pub fn new(coeffs: Vec<Complex<T>>) -> Self {
+        assert!(
+            !coeffs.is_empty(),
+            "power series needs a precision of at least 1"
+        );
+        PowerSeries { coeffs }
+    }
+
+    /// c + O(xⁿ)
+    pub fn constant(c: Complex<T>, precision: usize) -> Self {
+        let mut coeffs = vec![zero(); precision];
+        coeffs[0] = c;
+        Self::new(coeffs)
+    }
+
+    /// x + O(xⁿ)
+    pub fn variable(precision: usize) -> Self {
+        assert!(precision >= 2, "variable needs a precision of at least 2");
+        let mut coeffs = vec![zero(); precision];
+        coeffs[1] = one();
+        Self::new(coeffs)
+    }
+
+    /// Polynomial truncated to `precision` terms
+    pub fn from_polynomial(p: &Polynomial<T>, precision: usize) -> Self {
+        let coeffs = (0..precision)
+            .map(|k| p.coeffs().get(k).copied().unwrap_or_else(zero))
+            .collect();
+        Self::new(coeffs)
+    }
+
+    pub fn coeffs(&self) -> &[Complex<T>] {
+        &self.coeffs
+    }
+
+    /// Number of known coefficients n in O(xⁿ)
+    pub fn precision(&self) -> usize {
+        self.coeffs.len()
+    }
+
+    /// Same series with at most `precision` terms
+    pub fn truncate(&self, precision: usize) -> Self {
+        Self::new(self.coeffs[..precision.min(self.coeffs.len())].to_vec())
+    }
+
+    /// Sum of the known terms at `z` by Horner's scheme
+    pub fn eval(&self, z: Complex<T>) -> Complex<T> {
+        self.coeffs.iter().rev().fold(zero(), |acc, &c| acc * z + c)
+    }
+
+    pub fn scale(&self, factor: Complex<T>) -> Self {
+        Self::new(self.coeffs.iter().map(|&c| c * factor).collect())
+    }
+
+    /// Term-by-term derivative, which loses one term of precision
+    pub fn derivative(&self) -> Self {
+        if self.coeffs.len() == 1 {
+            return Self::new(vec![zero()]);
+        }
+        let coeffs = self
+            .coeffs
+            .iter()
+            .enumerate()
+            .skip(1)
+            .map(|(k, &c)| c * from_f64::<T>(k as f64))
+            .collect();
+        Self::new(coeffs)
+    }
+
+    /// Antiderivative with constant term `constant`, gaining one term
+    pub fn integral(&self, constant: Complex<T>) -> Self {
+        let mut coeffs = Vec::with_capacity(self.coeffs.len() + 1);
+        coeffs.push(constant);
+        coeffs.extend(
+            self.coeffs
+                .iter()
+                .enumerate()
+                .map(|(k, &c)| c / from_f64::<T>((k + 1) as f64)),
+        );
+        Self::new(coeffs)
+    }
+
+    /// 1/f, `None` when the constant term is zero
+    pub fn inv(&self) -> Option<Self> {
+        let c0 = self.coeffs[0];
+        if is_zero(c0) {
+            return None;
+        }
+        let n = self.coeffs.len();
+        let mut inverse = vec![c0.inv()];
+        for k in 1..n {
+            let sum = (1..=k).fold(zero(), |acc, j| acc + self.coeffs[j] * inverse[k - j]);
+            inverse.push(-sum / c0);
+        }
+        Some(Self::new(inverse))
+    }
+
+    /// f(g) for inner series g without constant term
+    pub fn compose(&self, inner: &Self) -> Self {
+        assert!(
+            is_zero(inner.coeffs[0]),
+            "inner series of a composition must have zero constant term"
+        );
+        let precision = self.precision().min(inner.precision());
+        let inner = inner.truncate(precision);
+        self.coeffs[..precision]
+            .iter()
+            .rev()
+            .fold(Self::constant(zero(), precision), |acc, &c| {
+                let mut next = &acc * &inner;
+                next.coeffs[0] = next.coeffs[0] + c;
+                next
+            })
+    }
+
+    /// Compositional inverse g with f(g(x)) = x
+    ///
+    /// Needs a zero constant term and returns `None` when the linear
+    /// coefficient is zero.
+    pub fn reversion(&self) -> Option<Self> {
+        assert!(
+            is_zero(self.coeffs[0]),
+            "reverted series must have zero constant term"
+        );
+        let n = self.precision();
+        if n < 2 || is_zero(self.coeffs[1]) {
+            return None;
+        }
+        let slope = self.coeffs[1];
+        let mut g = vec![zero(); n];
+        g[1] = slope.inv();
+        // The xᵏ coefficient of f(g) depends on g_k only through slope·g_k
+        for k in 2..n {
+            let partial = self.truncate(k + 1).compose(&Self::new(g[..=k].to_vec()));
+            g[k] = -partial.coeffs[k] / slope;
+        }
+        Some(Self::new(g))
+    }
+
+    /// e^f from g' = f'·g
+    pub fn exp(&self) -> Self {
+        let n = self.precision();
+        let mut g = vec![self.coeffs[0].exp()];
+        for k in 1..n {
+            let sum = (1..=k).fold(zero(), |acc, j| {
+                acc + self.coeffs[j] * g[k - j] * from_f64::<T>(j as f64)
+            });
+            g.push(sum / from_f64::<T>(k as f64));
+        }
+        Self::new(g)
+    }
+
+    /// Principal logarithm ln c₀ + ∫ f'/f, `None` when the constant term is zero
+    pub fn ln(&self) -> Option<Self> {
+        let quotient = &self.derivative() * &self.inv()?;
+        Some(
+            quotient
+                .integral(self.coeffs[0].ln())
+                .truncate(self.precision()),
+        )
+    }
+
+    /// Principal square root, `None` when the constant term is zero
+    pub fn sqrt(&self) -> Option<Self> {
+        let c0 = self.coeffs[0];
+        if is_zero(c0) {
+            return None;
+        }
+        let n = self.precision();
+        let mut g = vec![c0.sqrt()];
+        let twice = g[0] + g[0];
+        for k in 1..n {
+            let sum = (1..k).fold(zero(), |acc, j| acc + g[j] * g[k - j]);
+            g.push((self.coeffs[k] - sum) / twice);
+        }
+        Some(Self::new(g))
+    }
+
+    /// Padé approximant p/q with deg p ≤ m, deg q ≤ n and q(0) = 1
+    /// matching the series through xᵐ⁺ⁿ
+    ///
+    /// Returns `None` when the linear system for q is singular. Panics if
+    /// the precision is below m + n + 1.
+    pub fn pade(&self, m: usize, n: usize) -> Option<Pade<T>> {
+        assert!(
+            self.precision() > m + n,
+            "Padé approximant needs m + n + 1 coefficients"
+        );
+        let c = |i: isize| {
+            if i < 0 {
+                zero()
+            } else {
+                self.coeffs[i as usize]
+            }
+        };
+        // Σ_{j=1..n} q_j·c_{m+k-j} = -c_{m+k} for k = 1..n
+        let mut q = vec![one()];
+        if n > 0 {
+            let system = CMatrix::from_fn(n, n, |row, col| c((m + row) as isize - col as isize));
+            let rhs = CVector::new((1..=n).map(|k| -c((m + k) as isize)).collect());
+            let solution = system.lu().solve(&rhs)?;
+            q.extend_from_slice(solution.as_slice());
+        }
+        let p = (0..=m)
+            .map(|i| (0..=i.min(n)).fold(zero(), |acc, j| acc + q[j] * c((i - j) as isize)))
+            .collect();
+        Some(Pade {
+            numerator: Polynomial::new(p),
+            denominator: Polynomial::new(q),
+        })
+    }
+}
+
+impl<T: Float> Add<&PowerSeries<T>> for &PowerSeries<T> {
+    type Output = PowerSeries<T>;
+    fn add(self, rhs: &PowerSeries<T>) -> PowerSeries<T> {
+        let coeffs = self
+            .coeffs
+            .iter()
+            .zip(&rhs.coeffs)
+            .map(|(&a, &b)| a + b)
+            .collect();
+        PowerSeries::new(coeffs)
+    }
+}
+
+impl<T: Float> Sub<&PowerSeries<T>> for &PowerSeries<T> {
+    type Output = PowerSeries<T>;
+    fn sub(self, rhs: &PowerSeries<T>) -> PowerSeries<T> {
+        self + &(-rhs)
+    }
+}
+
+impl<T: Float> Mul<&PowerSeries<T>> for &PowerSeries<T> {
+    type Output = PowerSeries<T>;
+    fn mul(self, rhs: &PowerSeries<T>) -> PowerSeries<T> {
+        let n = self.precision().min(rhs.precision());
+        let coeffs = (0..n)
+            .map(|k| (0..=k).fold(zero(), |acc, j| acc + self.coeffs[j] * rhs.coeffs[k - j]))
+            .collect();
+        PowerSeries::new(coeffs)
+    }
+}
+
+impl<T: Float> Neg for &PowerSeries<T> {
+    type Output = PowerSeries<T>;
+    fn neg(self) -> PowerSeries<T> {
+        PowerSeries::new(self.coeffs.iter().map(|&c| -c).collect())
+    }
+}
+
+impl<T: Float> Neg for PowerSeries<T> {
+    type Output = PowerSeries<T>;
+    fn neg(self) -> PowerSeries<T> {
+        -&self
+    }
+}
+
+/// Rational approximation p(x)/q(x) from [`PowerSeries::pade`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Pade<T> {
+    pub numerator: Polynomial<T>,
+    pub denominator: Polynomial<T>,
+}
+
+impl<T: Float> Pade<T> {
+    pub fn eval(&self, z: Complex<T>) -> Complex<T> {
+        self.numerator.eval(z) / self.denominator.eval(z)
+    }
+}
+
+/// Laurent series x^v·(Σ c_k·xᵏ + O(xⁿ)) with possibly negative valuation v
+#[derive(Debug, Clone, PartialEq)]
+pub struct LaurentSeries<T> {
+    valuation: i32,
+    series: PowerSeries<T>,
+}
+
+impl<T: Float> LaurentSeries<T> {
+    /// Series whose first coefficient multiplies x^valuation
+    pub fn new(valuation: i32, coeffs: Vec<Complex<T>>) -> Self {
+        LaurentSeries {
+            valuation,
+            series: PowerSeries::new(coeffs),
+        }
+    }
+
+    pub fn from_power_series(series: PowerSeries<T>) -> Self {
+        LaurentSeries {
+            valuation: 0,
+            series,
+        }
+    }
+
+    /// Exponent of the first stored coefficient
+    pub fn valuation(&self) -> i32 {
+        self.valuation
+    }
+
+    /// Exponent of the error term O(x^order)
+    pub fn order(&self) -> i32 {
+        self.valuation + self.series.precision() as i32
+    }
+
+    /// Coefficient of xᵏ, zero below the valuation. Panics at or above the order.
+    pub fn coeff(&self, k: i32) -> Complex<T> {
+        assert!(k < self.order(), "coefficient beyond the known terms");
+        if k < self.valuation {
+            zero()
+        } else {
+            self.series.coeffs[(k - self.valuation) as usize]
+        }
+    }
+
+    /// Coefficient of x⁻¹
+    pub fn residue(&self) -> Complex<T> {
+        self.coeff(-1)
+    }
+
+    /// Sum of the known terms at `z`
+    pub fn eval(&self, z: Complex<T>) -> Complex<T> {
+        self.series.eval(z) * z.powi(self.valuation)
+    }
+
+    /// Same series with leading zero coefficients moved into the valuation
+    fn normalized(&self) -> Self {
+        let leading = self
+            .series
+            .coeffs
+            .iter()
+            .position(|&c| !is_zero(c))
+            .unwrap_or(self.series.precision() - 1);
+        LaurentSeries {
+            valuation: self.valuation + leading as i32,
+            series: PowerSeries::new(self.series.coeffs[leading..].to_vec()),
+        }
+    }
+
+    /// 1/f, panics if all known coefficients are zero
+    pub fn inv(&self) -> Self {
+        let normalized = self.normalized();
+        let series = normalized
+            .series
+            .inv()
+            .expect("inverse of a Laurent series with no nonzero known term");
+        LaurentSeries {
+            valuation: -normalized.valuation,
+            series,
+        }
+    }
+}
+
+impl<T: Float> Add<&LaurentSeries<T>> for &LaurentSeries<T> {
+    type Output = LaurentSeries<T>;
+    fn add(self, rhs: &LaurentSeries<T>) -> LaurentSeries<T> {
+        // Terms from the lower valuation up to the first unknown one
+        let order = self.order().min(rhs.order());
+        let valuation = self
+            .valuation
+            .min(rhs.valuation)
+            .min(order.saturating_sub(1));
+        let at = |s: &LaurentSeries<T>, k: i32| if k < s.order() { s.coeff(k) } else { zero() };
+        let coeffs = (valuation..order)
+            .map(|k| at(self, k) + at(rhs, k))
+            .collect();
+        LaurentSeries::new(valuation, coeffs)
+    }
+}
+
+impl<T: Float> Sub<&LaurentSeries<T>> for &LaurentSeries<T> {
+    type Output = LaurentSeries<T>;
+    fn sub(self, rhs: &LaurentSeries<T>) -> LaurentSeries<T> {
+        self + &(-rhs)
+    }
+}
+
+impl<T: Float> Mul<&LaurentSeries<T>> for &LaurentSeries<T> {
+    type Output = LaurentSeries<T>;
+    fn mul(self, rhs: &LaurentSeries<T>) -> LaurentSeries<T> {
+        let (a, b) = (self.normalized(), rhs.normalized());
+        LaurentSeries {
+            valuation: a.valuation + b.valuation,
+            series: &a.series * &b.series,
+        }
+    }
+}
+
+impl<T: Float> Div<&LaurentSeries<T>> for &LaurentSeries<T> {
+    type Output = LaurentSeries<T>;
+    fn div(self, rhs: &LaurentSeries<T>) -> LaurentSeries<T> {
+        self.mul(&rhs.inv())
+    }
+}
+
+impl<T: Float> Neg for &LaurentSeries<T> {
+    type Output = LaurentSeries<T>;
+    fn neg(self) -> LaurentSeries<T> {
+        LaurentSeries {
+            valuation: self.valuation,
+            series: -&self.series,
+        }
+    }
+}
+
+impl<T: Float> Neg for LaurentSeries<T> {
+    type Output = LaurentSeries<T>;
+    fn neg(self) -> LaurentSeries<T> {
+        -&self
+    }
+}
+
+macro_rules! forward_owned_ops {
+    ($ty:ident: $($imp:ident::$method:ident),*) => {
+        $(
+            impl<T: Float> $imp<$ty<T>> for $ty<T> {
+                type Output = $ty<T>;
+                fn $method(self, rhs: $ty<T>) -> $ty<T> {
+                    (&self).$method(&rhs)
+                }
+            }
+
+            impl<'a, T: Float> $imp<&'a $ty<T>> for $ty<T> {
+                type Output = $ty<T>;
+                fn $method(self, rhs: &'a $ty<T>) -> $ty<T> {
+                    (&self).$method(rhs)
+                }
+            }
+
+            impl<'a, T: Float> $imp<$ty<T>> for &'a $ty<T> {
+                type Output = $ty<T>;
+                fn $method(self, rhs: $ty<T>) -> $ty<T> {
+                    self.$method(&rhs)
+                }
+            }
+        )*
+    };
+}
+
+forward_owned_ops!(PowerSeries: Add::add, Sub::sub, Mul::mul);
+forward_owned_ops!(LaurentSeries: Add::add, Sub::sub, Mul::mul, Div::div);
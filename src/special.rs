@@ -0,0 +1,411 @@
+//! Special functions of complex argument
+//!
+//! Gamma, exponential integral, sine/cosine integrals, incomplete gamma
+//! and hypergeometric functions built on `Complex<T>` arithmetic.
+//! ```rust
+//! use ancomplex::*;
+//! use ancomplex::special::*;
+//!
+//! let e1 = expint_e1(c64(1.0, 1.0));
+//! let g = gamma_upper(c64(0.0, 0.0), c64(1.0, 1.0));
+//! assert!((e1 - g).norm() < 1e-12);
+//! ```
+use crate::cast::from_f64;
+use crate::{complex, Complex};
+use num_traits::{Float, FloatConst};
+
+/// Euler–Mascheroni constant
+const EULER_GAMMA: f64 = 0.577_215_664_901_532_9;
+/// Upper bound for terms of series and continued fractions
+const MAX_TERMS: usize = 10_000;
+/// Runge–Kutta steps per unit path length when continuing ₂F₁
+const RK4_STEPS_PER_UNIT: f64 = 512.0;
+
+const LANCZOS_G: f64 = 7.0;
+const LANCZOS_COEFFS: [f64; 9] = [
+    0.999_999_999_999_809_9,
+    676.520_368_121_885_1,
+    -1_259.139_216_722_402_8,
+    771.323_428_777_653_1,
+    -176.615_029_162_140_6,
+    12.507_343_278_686_905,
+    -0.138_571_095_265_720_12,
+    9.984_369_578_019_572e-6,
+    1.505_632_735_149_311_6e-7,
+];
+
+fn real<T: Float>(x: T) -> Complex<T> {
+    complex(x, T::zero())
+}
+
+fn converged<T: Float>(term: Complex<T>, sum: Complex<T>) -> bool {
+    term.norm() <= T::epsilon() * sum.norm()
+}
+
+/// Checks if complex number is (almost) a real integer
+fn is_integer<T: Float>(z: Complex<T>) -> bool {
+    let tol = from_f64::<T>(1e3) * T::epsilon();
+    z.imag.abs() <= tol * (T::one() + z.real.abs())
+        && (z.real - z.real.round()).abs() <= tol * (T::one() + z.real.abs())
+}
+
+/// Gamma function Γ(z) (Lanczos approximation with reflection)
+pub fn gamma<T: Float + FloatConst>(z: Complex<T>) -> Complex<T> {
+    let half = from_f64::<T>(0.5);
+    if z.real < half {
+        // Γ(z)Γ(1-z) = π / sin(πz)
+        let pi = T::PI();
+        let s = (z * pi).sin();
+        return real(pi) / (s * gamma(real(T::one()) - z));
+    }
+    let z = z - T::one();
+    let mut acc = real(from_f64::<T>(LANCZOS_COEFFS[0]));
+    for (k, &p) in LANCZOS_COEFFS.iter().enumerate().skip(1) {
+        acc = acc + real(from_f64::<T>(p)) / (z + from_f64::<T>(k as f64));
+    }
+    let t = z + from_f64::<T>(LANCZOS_G) + half;
+    let sqrt_two_pi = (T::PI() + T::PI()).sqrt();
+    ((z + half) * t.ln() - t).exp() * acc * sqrt_two_pi
+}
+
+/// Exponential integral E₁(z) = ∫ᶻ^∞ e⁻ᵗ/t dt, with branch cut along the negative real axis
+pub fn expint_e1<T: Float + FloatConst>(z: Complex<T>) -> Complex<T> {
+    let two = from_f64::<T>(2.0);
+    if z.norm() <= two || z.real < -z.imag.abs() {
+        e1_series(z)
+    } else {
+        e1_continued_fraction(z)
+    }
+}
+
+fn e1_series<T: Float>(z: Complex<T>) -> Complex<T> {
+    // E₁(z) = -γ - ln z - Σ (-z)ᵏ / (k·k!)
+    let mut term = real(T::one());
+    let mut sum = real(T::zero());
+    for k in 1..MAX_TERMS {
+        let k = from_f64::<T>(k as f64);
+        term = -term * z / k;
+        let add = term / k;
+        sum = sum + add;
+        if converged(add, sum) {
+            break;
+        }
+    }
+    -(z.ln() + from_f64::<T>(EULER_GAMMA)) - sum
+}
+
+fn e1_continued_fraction<T: Float>(z: Complex<T>) -> Complex<T> {
+    // e⁻ᶻ / (z+1- 1/(z+3- 4/(z+5- ...))) by the modified Lentz method
+    let two = from_f64::<T>(2.0);
+    let mut b = z + T::one();
+    let (mut f, mut c, mut d) = (b, b, real(T::zero()));
+    for i in 1..MAX_TERMS {
+        let i = from_f64::<T>(i as f64);
+        let an = -(i * i);
+        b = b + two;
+        d = lentz_guard(d * an + b).inv();
+        c = lentz_guard(b + real(an) / c);
+        let delta = c * d;
+        f *= delta;
+        if (delta - T::one()).norm() <= T::epsilon() {
+            break;
+        }
+    }
+    (-z).exp() / f
+}
+
+/// Replaces a vanishing denominator in the Lentz recurrence
+fn lentz_guard<T: Float>(x: Complex<T>) -> Complex<T> {
+    let tiny = T::min_positive_value().sqrt();
+    if x.norm() < tiny {
+        real(tiny)
+    } else {
+        x
+    }
+}
+
+/// Exponential integral Ei(z), real on the whole real axis (principal value)
+pub fn ei<T: Float + FloatConst>(z: Complex<T>) -> Complex<T> {
+    let two = from_f64::<T>(2.0);
+    // ½(ln z - ln(1/z)) equals ln z off the negative real axis and ln|z| on it
+    let half_log = (z.ln() - z.inv().ln()) / two;
+    if z.norm() <= two {
+        let mut term = real(T::one());
+        let mut sum = real(T::zero());
+        for k in 1..MAX_TERMS {
+            let k = from_f64::<T>(k as f64);
+            term = term * z / k;
+            let add = term / k;
+            sum = sum + add;
+            if converged(add, sum) {
+                break;
+            }
+        }
+        return half_log + sum + from_f64::<T>(EULER_GAMMA);
+    }
+    -expint_e1(-z) + half_log - (-z).ln()
+}
+
+/// Sine integral Si(z) = ∫₀ᶻ sin(t)/t dt
+pub fn si<T: Float + FloatConst>(z: Complex<T>) -> Complex<T> {
+    if z.real < T::zero() {
+        return -si(-z);
+    }
+    if z.norm() <= from_f64::<T>(4.0) {
+        // Σ (-1)ᵏ z²ᵏ⁺¹ / ((2k+1)(2k+1)!)
+        let z2 = z * z;
+        let mut term = z;
+        let mut sum = z;
+        for k in 1..MAX_TERMS {
+            let n = from_f64::<T>((2 * k) as f64);
+            term = -term * z2 / (n * (n + T::one()));
+            let add = term / (n + T::one());
+            sum = sum + add;
+            if converged(add, sum) {
+                break;
+            }
+        }
+        return sum;
+    }
+    let i = Complex::i();
+    let two_i = i * from_f64::<T>(2.0);
+    (expint_e1(i * z) - expint_e1(-i * z)) / two_i + T::FRAC_PI_2()
+}
+
+/// Cosine integral Ci(z) = γ + ln z + ∫₀ᶻ (cos(t) - 1)/t dt
+pub fn ci<T: Float + FloatConst>(z: Complex<T>) -> Complex<T> {
+    if z.norm() <= from_f64::<T>(4.0) {
+        // γ + ln z + Σ (-1)ᵏ z²ᵏ / (2k·(2k)!)
+        let z2 = z * z;
+        let mut term = real(T::one());
+        let mut sum = real(T::zero());
+        for k in 1..MAX_TERMS {
+            let n = from_f64::<T>((2 * k) as f64);
+            term = -term * z2 / (n * (n - T::one()));
+            let add = term / n;
+            sum = sum + add;
+            if converged(add, sum) {
+                break;
+            }
+        }
+        return z.ln() + sum + from_f64::<T>(EULER_GAMMA);
+    }
+    if z.real < T::zero() {
+        // Ci(z) = Ci(-z) ± iπ depending on half-plane of z
+        let jump = if z.imag.is_sign_negative() {
+            -T::PI()
+        } else {
+            T::PI()
+        };
+        return ci(-z) + complex(T::zero(), jump);
+    }
+    let i = Complex::i();
+    -(expint_e1(i * z) + expint_e1(-i * z)) / from_f64::<T>(2.0)
+}
+
+/// Lower incomplete gamma function γ(a, z) = ∫₀ᶻ tᵃ⁻¹ e⁻ᵗ dt
+pub fn gamma_lower<T: Float + FloatConst>(a: Complex<T>, z: Complex<T>) -> Complex<T> {
+    if z == real(T::zero()) {
+        return z;
+    }
+    if use_gamma_series(a, z) {
+        gamma_series(a, z)
+    } else {
+        gamma(a) - gamma_continued_fraction(a, z)
+    }
+}
+
+/// Upper incomplete gamma function Γ(a, z) = ∫ᶻ^∞ tᵃ⁻¹ e⁻ᵗ dt
+pub fn gamma_upper<T: Float + FloatConst>(a: Complex<T>, z: Complex<T>) -> Complex<T> {
+    if z == real(T::zero()) {
+        return gamma(a);
+    }
+    if use_gamma_series(a, z) {
+        gamma(a) - gamma_series(a, z)
+    } else {
+        gamma_continued_fraction(a, z)
+    }
+}
+
+fn use_gamma_series<T: Float>(a: Complex<T>, z: Complex<T>) -> bool {
+    z.real < T::zero() || z.norm() < a.norm() + T::one()
+}
+
+fn gamma_series<T: Float>(a: Complex<T>, z: Complex<T>) -> Complex<T> {
+    // zᵃ e⁻ᶻ Σ zᵏ / (a(a+1)...(a+k))
+    let mut term = a.inv();
+    let mut sum = term;
+    for k in 1..MAX_TERMS {
+        term = term * z / (a + from_f64::<T>(k as f64));
+        sum = sum + term;
+        if converged(term, sum) {
+            break;
+        }
+    }
+    sum * (a * z.ln() - z).exp()
+}
+
+fn gamma_continued_fraction<T: Float>(a: Complex<T>, z: Complex<T>) -> Complex<T> {
+    // zᵃ e⁻ᶻ / (z+1-a- 1(1-a)/(z+3-a- 2(2-a)/(...))) by the modified Lentz method
+    let two = from_f64::<T>(2.0);
+    let mut b = lentz_guard(z + T::one() - a);
+    let (mut f, mut c, mut d) = (b, b, real(T::zero()));
+    for i in 1..MAX_TERMS {
+        let i = from_f64::<T>(i as f64);
+        let an = -(real(i) - a) * i;
+        b = b + two;
+        d = lentz_guard(an * d + b).inv();
+        c = lentz_guard(b + an / c);
+        let delta = c * d;
+        f *= delta;
+        if (delta - T::one()).norm() <= T::epsilon() {
+            break;
+        }
+    }
+    (a * z.ln() - z).exp() / f
+}
+
+/// Confluent hypergeometric function ₁F₁(a; b; z) (Kummer's M)
+pub fn hyp1f1<T: Float + FloatConst>(a: Complex<T>, b: Complex<T>, z: Complex<T>) -> Complex<T> {
+    if z.real < T::zero() {
+        // Kummer's transformation avoids cancellation in the alternating series
+        return z.exp() * hyp1f1_series(b - a, b, -z);
+    }
+    hyp1f1_series(a, b, z)
+}
+
+fn hyp1f1_series<T: Float>(a: Complex<T>, b: Complex<T>, z: Complex<T>) -> Complex<T> {
+    let mut term = real(T::one());
+    let mut sum = term;
+    for k in 0..MAX_TERMS {
+        let k = from_f64::<T>(k as f64);
+        term = term * (a + k) / (b + k) * z / (k + T::one());
+        sum = sum + term;
+        if converged(term, sum) {
+            break;
+        }
+    }
+    sum
+}
+
+/// Gauss hypergeometric function ₂F₁(a, b; c; z)
+///
+/// Uses the Pfaff, 1-z and 1/z transformations to map `z` into a region
+/// where the power series converges quickly. Near |z| = |1-z| = 1, and in
+/// degenerate cases (integer c-a-b or a-b), the series is continued by
+/// integrating the hypergeometric equation; only paths passing close to
+/// z = 1 fall back to the slowly converging series.
+pub fn hyp2f1<T: Float + FloatConst>(
+    a: Complex<T>,
+    b: Complex<T>,
+    c: Complex<T>,
+    z: Complex<T>,
+) -> Complex<T> {
+    let one = real(T::one());
+    let fast = from_f64::<T>(0.8);
+    if z == one {
+        // Gauss's summation theorem
+        return gamma(c) * gamma(c - a - b) / (gamma(c - a) * gamma(c - b));
+    }
+    let w = z / (z - one);
+    if z.norm() < fast && z.norm() <= w.norm() {
+        return hyp2f1_series(a, b, c, z);
+    }
+    if w.norm() < fast {
+        // Pfaff: (1-z)⁻ᵃ ₂F₁(a, c-b; c; z/(z-1))
+        return (-a * (one - z).ln()).exp() * hyp2f1_series(a, c - b, c, w);
+    }
+    let s = c - a - b;
+    if (one - z).norm() < fast && !is_integer(s) {
+        let first = gamma(c) * gamma(s) / (gamma(c - a) * gamma(c - b))
+            * hyp2f1_series(a, b, one - s, one - z);
+        let second = (s * (one - z).ln()).exp() * gamma(c) * gamma(-s) / (gamma(a) * gamma(b))
+            * hyp2f1_series(c - a, c - b, s + one, one - z);
+        return first + second;
+    }
+    if z.norm() > T::one() && !is_integer(a - b) {
+        let zi = z.inv();
+        let ln_mz = (-z).ln();
+        let first = gamma(c) * gamma(b - a) / (gamma(b) * gamma(c - a))
+            * (-a * ln_mz).exp()
+            * hyp2f1_series(a, a - c + one, a - b + one, zi);
+        let second = gamma(c) * gamma(a - b) / (gamma(a) * gamma(c - b))
+            * (-b * ln_mz).exp()
+            * hyp2f1_series(b, b - c + one, b - a + one, zi);
+        return first + second;
+    }
+    if w.norm() < z.norm() {
+        (-a * (one - z).ln()).exp() * hyp2f1_continued(a, c - b, c, w)
+    } else {
+        hyp2f1_continued(a, b, c, z)
+    }
+}
+
+/// Sums the series for |z| ≈ 1, where none of the transformations help
+/// (e.g. near e^{±iπ/3}), by integrating the hypergeometric equation from
+/// a point on the same ray with |z| = 1/2
+///
+/// Falls back to the direct series when the path passes close to the
+/// singular point z = 1.
+fn hyp2f1_continued<T: Float>(
+    a: Complex<T>,
+    b: Complex<T>,
+    c: Complex<T>,
+    z: Complex<T>,
+) -> Complex<T> {
+    let half = from_f64::<T>(0.5);
+    let start = z.scale(half / z.norm());
+    let path = z - start;
+    // distance from 1 to the segment start + t·path, t ∈ [0, 1]
+    let t = ((real(T::one()) - start) * path.conj()).real / path.square_norm();
+    let nearest = start + path.scale(t.max(T::zero()).min(T::one()));
+    if (nearest - T::one()).norm() < from_f64(0.25) {
+        return hyp2f1_series(a, b, c, z);
+    }
+    let one = real(T::one());
+    // z(1-z) F'' + (c - (a+b+1) z) F' - ab F = 0 along the path
+    let rhs = |z: Complex<T>, f: Complex<T>, df: Complex<T>| {
+        let d2f = (a * b * f - (c - (a + b + one) * z) * df) / (z * (one - z));
+        (df * path, d2f * path)
+    };
+    let steps = (path.norm() * from_f64(RK4_STEPS_PER_UNIT))
+        .ceil()
+        .to_usize()
+        .unwrap_or(1)
+        .max(1);
+    let h = from_f64::<T>(steps as f64).recip();
+    let mut f = hyp2f1_series(a, b, c, start);
+    let mut df = a * b / c * hyp2f1_series(a + one, b + one, c + one, start);
+    for k in 0..steps {
+        let z0 = start + path.scale(from_f64::<T>(k as f64) * h);
+        let zm = z0 + path.scale(h * half);
+        let z1 = z0 + path.scale(h);
+        let (f1, g1) = rhs(z0, f, df);
+        let (f2, g2) = rhs(zm, f + f1.scale(h * half), df + g1.scale(h * half));
+        let (f3, g3) = rhs(zm, f + f2.scale(h * half), df + g2.scale(h * half));
+        let (f4, g4) = rhs(z1, f + f3.scale(h), df + g3.scale(h));
+        let sixth = h / from_f64(6.0);
+        f = f + (f1 + f2.scale(from_f64(2.0)) + f3.scale(from_f64(2.0)) + f4).scale(sixth);
+        df = df + (g1 + g2.scale(from_f64(2.0)) + g3.scale(from_f64(2.0)) + g4).scale(sixth);
+    }
+    f
+}
+
+fn hyp2f1_series<T: Float>(
+    a: Complex<T>,
+    b: Complex<T>,
+    c: Complex<T>,
+    z: Complex<T>,
+) -> Complex<T> {
+    let mut term = real(T::one());
+    let mut sum = term;
+    for k in 0..MAX_TERMS {
+        let k = from_f64::<T>(k as f64);
+        term = term * (a + k) * (b + k) / ((c + k) * (k + T::one())) * z;
+        sum = sum + term;
+        if converged(term, sum) {
+            break;
+        }
+    }
+    sum
+}
@@ -0,0 +1,149 @@
+//! Spectral evaluation on arbitrary contours: Chirp-Z transform, Goertzel and zoom FFT
+//!
+//! ```rust
+//! use ancomplex::*;
+//! use ancomplex::spectral::goertzel;
+//!
+//! // 8 samples of a tone in bin 2
+//! let samples: Vec<f64> = (0..8)
+//!     .map(|n| (std::f64::consts::PI * n as f64 / 2.0).cos())
+//!     .collect();
+//! let bin = goertzel(&samples, std::f64::consts::PI / 2.0);
+//! assert!((bin.norm() - 4.0).abs() < 1e-12);
+//! ```
+use crate::cast::from_f64;
+use crate::fft::FftPlan;
+use crate::{complex, Complex};
+use num_traits::{Float, FloatConst};
+
+/// Chirp-Z transform X_k = Σ x_n·a⁻ⁿ·wⁿᵏ for k = 0..m
+///
+/// Evaluates z-transform on the spiral z_k = a·w⁻ᵏ using Bluestein's
+/// algorithm, `w = e^(-2πi/n)` with `a = 1` gives ordinary DFT.
+pub fn czt<T: Float + FloatConst>(
+    input: &[Complex<T>],
+    m: usize,
+    w: Complex<T>,
+    a: Complex<T>,
+) -> Vec<Complex<T>> {
+    let n = input.len();
+    if n == 0 || m == 0 {
+        return vec![complex(T::zero(), T::zero()); m];
+    }
+    let two = from_f64::<T>(2.0);
+    // nk = (n² + k² - (k-n)²)/2
+    let chirp = |t: usize| w.powf(from_f64::<T>((t * t) as f64) / two);
+    let plan = FftPlan::new((n + m - 1).next_power_of_two());
+    let len = plan.len();
+    let mut signal = vec![complex(T::zero(), T::zero()); len];
+    let a_inv = a.inv();
+    let mut a_power = complex(T::one(), T::zero());
+    for (k, x) in input.iter().enumerate() {
+        signal[k] = *x * a_power * chirp(k);
+        a_power *= a_inv;
+    }
+    let mut kernel = vec![complex(T::zero(), T::zero()); len];
+    for (k, h) in kernel.iter_mut().enumerate().take(m) {
+        *h = chirp(k).inv();
+    }
+    for k in 1..n {
+        kernel[len - k] = chirp(k).inv();
+    }
+    plan.forward(&mut signal);
+    plan.forward(&mut kernel);
+    for (x, h) in signal.iter_mut().zip(&kernel) {
+        *x *= *h;
+    }
+    plan.inverse(&mut signal);
+    (0..m).map(|k| signal[k] * chirp(k)).collect()
+}
+
+/// Spectrum on `m` equally spaced frequencies in `[f_start, f_end)`
+///
+/// Frequencies are in the same units as the sample rate `fs`.
+pub fn zoom_fft<T: Float + FloatConst>(
+    input: &[Complex<T>],
+    m: usize,
+    f_start: T,
+    f_end: T,
+    fs: T,
+) -> Vec<Complex<T>> {
+    let two_pi = T::PI() + T::PI();
+    let step = (f_end - f_start) / from_f64::<T>(m as f64);
+    let a = Complex::from_polar(T::one(), two_pi * f_start / fs);
+    let w = Complex::from_polar(T::one(), -two_pi * step / fs);
+    czt(input, m, w, a)
+}
+
+/// Streaming Goertzel evaluation of a single DFT bin
+///
+/// After pushing N samples, [`Goertzel::value`] equals Σ x_n e^(-iωn).
+#[derive(Debug, Clone, Copy)]
+pub struct Goertzel<T> {
+    omega: T,
+    coeff: T,
+    s1: Complex<T>,
+    s2: Complex<T>,
+    count: usize,
+}
+
+impl<T: Float> Goertzel<T> {
+    /// Creates evaluator for angular frequency `omega` in radians per sample
+    pub fn new(omega: T) -> Self {
+        Goertzel {
+            omega,
+            coeff: omega.cos() * from_f64::<T>(2.0),
+            s1: complex(T::zero(), T::zero()),
+            s2: complex(T::zero(), T::zero()),
+            count: 0,
+        }
+    }
+
+    /// Feeds next sample
+    pub fn push(&mut self, x: Complex<T>) {
+        let s = x + self.s1 * self.coeff - self.s2;
+        self.s2 = self.s1;
+        self.s1 = s;
+        self.count += 1;
+    }
+
+    /// Number of samples pushed since creation or last reset
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Current value of the bin
+    pub fn value(&self) -> Complex<T> {
+        if self.count == 0 {
+            return complex(T::zero(), T::zero());
+        }
+        let y = self.s1 - self.s2 * Complex::from_polar(T::one(), -self.omega);
+        let last = from_f64::<T>((self.count - 1) as f64);
+        y * Complex::from_polar(T::one(), -self.omega * last)
+    }
+
+    /// Clears state to start new block
+    pub fn reset(&mut self) {
+        self.s1 = complex(T::zero(), T::zero());
+        self.s2 = complex(T::zero(), T::zero());
+        self.count = 0;
+    }
+}
+
+/// Single bin Σ x_n e^(-iωn) of real samples, `omega` in radians per sample
+pub fn goertzel<T: Float>(samples: &[T], omega: T) -> Complex<T> {
+    let mut state = Goertzel::new(omega);
+    for &x in samples {
+        state.push(complex(x, T::zero()));
+    }
+    state.value()
+}
+
+/// Single bin Σ x_n e^(-iωn) of complex samples, `omega` in radians per sample
+pub fn goertzel_complex<T: Float>(samples: &[Complex<T>], omega: T) -> Complex<T> {
+    let mut state = Goertzel::new(omega);
+    for &x in samples {
+        state.push(x);
+    }
+    state.value()
+}
@@ -0,0 +1,165 @@
+//! Short-time Fourier transform and its inverse
+//!
+//! Frames are stored frame-major: bin `k` of frame `f` is at `f * window_len + k`.
+//! The signal is padded with `window_len / 2` zeros on both sides, so every
+//! sample sits in the middle of some frame.
+//! ```rust
+//! use ancomplex::*;
+//! use ancomplex::stft::{is_cola, Stft};
+//! use ancomplex::window::Window;
+//!
+//! let window = Window::Hann.periodic(16);
+//! assert!(is_cola(&window, 8, 1e-12));
+//! let stft = Stft::new(window, 8);
+//! let signal: Vec<c64> = (0..40).map(|n| c64(n as f64, 0.0)).collect();
+//! let frames = stft.forward(&signal);
+//! let restored = stft.inverse(&frames, signal.len());
+//! assert!((restored[17] - signal[17]).norm() < 1e-9);
+//! ```
+use crate::fft::FftPlan;
+use crate::{complex, Complex};
+use num_traits::{Float, FloatConst};
+
+/// Short-time Fourier transform with fixed window and hop size
+#[derive(Debug, Clone)]
+pub struct Stft<T> {
+    window: Vec<T>,
+    hop: usize,
+    plan: FftPlan<T>,
+}
+
+impl<T: Float + FloatConst> Stft<T> {
+    /// Creates transform with analysis `window` moved by `hop` samples between frames
+    pub fn new(window: Vec<T>, hop: usize) -> Self {
+        assert!(!window.is_empty(), "window must not be empty");
+        assert!(hop > 0, "hop size must be positive");
+        Stft {
+            plan: FftPlan::new(window.len()),
+            window,
+            hop,
+        }
+    }
+
+    /// Samples per frame, also the number of bins in each frame
+    pub fn window_len(&self) -> usize {
+        self.window.len()
+    }
+
+    pub fn hop(&self) -> usize {
+        self.hop
+    }
+
+    /// Zeros padded in front of the signal
+    fn padding(&self) -> usize {
+        self.window.len() / 2
+    }
+
+    /// Number of frames produced for signal of `signal_len` samples
+    pub fn num_frames(&self, signal_len: usize) -> usize {
+        let padded = signal_len + 2 * self.padding();
+        if padded <= self.window.len() {
+            1
+        } else {
+            1 + (padded - self.window.len() + self.hop - 1) / self.hop
+        }
+    }
+
+    /// Frame-major matrix of spectra of windowed frames
+    pub fn forward(&self, signal: &[Complex<T>]) -> Vec<Complex<T>> {
+        let n = self.window.len();
+        let frames = self.num_frames(signal.len());
+        let mut output = Vec::with_capacity(frames * n);
+        for f in 0..frames {
+            let start = f * self.hop;
+            let mut frame: Vec<Complex<T>> = (0..n)
+                .map(|k| {
+                    // Position inside the original, unpadded signal
+                    let index = (start + k).checked_sub(self.padding());
+                    match index.and_then(|i| signal.get(i)) {
+                        Some(x) => *x * self.window[k],
+                        None => complex(T::zero(), T::zero()),
+                    }
+                })
+                .collect();
+            self.plan.forward(&mut frame);
+            output.extend(frame);
+        }
+        output
+    }
+
+    /// [`Stft::forward`] of real signal
+    pub fn forward_real(&self, signal: &[T]) -> Vec<Complex<T>> {
+        let signal: Vec<Complex<T>> = signal.iter().map(|&x| complex(x, T::zero())).collect();
+        self.forward(&signal)
+    }
+
+    /// Inverse transform by weighted overlap-add, returns `signal_len` samples
+    ///
+    /// Frames are windowed again and normalized by Σw², which reconstructs the
+    /// signal exactly whenever [`is_nola`] holds for the window and hop.
+    /// Without frames there is nothing to reconstruct and the result is empty.
+    pub fn inverse(&self, frames: &[Complex<T>], signal_len: usize) -> Vec<Complex<T>> {
+        let n = self.window.len();
+        assert_eq!(
+            frames.len() % n,
+            0,
+            "frames length is not a multiple of window length"
+        );
+        let count = frames.len() / n;
+        if count == 0 {
+            return Vec::new();
+        }
+        let total = (count - 1) * self.hop + n;
+        let mut output = vec![complex(T::zero(), T::zero()); total];
+        let mut weight = vec![T::zero(); total];
+        for (f, spectrum) in frames.chunks(n).enumerate() {
+            let mut frame = spectrum.to_vec();
+            self.plan.inverse(&mut frame);
+            let start = f * self.hop;
+            for (k, x) in frame.iter().enumerate() {
+                output[start + k] = output[start + k] + *x * self.window[k];
+                weight[start + k] = weight[start + k] + self.window[k] * self.window[k];
+            }
+        }
+        let pad = self.padding();
+        (pad..pad + signal_len)
+            .map(|i| match (output.get(i), weight.get(i)) {
+                (Some(x), Some(&w)) if w > T::epsilon() => *x / w,
+                _ => complex(T::zero(), T::zero()),
+            })
+            .collect()
+    }
+}
+
+/// Power spectrogram |X|² of frame-major STFT matrix
+pub fn spectrogram<T: Float>(frames: &[Complex<T>]) -> Vec<T> {
+    frames.iter().map(|x| x.square_norm()).collect()
+}
+
+/// Sum of shifted copies of `values`, one period of length `hop`
+fn overlap_sum<T: Float>(values: &[T], hop: usize) -> Vec<T> {
+    assert!(hop > 0, "hop size must be positive");
+    let mut sums = vec![T::zero(); hop];
+    for (k, &v) in values.iter().enumerate() {
+        sums[k % hop] = sums[k % hop] + v;
+    }
+    sums
+}
+
+/// Constant overlap-add check: Σ w[n - f·hop] is constant for all n
+///
+/// COLA windows reconstruct the signal by plain overlap-add of frames.
+pub fn is_cola<T: Float>(window: &[T], hop: usize, tol: T) -> bool {
+    let sums = overlap_sum(window, hop);
+    let reference = sums[0];
+    sums.iter()
+        .all(|&s| (s - reference).abs() <= tol * reference.abs())
+}
+
+/// Nonzero overlap-add check: Σ w²[n - f·hop] is positive for all n
+///
+/// NOLA is necessary and sufficient for [`Stft::inverse`] to reconstruct the signal.
+pub fn is_nola<T: Float>(window: &[T], hop: usize, tol: T) -> bool {
+    let squares: Vec<T> = window.iter().map(|&w| w * w).collect();
+    overlap_sum(&squares, hop).iter().all(|&s| s > tol)
+}
@@ -2,6 +2,15 @@
 
 use crate::*;
 use std::f64::consts::FRAC_PI_2;
+
+fn assert_close(left: c64, right: c64, tol: f64) {
+    assert!(
+        (left - right).norm() <= tol * (1.0 + right.norm()),
+        "{:?} != {:?}",
+        left,
+        right
+    );
+}
 #[test]
 fn complex_add_complex() {
     let c = complex(1.0, 4.0);
@@ -165,7 +174,7 @@ fn asinh_of_complex() {
 #[test]
 fn acosh_of_complex() {
     let c = complex(0.0, 0.0);
-    let correct_result = complex(0.0, 0.0);
+    let correct_result = complex(0.0, FRAC_PI_2);
     assert_eq!(c.acosh(), correct_result);
 }
 
@@ -349,3 +358,2732 @@ fn neg_of_complex() {
     let correct_result = complex(-2.0, -2.0);
     assert_eq!(-c, correct_result);
 }
+
+#[test]
+fn gamma_of_complex() {
+    use crate::special::gamma;
+    assert_close(
+        gamma(c64(0.5, 1.0)),
+        c64(0.300694617260656, -0.424967879433124),
+        1e-13,
+    );
+    assert_close(
+        gamma(c64(-2.5, 0.3)),
+        c64(-0.613822997437741, -0.211232614937042),
+        1e-13,
+    );
+    assert_close(gamma(c64(5.0, 0.0)), c64(24.0, 0.0), 1e-13);
+}
+
+#[test]
+fn expint_e1_of_complex() {
+    use crate::special::expint_e1;
+    assert_close(
+        expint_e1(c64(1.0, 1.0)),
+        c64(0.000281624451981418, -0.179324535039359),
+        1e-13,
+    );
+    assert_close(
+        expint_e1(c64(-3.0, 0.5)),
+        c64(-9.38360350933094, 0.12921297008463),
+        1e-13,
+    );
+    assert_close(
+        expint_e1(c64(5.0, -7.0)),
+        c64(-1.3465108752292e-5, 7.30361727264652e-4),
+        1e-12,
+    );
+    assert_close(
+        expint_e1(c64(-2.5, 0.0)),
+        c64(-7.07376589457871, -std::f64::consts::PI),
+        1e-13,
+    );
+    assert_close(
+        expint_e1(c64(-10.0, 12.0)),
+        c64(-106.612320114721, -1461.73282555785),
+        1e-12,
+    );
+}
+
+#[test]
+fn ei_of_complex() {
+    use crate::special::ei;
+    assert_close(
+        ei(c64(1.0, 1.0)),
+        c64(1.76462598556386, 2.38776985151052),
+        1e-13,
+    );
+    assert_close(ei(c64(-3.0, 0.0)), c64(-0.0130483810941970, 0.0), 1e-13);
+    assert_close(
+        ei(c64(10.0, 2.0)),
+        c64(-485.050541107552, 2374.81898899924),
+        1e-12,
+    );
+    assert_close(ei(c64(5.0, 0.0)), c64(40.1852753558032, 0.0), 1e-13);
+}
+
+#[test]
+fn si_ci_of_complex() {
+    use crate::special::{ci, si};
+    assert_close(
+        si(c64(2.0, 1.0)),
+        c64(1.83320992150484, 0.457691711286688),
+        1e-13,
+    );
+    assert_close(
+        ci(c64(2.0, 1.0)),
+        c64(0.584475996878248, -0.297495177638134),
+        1e-13,
+    );
+    assert_close(
+        si(c64(8.0, 3.0)),
+        c64(2.02375532436622, 1.10948789545264),
+        1e-13,
+    );
+    assert_close(
+        ci(c64(8.0, 3.0)),
+        c64(1.11476421762754, -0.454637138949408),
+        1e-13,
+    );
+    assert_close(
+        ci(c64(-8.0, 3.0)),
+        c64(1.11476421762754, 3.5962297925392),
+        1e-13,
+    );
+    assert_close(si(c64(0.0, 6.0)), c64(0.0, 42.9950611124457), 1e-13);
+    assert_close(ci(c64(0.0, 6.0)), c64(42.9947010299935, FRAC_PI_2), 1e-13);
+}
+
+#[test]
+fn incomplete_gamma_of_complex() {
+    use crate::special::{gamma_lower, gamma_upper};
+    let (a, z) = (c64(2.0, 1.0), c64(1.0, 0.5));
+    assert_close(
+        gamma_lower(a, z),
+        c64(0.187250138593502, 0.0270722270537943),
+        1e-13,
+    );
+    assert_close(
+        gamma_upper(a, z),
+        c64(0.465715357826665, 0.315993612762751),
+        1e-13,
+    );
+    assert_close(
+        gamma_upper(c64(0.5, -1.0), c64(6.0, 3.0)),
+        c64(6.6236803820144e-4, 1.18688182727570e-3),
+        1e-12,
+    );
+    assert_close(
+        gamma_lower(c64(3.0, 0.0), c64(-2.0, 1.0)),
+        c64(10.443028576294665, 14.202324409250511),
+        1e-13,
+    );
+}
+
+#[test]
+fn hyp1f1_of_complex() {
+    use crate::special::hyp1f1;
+    let value = hyp1f1(c64(1.0, 1.0), c64(2.0, -1.0), c64(3.0, 2.0));
+    assert_close(value, c64(-0.854598991207102, -1.16676288036753), 1e-13);
+    let value = hyp1f1(c64(0.5, 0.0), c64(1.5, 0.0), c64(-20.0, 0.0));
+    assert_close(value, c64(0.198166364829974, 0.0), 1e-13);
+}
+
+#[test]
+fn hyp2f1_of_complex() {
+    use crate::special::hyp2f1;
+    let value = hyp2f1(c64(1.0, 0.0), c64(2.0, 0.0), c64(3.5, 1.0), c64(0.3, 0.2));
+    assert_close(value, c64(1.22988924727114, 0.1045137471977), 1e-13);
+    let value = hyp2f1(c64(0.5, 0.0), c64(1.2, 0.0), c64(2.7, 0.0), c64(-5.0, 0.0));
+    assert_close(value, c64(0.598103504299806, 0.0), 1e-13);
+    let value = hyp2f1(c64(0.3, 1.0), c64(0.7, 0.0), c64(1.9, 0.0), c64(0.9, 0.3));
+    assert_close(value, c64(0.757579085530002, 0.338451100145337), 1e-12);
+    let value = hyp2f1(c64(0.25, 0.0), c64(0.6, 0.0), c64(1.3, 0.0), c64(3.0, 1.0));
+    assert_close(value, c64(0.933569398101179, 0.370007129296607), 1e-12);
+    let value = hyp2f1(c64(1.0, 0.0), c64(1.0, 0.0), c64(2.0, 0.0), c64(0.95, 0.0));
+    assert_close(value, c64(3.15340239321473, 0.0), 1e-10);
+}
+
+#[test]
+fn hyp2f1_near_unit_circle() {
+    use crate::special::hyp2f1;
+    // |z| = |1 - z| = 1, out of reach of every transformation
+    let z = c64::from_polar(1.0, std::f64::consts::FRAC_PI_3);
+    let one = c64(1.0, 0.0);
+    // ₂F₁(1, 1; 2; z) = -ln(1 - z) / z
+    let value = hyp2f1(one, one, c64(2.0, 0.0), z);
+    assert_close(value, -(one - z).ln() / z, 1e-12);
+    // ₂F₁(a, b; b; z) = (1 - z)⁻ᵃ
+    let value = hyp2f1(c64(0.3, 0.0), c64(0.7, 0.0), c64(0.7, 0.0), z);
+    assert_close(value, (one - z).powc(c64(-0.3, 0.0)), 1e-12);
+    let value = hyp2f1(
+        c64(0.5, 0.5),
+        c64(1.5, 0.0),
+        c64(1.5, 0.0),
+        z.conj().scale(0.9),
+    );
+    assert_close(
+        value,
+        (one - z.conj().scale(0.9)).powc(c64(-0.5, -0.5)),
+        1e-12,
+    );
+}
+
+#[test]
+fn carlson_integrals_of_complex() {
+    use crate::elliptic::{rc, rd, rf, rj};
+    let value = rf(c64(1.0, 1.0), c64(2.0, -1.0), c64(3.0, 0.0));
+    assert_close(value, c64(0.698165952679190, -0.0214893276565343), 1e-13);
+    let value = rd(c64(1.0, 1.0), c64(2.0, 0.0), c64(3.0, 0.5));
+    assert_close(value, c64(0.250792851354845, -0.0939321626684035), 1e-13);
+    let value = rj(c64(2.0, 0.0), c64(1.0, 1.0), c64(3.0, 0.0), c64(0.5, 0.2));
+    assert_close(value, c64(0.638659223631686, -0.289602528357503), 1e-13);
+    let value = rc(c64(1.0, 1.0), c64(2.0, 1.0));
+    assert_close(value, c64(0.692239136343541, -0.198284115056164), 1e-13);
+}
+
+#[test]
+fn legendre_elliptic_integrals() {
+    use crate::elliptic::{ellipe, ellipe_inc, ellipf, ellipk};
+    assert_close(ellipk(c64(0.5, 0.0)), c64(1.85407467730137, 0.0), 1e-13);
+    assert_close(
+        ellipk(c64(0.3, 0.4)),
+        c64(1.65024192564194, 0.209510704123987),
+        1e-13,
+    );
+    assert_close(
+        ellipe(c64(0.3, 0.4)),
+        c64(1.46251281071724, -0.17516060541693),
+        1e-13,
+    );
+    assert_close(
+        ellipk(c64(2.5, 0.0)),
+        c64(1.12420195979667, -1.23301490843927),
+        1e-13,
+    );
+    assert_close(ellipe(c64(-3.0, 0.0)), c64(2.42211205513692, 0.0), 1e-13);
+    let (phi, m) = (c64(0.7, 0.3), c64(0.4, 0.1));
+    assert_close(
+        ellipf(phi, m),
+        c64(0.703255105776779, 0.329392482455238),
+        1e-13,
+    );
+    assert_close(
+        ellipe_inc(phi, m),
+        c64(0.695237968524192, 0.271863927715787),
+        1e-13,
+    );
+    assert_close(
+        ellipf(c64(4.0, 0.2), c64(0.6, 0.0)),
+        c64(4.8121770417738, 0.246052339949894),
+        1e-13,
+    );
+    assert_close(
+        ellipe_inc(c64(-5.0, 0.2), c64(0.6, 0.0)),
+        c64(-4.07582991437534, 0.133016429126555),
+        1e-13,
+    );
+}
+
+#[test]
+fn jacobi_elliptic_functions() {
+    use crate::elliptic::ellipj;
+    let u = c64(0.8, 0.6);
+    let expected = [
+        (
+            0.5,
+            (0.827520170269495, 0.377087278121462),
+            (0.784592590698085, -0.397718933746099),
+            (0.87218331102947, -0.178888614727798),
+        ),
+        (
+            0.99,
+            (0.808422316722792, 0.318186319597163),
+            (0.751557444522983, -0.342261158495345),
+            (0.753321572783653, -0.338045054890509),
+        ),
+        (
+            2.0,
+            (0.778463317394494, 0.211162648992428),
+            (0.70239270324281, -0.234032010135546),
+            (0.522528947992166, -0.629179978931691),
+        ),
+        (
+            -1.5,
+            (0.941846995798020, 0.70089318157401),
+            (1.01393238252699, -0.651063274846364),
+            (1.43801622691018, 0.688588339707966),
+        ),
+    ];
+    for &(m, sn, cn, dn) in expected.iter() {
+        let (s, c, d) = ellipj(u, m);
+        assert_close(s, c64(sn.0, sn.1), 1e-12);
+        assert_close(c, c64(cn.0, cn.1), 1e-12);
+        assert_close(d, c64(dn.0, dn.1), 1e-12);
+    }
+}
+
+#[test]
+fn jacobi_theta_functions() {
+    use crate::elliptic::{theta1, theta2, theta3, theta4};
+    let (z, q) = (c64(0.5, 0.3), c64(0.2, 0.1));
+    assert_close(
+        theta1(z, q),
+        c64(0.596782900494528, 0.357683943151179),
+        1e-13,
+    );
+    assert_close(
+        theta2(z, q),
+        c64(1.34114128526335, -0.0828172621655980),
+        1e-13,
+    );
+    assert_close(
+        theta3(z, q),
+        c64(1.37099549612817, -0.0878755394166624),
+        1e-13,
+    );
+    assert_close(
+        theta4(z, q),
+        c64(0.644290772621443, 0.0844850908590966),
+        1e-13,
+    );
+    let zero = c64(0.0, 0.0);
+    assert_eq!(theta1(z, zero), zero);
+    assert_eq!(theta2(z, zero), zero);
+    assert_eq!(theta3(z, zero), c64(1.0, 0.0));
+    assert_eq!(theta4(z, zero), c64(1.0, 0.0));
+}
+
+#[test]
+#[should_panic(expected = "|q| < 1")]
+fn theta_outside_unit_disk() {
+    crate::elliptic::theta3(c64(0.5, 0.0), c64(1.2, 0.0));
+}
+
+fn naive_dft(input: &[c64], sign: f64) -> Vec<c64> {
+    let n = input.len();
+    (0..n)
+        .map(|k| {
+            input
+                .iter()
+                .enumerate()
+                .fold(c64(0.0, 0.0), |acc, (j, &x)| {
+                    let theta = sign * 2.0 * std::f64::consts::PI * ((j * k) % n) as f64 / n as f64;
+                    acc + x * Complex::from_polar(1.0, theta)
+                })
+        })
+        .collect()
+}
+
+fn test_signal(n: usize) -> Vec<c64> {
+    (0..n)
+        .map(|k| {
+            c64(
+                (k as f64 * 0.37).sin() + 0.1 * k as f64,
+                (k as f64 * 1.3).cos(),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn fft_matches_naive_dft() {
+    use crate::fft::FftPlan;
+    for &n in [
+        1, 2, 3, 4, 5, 6, 7, 8, 12, 15, 16, 17, 30, 49, 64, 97, 100, 128, 243, 250,
+    ]
+    .iter()
+    {
+        let input = test_signal(n);
+        let expected = naive_dft(&input, -1.0);
+        let mut output = input.clone();
+        FftPlan::new(n).forward(&mut output);
+        for (x, y) in output.iter().zip(&expected) {
+            assert_close(*x, *y, 1e-11);
+        }
+    }
+}
+
+#[test]
+fn ifft_matches_naive_dft() {
+    use crate::fft::FftPlan;
+    for &n in [3, 8, 10, 19, 36, 101].iter() {
+        let input = test_signal(n);
+        let expected = naive_dft(&input, 1.0);
+        let mut output = vec![c64(0.0, 0.0); n];
+        FftPlan::new(n).inverse_to(&input, &mut output);
+        for (x, y) in output.iter().zip(&expected) {
+            assert_close(*x * n as f64, *y, 1e-11);
+        }
+    }
+}
+
+#[test]
+fn fft_round_trip() {
+    use crate::fft::{fft, ifft};
+    for &n in [9, 32, 53, 120].iter() {
+        let input = test_signal(n);
+        let mut buffer = input.clone();
+        fft(&mut buffer);
+        ifft(&mut buffer);
+        for (x, y) in buffer.iter().zip(&input) {
+            assert_close(*x, *y, 1e-12);
+        }
+    }
+}
+
+#[test]
+fn real_fft_matches_complex_fft() {
+    use crate::fft::{irfft, rfft};
+    for &n in [1, 2, 7, 10, 16, 31, 64].iter() {
+        let signal: Vec<f64> = test_signal(n).iter().map(|c| c.real).collect();
+        let as_complex: Vec<c64> = signal.iter().map(|&x| c64(x, 0.0)).collect();
+        let expected = naive_dft(&as_complex, -1.0);
+        let spectrum = rfft(&signal);
+        assert_eq!(spectrum.len(), n / 2 + 1);
+        for (x, y) in spectrum.iter().zip(&expected) {
+            assert_close(*x, *y, 1e-11);
+        }
+        let restored = irfft(&spectrum, n);
+        for (x, y) in restored.iter().zip(&signal) {
+            assert!((x - y).abs() < 1e-11);
+        }
+    }
+}
+
+#[test]
+fn fft2_matches_naive_dft() {
+    use crate::fft::{fft2, ifft2};
+    let (rows, cols) = (6, 5);
+    let input = test_signal(rows * cols);
+    let mut expected = input.clone();
+    for row in expected.chunks_mut(cols) {
+        let transformed = naive_dft(row, -1.0);
+        row.copy_from_slice(&transformed);
+    }
+    for c in 0..cols {
+        let column: Vec<c64> = (0..rows).map(|r| expected[r * cols + c]).collect();
+        for (r, x) in naive_dft(&column, -1.0).into_iter().enumerate() {
+            expected[r * cols + c] = x;
+        }
+    }
+    let mut buffer = input.clone();
+    fft2(&mut buffer, rows, cols);
+    for (x, y) in buffer.iter().zip(&expected) {
+        assert_close(*x, *y, 1e-11);
+    }
+    ifft2(&mut buffer, rows, cols);
+    for (x, y) in buffer.iter().zip(&input) {
+        assert_close(*x, *y, 1e-12);
+    }
+}
+
+#[test]
+fn fftn_round_trip() {
+    use crate::fft::FftPlanNd;
+    let shape = [3, 4, 5];
+    let input = test_signal(60);
+    let plan = FftPlanNd::new(&shape);
+    let mut buffer = input.clone();
+    plan.forward(&mut buffer);
+    // DC bin is the sum of all samples
+    let sum = input.iter().fold(c64(0.0, 0.0), |acc, &x| acc + x);
+    assert_close(buffer[0], sum, 1e-12);
+    plan.inverse(&mut buffer);
+    for (x, y) in buffer.iter().zip(&input) {
+        assert_close(*x, *y, 1e-12);
+    }
+}
+
+#[test]
+fn fft_of_empty_input() {
+    use crate::convolution::circular_convolve;
+    use crate::fft::{fft, ifft, irfft, rfft, FftPlan, FftPlanNd};
+    use crate::hilbert::analytic_signal;
+    let mut buffer: Vec<c64> = vec![];
+    fft(&mut buffer);
+    ifft(&mut buffer);
+    assert!(buffer.is_empty());
+    assert!(FftPlan::<f64>::new(0).is_empty());
+    assert!(rfft::<f64>(&[]).is_empty());
+    assert!(irfft(&[c64(0.0, 0.0)], 0).is_empty());
+    let plan = FftPlanNd::new(&[3, 0, 2]);
+    plan.forward(&mut buffer);
+    plan.inverse(&mut buffer);
+    assert!(buffer.is_empty());
+    assert!(circular_convolve::<f64>(&[], &[]).is_empty());
+    assert!(analytic_signal::<f64>(&[]).is_empty());
+}
+
+fn direct_convolution(a: &[c64], b: &[c64]) -> Vec<c64> {
+    let mut out = vec![c64(0.0, 0.0); a.len() + b.len() - 1];
+    for (i, &x) in a.iter().enumerate() {
+        for (j, &y) in b.iter().enumerate() {
+            out[i + j] += x * y;
+        }
+    }
+    out
+}
+
+#[test]
+fn convolution_and_correlation() {
+    use crate::convolution::{circular_convolve, circular_correlate, convolve, correlate};
+    let a = test_signal(13);
+    let b: Vec<c64> = test_signal(5).iter().map(|x| x.conj() * 0.5).collect();
+    for (x, y) in convolve(&a, &b).iter().zip(&direct_convolution(&a, &b)) {
+        assert_close(*x, *y, 1e-12);
+    }
+    let r = correlate(&a, &b);
+    assert_eq!(r.len(), 17);
+    for lag in -4i32..13 {
+        let expected = (0..b.len() as i32)
+            .filter(|n| (0..13).contains(&(n + lag)))
+            .fold(c64(0.0, 0.0), |acc, n| {
+                acc + a[(n + lag) as usize] * b[n as usize].conj()
+            });
+        assert_close(r[(lag + 4) as usize], expected, 1e-12);
+    }
+    let c = test_signal(7);
+    let d: Vec<c64> = c.iter().rev().copied().collect();
+    let circular = circular_convolve(&c, &d);
+    let circular_corr = circular_correlate(&c, &d);
+    for k in 0..7 {
+        let conv = (0..7).fold(c64(0.0, 0.0), |acc, j| acc + c[j] * d[(7 + k - j) % 7]);
+        let corr = (0..7).fold(c64(0.0, 0.0), |acc, j| acc + c[(j + k) % 7] * d[j].conj());
+        assert_close(circular[k], conv, 1e-12);
+        assert_close(circular_corr[k], corr, 1e-12);
+    }
+}
+
+#[test]
+fn convolution_2d() {
+    use crate::convolution::convolve2d;
+    let a = test_signal(12);
+    let b = test_signal(6);
+    let result = convolve2d(&a, (3, 4), &b, (2, 3));
+    assert_eq!(result.len(), 4 * 6);
+    let mut expected = vec![c64(0.0, 0.0); 24];
+    for i in 0..3 {
+        for j in 0..4 {
+            for k in 0..2 {
+                for l in 0..3 {
+                    expected[(i + k) * 6 + j + l] += a[i * 4 + j] * b[k * 3 + l];
+                }
+            }
+        }
+    }
+    for (x, y) in result.iter().zip(&expected) {
+        assert_close(*x, *y, 1e-12);
+    }
+}
+
+#[test]
+fn streaming_block_convolution() {
+    use crate::convolution::{convolve, OverlapAdd, OverlapSave};
+    let signal = test_signal(100);
+    let filter = test_signal(9);
+    let expected = convolve(&signal, &filter);
+    let mut overlap_add = OverlapAdd::new(&filter, 16);
+    let mut overlap_save = OverlapSave::new(&filter, 16);
+    let (mut added, mut saved) = (Vec::new(), Vec::new());
+    for chunk in [7, 30, 1, 40, 22].iter().scan(0, |start, &len| {
+        let range = *start..*start + len;
+        *start += len;
+        Some(range)
+    }) {
+        added.extend(overlap_add.process(&signal[chunk.clone()]));
+        saved.extend(overlap_save.process(&signal[chunk]));
+    }
+    added.extend(overlap_add.flush());
+    assert_eq!(added.len(), expected.len());
+    for (x, y) in added.iter().zip(&expected) {
+        assert_close(*x, *y, 1e-12);
+    }
+    assert_eq!(saved.len(), signal.len());
+    for (x, y) in saved.iter().zip(&expected) {
+        assert_close(*x, *y, 1e-12);
+    }
+}
+
+fn direct_z_transform(input: &[c64], z: c64) -> c64 {
+    input
+        .iter()
+        .enumerate()
+        .fold(c64(0.0, 0.0), |acc, (n, &x)| acc + x * z.powi(-(n as i32)))
+}
+
+#[test]
+fn chirp_z_transform() {
+    use crate::spectral::czt;
+    let input = test_signal(11);
+    let w = Complex::from_polar(1.0, -2.0 * std::f64::consts::PI / 11.0);
+    let spectrum = czt(&input, 11, w, c64(1.0, 0.0));
+    for (x, y) in spectrum.iter().zip(&naive_dft(&input, -1.0)) {
+        assert_close(*x, *y, 1e-11);
+    }
+    // Inward spiral starting off the unit circle
+    let (w, a) = (
+        Complex::from_polar(1.02, -0.1),
+        Complex::from_polar(0.9, 0.3),
+    );
+    let spectrum = czt(&input, 20, w, a);
+    for (k, x) in spectrum.iter().enumerate() {
+        let z = a * w.powi(-(k as i32));
+        assert_close(*x, direct_z_transform(&input, z), 1e-10);
+    }
+}
+
+#[test]
+fn goertzel_single_bin() {
+    use crate::spectral::{goertzel, goertzel_complex};
+    let input = test_signal(40);
+    let omega = 0.731;
+    let expected = direct_z_transform(&input, Complex::from_polar(1.0, omega));
+    assert_close(goertzel_complex(&input, omega), expected, 1e-11);
+    let real: Vec<f64> = input.iter().map(|x| x.real).collect();
+    let as_complex: Vec<c64> = real.iter().map(|&x| c64(x, 0.0)).collect();
+    let expected = direct_z_transform(&as_complex, Complex::from_polar(1.0, omega));
+    assert_close(goertzel(&real, omega), expected, 1e-11);
+}
+
+#[test]
+fn zoom_fft_matches_direct_evaluation() {
+    use crate::spectral::zoom_fft;
+    let input = test_signal(64);
+    let (fs, f_start, f_end) = (1000.0, 100.0, 150.0);
+    let spectrum = zoom_fft(&input, 25, f_start, f_end, fs);
+    for (k, x) in spectrum.iter().enumerate() {
+        let f = f_start + (f_end - f_start) * k as f64 / 25.0;
+        let z = Complex::from_polar(1.0, 2.0 * std::f64::consts::PI * f / fs);
+        assert_close(*x, direct_z_transform(&input, z), 1e-11);
+    }
+}
+
+#[test]
+fn analytic_signal_of_cosine() {
+    use crate::hilbert::{analytic_signal, hilbert, instantaneous_amplitude};
+    let omega = 2.0 * std::f64::consts::PI * 5.0 / 64.0;
+    for &n in [64, 65].iter() {
+        let omega = omega * 64.0 / n as f64;
+        let signal: Vec<f64> = (0..n).map(|k| 3.0 * (omega * k as f64).cos()).collect();
+        let analytic = analytic_signal(&signal);
+        for (k, z) in analytic.iter().enumerate() {
+            assert_close(*z, Complex::from_polar(3.0, omega * k as f64), 1e-12);
+        }
+        for a in instantaneous_amplitude(&analytic) {
+            assert!((a - 3.0).abs() < 1e-12);
+        }
+        let transformed = hilbert(&signal);
+        assert!((transformed[3] - 3.0 * (omega * 3.0).sin()).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn fir_hilbert_transformer() {
+    use crate::hilbert::{analytic_signal_fir, hilbert_fir_taps};
+    let taps = hilbert_fir_taps::<f64>(31);
+    assert_eq!(taps[15], 0.0);
+    assert_eq!(taps[14], -taps[16]);
+    let omega = 1.1;
+    let signal: Vec<f64> = (0..200).map(|k| (omega * k as f64).cos()).collect();
+    let analytic = analytic_signal_fir(&signal, 63);
+    for (k, z) in analytic.iter().enumerate().take(160).skip(40) {
+        assert_close(*z, Complex::from_polar(1.0, omega * k as f64), 1e-2);
+    }
+}
+
+#[test]
+fn unwrapped_phase_and_instantaneous_frequency() {
+    use crate::hilbert::{instantaneous_frequency, instantaneous_phase, unwrapped_phase};
+    // Linear chirp with phase φ(n) = 0.1n + 0.002n²
+    let phase = |n: f64| 0.1 * n + 0.002 * n * n;
+    let analytic: Vec<c64> = (0..300)
+        .map(|n| Complex::from_polar(2.0, phase(n as f64)))
+        .collect();
+    let wrapped = instantaneous_phase(&analytic);
+    assert!(wrapped.iter().all(|p| p.abs() <= std::f64::consts::PI));
+    for (n, p) in unwrapped_phase(&analytic).iter().enumerate() {
+        assert!((p - phase(n as f64)).abs() < 1e-9);
+    }
+    let fs = 100.0;
+    for (n, f) in instantaneous_frequency(&analytic, fs).iter().enumerate() {
+        let expected =
+            (phase(n as f64 + 1.0) - phase(n as f64)) * fs / (2.0 * std::f64::consts::PI);
+        assert!((f - expected).abs() < 1e-9);
+    }
+}
+
+#[test]
+fn unwrap_phase_with_discontinuity() {
+    use crate::phase::{unwrap_angles, unwrap_phase};
+    use std::f64::consts::PI;
+    let samples: Vec<c64> = (0..50)
+        .map(|k| Complex::from_polar(1.5, 0.9 * k as f64))
+        .collect();
+    for (k, p) in unwrap_phase(&samples, PI).iter().enumerate() {
+        assert!((p - 0.9 * k as f64).abs() < 1e-12);
+    }
+    // Jumps smaller than discont are kept as they are
+    let angles = [0.0, 2.0, 4.0, -2.0];
+    assert_eq!(unwrap_angles(&angles, 3.0 * PI), angles.to_vec());
+    let unwrapped = unwrap_angles(&angles, PI);
+    assert!((unwrapped[3] - (2.0 * PI - 2.0)).abs() < 1e-12);
+}
+
+#[test]
+fn circular_statistics() {
+    use crate::phase::*;
+    use std::f64::consts::PI;
+    let angles = [0.1, 0.3, -0.2, 0.5, 0.0];
+    let samples: Vec<c64> = angles
+        .iter()
+        .enumerate()
+        .map(|(k, &a)| Complex::from_polar(1.0 + k as f64, a))
+        .collect();
+    let (c, s) = angles
+        .iter()
+        .fold((0.0, 0.0), |(c, s), a: &f64| (c + a.cos(), s + a.sin()));
+    let r = (c * c + s * s).sqrt() / 5.0;
+    assert!((resultant_length(&samples) - r).abs() < 1e-12);
+    assert!((circular_mean(&samples) - s.atan2(c)).abs() < 1e-12);
+    assert!((circular_variance(&samples) - (1.0 - r)).abs() < 1e-12);
+    assert!((circular_std(&samples) - (-2.0 * r.ln()).sqrt()).abs() < 1e-12);
+    // Mean wraps around ±π instead of averaging to zero
+    let opposite = [
+        Complex::from_polar(1.0, PI - 0.1),
+        Complex::from_polar(1.0, -PI + 0.1),
+    ];
+    assert!((circular_mean(&opposite).abs() - PI).abs() < 1e-12);
+    // Zero samples carry no phase
+    let with_zero = [c64(0.0, 0.0), c64(0.0, 3.0)];
+    assert!((circular_mean(&with_zero) - PI / 2.0).abs() < 1e-12);
+    // Without phasors the statistics are undefined
+    let empty: [c64; 0] = [];
+    let resultant = mean_resultant(&empty);
+    assert!(resultant.real.is_nan() && resultant.imag.is_nan());
+    assert!(mean_resultant(&with_zero[..1]).real.is_nan());
+    assert!(resultant_length(&empty).is_nan());
+    assert!(circular_mean(&empty).is_nan());
+}
+
+#[test]
+fn rayleigh_test_and_phase_locking() {
+    use crate::phase::{phase_locking_value, rayleigh_test};
+    let uniform: Vec<c64> = (0..100)
+        .map(|k| Complex::from_polar(1.0, 2.0 * std::f64::consts::PI * k as f64 / 100.0))
+        .collect();
+    let test = rayleigh_test(&uniform);
+    assert!(test.statistic < 1e-20);
+    assert!(test.p_value > 0.99);
+    let clustered: Vec<c64> = (0..30)
+        .map(|k| Complex::from_polar(2.0, 0.5 + 0.01 * k as f64))
+        .collect();
+    let test = rayleigh_test(&clustered);
+    assert!(test.statistic > 29.0);
+    assert!(test.p_value < 1e-10);
+    let shifted: Vec<c64> = uniform
+        .iter()
+        .map(|z| *z * Complex::from_polar(3.0, 0.7))
+        .collect();
+    assert!((phase_locking_value(&uniform, &shifted) - 1.0).abs() < 1e-12);
+    let scrambled: Vec<c64> = uniform.iter().map(|z| z.powi(3)).collect();
+    assert!(phase_locking_value(&uniform, &scrambled) < 1e-12);
+}
+
+#[test]
+fn window_functions() {
+    use crate::window::Window;
+    let close = |a: &[f64], b: &[f64]| a.iter().zip(b).all(|(x, y)| (x - y).abs() < 1e-12);
+    assert!(close(
+        &Window::Hann.symmetric(5),
+        &[0.0, 0.5, 1.0, 0.5, 0.0]
+    ));
+    assert!(close(&Window::Hamming.symmetric(3), &[0.08, 1.0, 0.08]));
+    assert!(close(&Window::Hann.periodic(4), &[0.0, 0.5, 1.0, 0.5]));
+    let blackman_harris: Vec<f64> = Window::BlackmanHarris.symmetric(7);
+    assert!((blackman_harris[0] - 6.0e-5).abs() < 1e-12);
+    assert!((blackman_harris[3] - 1.0).abs() < 1e-12);
+    let flat_top: Vec<f64> = Window::FlatTop.symmetric(5);
+    assert!((flat_top[0] + 4.21051e-4).abs() < 1e-9);
+    assert!((flat_top[2] - 1.0).abs() < 1e-8);
+    let kaiser: Vec<f64> = Window::Kaiser(8.6).symmetric(9);
+    assert!((kaiser[1] - 0.0674720792044085).abs() < 1e-12);
+    assert!((kaiser[4] - 1.0).abs() < 1e-12);
+    assert!(close(
+        &Window::Tukey(0.5).symmetric(5),
+        &[0.0, 1.0, 1.0, 1.0, 0.0]
+    ));
+    assert!(close(
+        &Window::Tukey(1.0).symmetric(6),
+        &Window::Hann.symmetric(6)
+    ));
+    assert!(close(
+        &Window::Tukey(0.0).symmetric(4),
+        &Window::<f64>::Rectangular.symmetric(4)
+    ));
+}
+
+#[test]
+fn stft_frames_and_cola_checks() {
+    use crate::stft::{is_cola, is_nola, spectrogram, Stft};
+    use crate::window::Window;
+    assert!(is_cola(&Window::Hann.periodic(64), 32, 1e-12));
+    assert!(is_cola(&Window::Hamming.periodic(64), 16, 1e-12));
+    assert!(!is_cola(&Window::Hann.symmetric(64), 32, 1e-6));
+    assert!(is_nola(&Window::Hann.symmetric(64), 32, 1e-12));
+    assert!(!is_nola(&Window::Hann.periodic(8), 8, 1e-12));
+    let window = Window::Hann.periodic(16);
+    let stft = Stft::new(window.clone(), 4);
+    let signal = test_signal(50);
+    let frames = stft.forward(&signal);
+    assert_eq!(frames.len(), stft.num_frames(50) * 16);
+    // Frame 5 starts at sample 5 * 4 - 8 of the unpadded signal
+    let segment: Vec<c64> = (0..16).map(|k| signal[12 + k] * window[k]).collect();
+    for (x, y) in frames[5 * 16..6 * 16]
+        .iter()
+        .zip(&naive_dft(&segment, -1.0))
+    {
+        assert_close(*x, *y, 1e-12);
+    }
+    let power = spectrogram(&frames);
+    assert!((power[80] - frames[80].square_norm()).abs() < 1e-12);
+}
+
+#[test]
+fn stft_perfect_reconstruction() {
+    use crate::stft::Stft;
+    use crate::window::Window;
+    let signal = test_signal(203);
+    for &(window, hop) in [
+        (Window::Hann, 32),
+        (Window::Kaiser(6.0), 16),
+        (Window::BlackmanHarris, 20),
+        (Window::Tukey(0.3), 50),
+    ]
+    .iter()
+    {
+        let stft = Stft::new(window.periodic(64), hop);
+        let restored = stft.inverse(&stft.forward(&signal), signal.len());
+        assert_eq!(restored.len(), signal.len());
+        for (x, y) in restored.iter().zip(&signal) {
+            assert_close(*x, *y, 1e-12);
+        }
+    }
+    let real: Vec<f64> = signal.iter().map(|x| x.real).collect();
+    let stft = Stft::new(Window::Hamming.periodic(32), 8);
+    let restored = stft.inverse(&stft.forward_real(&real), real.len());
+    for (x, y) in restored.iter().zip(&real) {
+        assert!((x.real - y).abs() < 1e-12 && x.imag.abs() < 1e-12);
+    }
+    assert!(stft.inverse(&[], real.len()).is_empty());
+}
+
+#[test]
+#[should_panic(expected = "hop size must be positive")]
+fn cola_check_with_zero_hop() {
+    use crate::stft::is_cola;
+    use crate::window::Window;
+    is_cola(&Window::Hann.periodic(16), 0, 1e-12);
+}
+
+#[test]
+fn fir_filter_streaming() {
+    use crate::filter::{Filter, Fir};
+    let taps = vec![
+        c64(0.5, 0.2),
+        c64(-0.3, 0.1),
+        c64(0.25, 0.0),
+        c64(0.0, -0.4),
+    ];
+    let input = test_signal(30);
+    let expected = direct_convolution(&input, &taps);
+    let mut fir = Fir::new(taps.clone());
+    let mut output = fir.process(&input[..11]);
+    output.extend(fir.process(&input[11..]));
+    for (x, y) in output.iter().zip(&expected) {
+        assert_close(*x, *y, 1e-12);
+    }
+    fir.reset();
+    assert_close(fir.process_sample(c64(1.0, 0.0)), taps[0], 1e-15);
+    let omega = 0.8;
+    let expected = direct_z_transform(&taps, Complex::from_polar(1.0, omega));
+    assert_close(fir.frequency_response(omega), expected, 1e-12);
+}
+
+#[test]
+fn iir_filter_direct_form() {
+    use crate::filter::{Biquad, Filter, Iir, Sos};
+    let b = vec![c64(0.2, 0.1), c64(0.4, 0.0), c64(0.2, -0.1)];
+    let a = vec![c64(2.0, 0.0), c64(-0.8, 0.2), c64(0.3, 0.0)];
+    let input = test_signal(40);
+    // a₀y[n] = Σ b_k x[n-k] - a₁y[n-1] - a₂y[n-2]
+    let mut expected: Vec<c64> = Vec::new();
+    for n in 0..input.len() {
+        let mut acc = c64(0.0, 0.0);
+        for k in 0..3 {
+            if n >= k {
+                acc += b[k] * input[n - k];
+                if k > 0 {
+                    acc -= a[k] * expected[n - k];
+                }
+            }
+        }
+        expected.push(acc / a[0]);
+    }
+    let mut iir = Iir::new(b.clone(), a.clone());
+    let mut output = iir.process(&input[..7]);
+    output.extend(iir.process(&input[7..]));
+    for (x, y) in output.iter().zip(&expected) {
+        assert_close(*x, *y, 1e-12);
+    }
+    let mut biquad = Biquad::new([b[0], b[1], b[2]], [a[0], a[1], a[2]]);
+    for (x, y) in biquad.process(&input).iter().zip(&expected) {
+        assert_close(*x, *y, 1e-12);
+    }
+    // Response of a stable filter matches the DTFT of its impulse response
+    let mut impulse = vec![c64(0.0, 0.0); 400];
+    impulse[0] = c64(1.0, 0.0);
+    iir.reset();
+    let h = iir.process(&impulse);
+    let omega = 1.3;
+    let expected = direct_z_transform(&h, Complex::from_polar(1.0, omega));
+    assert_close(iir.frequency_response(omega), expected, 1e-12);
+    assert_close(biquad.frequency_response(omega), expected, 1e-12);
+    let second = Biquad::from_real([1.0, -0.5, 0.0], [1.0, 0.0, 0.25]);
+    let mut sos = Sos::new(vec![biquad, second]);
+    sos.reset();
+    assert_close(
+        sos.frequency_response(omega),
+        expected * second.frequency_response(omega),
+        1e-12,
+    );
+    let mut chained = second;
+    let cascaded = chained.process(&Iir::new(b, a).process(&input));
+    for (x, y) in sos.process(&input).iter().zip(&cascaded) {
+        assert_close(*x, *y, 1e-12);
+    }
+}
+
+#[test]
+fn freqz_of_moving_average() {
+    use crate::filter::{freqz, Fir};
+    let fir = Fir::new(vec![c64(0.25, 0.0); 4]);
+    let response = freqz(&fir, 8);
+    assert_eq!(response.len(), 8);
+    assert_close(response[0], c64(1.0, 0.0), 1e-15);
+    assert!(response[2].norm() < 1e-15);
+    assert!(response[4].norm() < 1e-15);
+}
+
+#[test]
+fn butterworth_matches_reference_coefficients() {
+    use crate::design::{butter, Band};
+    // scipy.signal.butter(2, 0.2)
+    let sos = butter(2, Band::Lowpass(0.2), 2.0).to_sos();
+    assert_eq!(sos.sections().len(), 1);
+    let section = sos.sections()[0];
+    let b = [
+        0.067_455_273_889_071_9,
+        0.134_910_547_778_143_8,
+        0.067_455_273_889_071_9,
+    ];
+    let a = [-1.142_980_502_539_901, 0.412_801_598_096_188_8];
+    for (x, y) in section.b.iter().zip(&b) {
+        assert_close(*x, c64(*y, 0.0), 1e-12);
+    }
+    for (x, y) in section.a.iter().zip(&a) {
+        assert_close(*x, c64(*y, 0.0), 1e-12);
+    }
+}
+
+#[test]
+fn butterworth_band_edges_are_half_power() {
+    use crate::design::{butter, Band};
+    use crate::filter::Filter;
+    use std::f64::consts::PI;
+    let fs = 1000.0;
+    let omega = |f: f64| 2.0 * PI * f / fs;
+    let half_power = 0.5f64.sqrt();
+    let bandpass = butter(3, Band::Bandpass(100.0, 200.0), fs);
+    let sos = bandpass.to_sos();
+    assert_eq!(sos.sections().len(), 3);
+    for f in [100.0, 200.0] {
+        assert!((sos.frequency_response(omega(f)).norm() - half_power).abs() < 1e-10);
+    }
+    assert!(sos.frequency_response(0.0).norm() < 1e-12);
+    let highpass = butter(5, Band::Highpass(50.0), fs).to_sos();
+    assert!((highpass.frequency_response(omega(50.0)).norm() - half_power).abs() < 1e-10);
+    assert!((highpass.frequency_response(PI).norm() - 1.0).abs() < 1e-12);
+    let bandstop = butter(2, Band::Bandstop(100.0, 200.0), fs).to_sos();
+    assert!((bandstop.frequency_response(0.0).norm() - 1.0).abs() < 1e-12);
+    assert!((bandstop.frequency_response(omega(150.0)).norm()) < 0.2);
+    for section in bandstop.sections() {
+        assert!(section.b.iter().chain(&section.a).all(|c| c.imag == 0.0));
+    }
+}
+
+#[test]
+fn sos_agrees_with_zero_pole_gain_response() {
+    use crate::design::{ellip, Band};
+    use crate::filter::Filter;
+    let zpk = ellip(5, 0.5, 50.0, Band::Bandpass(0.1, 0.2), 1.0);
+    let sos = zpk.to_sos();
+    for omega in [0.3, 0.9, 1.2, 2.5] {
+        let expected = zpk.response(Complex::from_polar(1.0, omega));
+        assert_close(sos.frequency_response(omega), expected, 1e-9);
+    }
+    for section in sos.sections() {
+        let poles = (section.a[0] * section.a[0] - 4.0 * section.a[1]).sqrt();
+        assert!((-section.a[0] + poles).norm() / 2.0 < 1.0);
+        assert!((-section.a[0] - poles).norm() / 2.0 < 1.0);
+    }
+}
+
+#[test]
+fn chebyshev_ripple_at_band_edges() {
+    use crate::design::{cheby1, cheby2, Band};
+    use crate::filter::Filter;
+    use std::f64::consts::PI;
+    let db = |x: c64| 20.0 * x.norm().log10();
+    let type1 = cheby1(4, 1.0, Band::Lowpass(0.25), 2.0).to_sos();
+    assert!((db(type1.frequency_response(0.25 * PI)) + 1.0).abs() < 1e-9);
+    // Even order starts at the bottom of the ripple
+    assert!((db(type1.frequency_response(0.0)) + 1.0).abs() < 1e-9);
+    for k in 0..100 {
+        let gain = db(type1.frequency_response(0.25 * PI * k as f64 / 100.0));
+        assert!((-1.0 - 1e-9..=1e-9).contains(&gain));
+    }
+    let type2 = cheby2(5, 40.0, Band::Highpass(0.5), 2.0).to_sos();
+    assert!((db(type2.frequency_response(0.5 * PI)) + 40.0).abs() < 1e-8);
+    assert!((db(type2.frequency_response(PI))).abs() < 1e-9);
+    for k in 0..100 {
+        let gain = db(type2.frequency_response(0.5 * PI * k as f64 / 100.0));
+        assert!(gain <= -40.0 + 1e-8);
+    }
+}
+
+#[test]
+fn elliptic_prototype_is_equiripple() {
+    use crate::design::ellip_prototype;
+    let (rp, rs) = (1.0, 40.0);
+    let zpk = ellip_prototype::<f64>(4, rp, rs);
+    assert_eq!(zpk.zeros.len(), 4);
+    assert_eq!(zpk.poles.len(), 4);
+    let db = |w: f64| 20.0 * zpk.response(c64(0.0, w)).norm().log10();
+    assert!((db(1.0) + rp).abs() < 1e-9);
+    assert!((db(0.0) + rp).abs() < 1e-9);
+    let passband: Vec<f64> = (0..=200).map(|k| db(k as f64 / 200.0)).collect();
+    assert!(passband.iter().all(|g| (-rp - 1e-9..=1e-9).contains(g)));
+    assert!(passband.iter().any(|&g| g > -1e-4));
+    // Stopband starts where the gain first drops to -rs and never rises above it again
+    let grid: Vec<f64> = (0..=40_000).map(|k| 1.0 + k as f64 * 1e-3).collect();
+    let edge = grid.iter().position(|&w| db(w) <= -rs).unwrap();
+    let peak = grid[edge..]
+        .iter()
+        .map(|&w| db(w))
+        .filter(|g| g.is_finite())
+        .fold(f64::NEG_INFINITY, f64::max);
+    assert!(peak <= -rs + 1e-6 && peak > -rs - 0.05);
+    let constant = ellip_prototype::<f64>(0, rp, rs);
+    assert!(constant.zeros.is_empty() && constant.poles.is_empty());
+    assert!((20.0 * constant.gain.log10() + rp).abs() < 1e-12);
+}
+
+#[test]
+fn bessel_prototype_poles() {
+    use crate::design::bessel_prototype;
+    // Roots of s³ + 6s² + 15s + 15 scaled by 15^(-1/3)
+    let third = bessel_prototype::<f64>(3);
+    let mut poles = third.poles.clone();
+    poles.sort_by(|a, b| a.imag.partial_cmp(&b.imag).unwrap());
+    assert_close(
+        poles[0],
+        c64(-0.745_640_385_848_076_7, -0.711_366_624_972_835_3),
+        1e-12,
+    );
+    assert_close(poles[1], c64(-0.941_600_026_533_206_9, 0.0), 1e-12);
+    assert_close(
+        poles[2],
+        c64(-0.745_640_385_848_076_7, 0.711_366_624_972_835_3),
+        1e-12,
+    );
+    for order in 1..12 {
+        let zpk = bessel_prototype::<f64>(order);
+        assert_eq!(zpk.poles.len(), order);
+        assert!(zpk.poles.iter().all(|p| p.real < 0.0));
+        assert_close(zpk.response(c64(0.0, 0.0)), c64(1.0, 0.0), 1e-10);
+        // High frequency asymptote matches Butterworth of the same order
+        let w: f64 = 1e4;
+        let asymptote = zpk.response(c64(0.0, w)).norm() * w.powi(order as i32);
+        assert!((asymptote - 1.0).abs() < 1e-2);
+    }
+}
+
+#[test]
+fn polynomial_arithmetic_and_calculus() {
+    use crate::polynomial::Polynomial;
+    let p = Polynomial::new(vec![
+        c64(1.0, 2.0),
+        c64(-3.0, 0.5),
+        c64(0.0, 1.0),
+        c64(2.0, 0.0),
+    ]);
+    let q = Polynomial::new(vec![c64(0.5, -1.0), c64(1.0, 1.0)]);
+    let z = c64(0.7, -1.3);
+    let (value, slope) = p.eval_with_derivative(z);
+    assert_close(value, p.eval(z), 1e-15);
+    assert_close(slope, p.derivative().eval(z), 1e-14);
+    assert_close((&p + &q).eval(z), p.eval(z) + q.eval(z), 1e-14);
+    assert_close((&p - &q).eval(z), p.eval(z) - q.eval(z), 1e-14);
+    assert_close((&p * &q).eval(z), p.eval(z) * q.eval(z), 1e-14);
+    assert_close(p.compose(&q).eval(z), p.eval(q.eval(z)), 1e-13);
+    assert!((&p - &p).is_zero());
+    assert_eq!((&p * &q).degree(), 4);
+    let antiderivative = p.integral(c64(3.0, 0.0));
+    assert_close(antiderivative.eval(c64(0.0, 0.0)), c64(3.0, 0.0), 1e-15);
+    assert_eq!(antiderivative.derivative(), p);
+    let (quotient, remainder) = (&p * &q + Polynomial::from_real(&[1.0])).div_rem(&q);
+    assert_eq!(remainder.degree(), 0);
+    assert_close(remainder.coeffs()[0], c64(1.0, 0.0), 1e-13);
+    for (a, b) in quotient.coeffs().iter().zip(p.coeffs()) {
+        assert_close(*a, *b, 1e-13);
+    }
+}
+
+#[test]
+fn polynomial_roots_of_well_separated_and_real_polynomials() {
+    use crate::polynomial::Polynomial;
+    let expected = [
+        c64(1.0, 0.0),
+        c64(-2.0, 0.5),
+        c64(0.0, 3.0),
+        c64(0.25, -0.75),
+        c64(-1.5, -2.0),
+        c64(0.0, 0.0),
+    ];
+    let p = Polynomial::from_roots(&expected).scale(c64(2.0, -1.0));
+    let roots = p.roots();
+    assert_eq!(roots.len(), expected.len());
+    for r in &expected {
+        assert!(roots.iter().any(|z| (*z - *r).norm() < 1e-12));
+    }
+    // Wilkinson-like polynomial with roots 1..=10
+    let real: Vec<c64> = (1..=10).map(|k| c64(k as f64, 0.0)).collect();
+    let wilkinson = Polynomial::from_roots(&real);
+    for r in wilkinson.roots() {
+        let nearest = r.real.round();
+        assert!((r - c64(nearest, 0.0)).norm() < 1e-8 * nearest);
+    }
+}
+
+#[test]
+fn polynomial_multiple_roots() {
+    use crate::polynomial::Polynomial;
+    let roots = [
+        c64(1.0, 0.0),
+        c64(1.0, 0.0),
+        c64(1.0, 0.0),
+        c64(0.0, -2.0),
+        c64(0.0, -2.0),
+        c64(0.5, 0.0),
+    ];
+    let p = Polynomial::from_roots(&roots);
+    let mut found = p.roots_with_multiplicity(1e-3);
+    found.sort_by_key(|r| std::cmp::Reverse(r.1));
+    assert_eq!(found.len(), 3);
+    assert_eq!(found[0].1, 3);
+    assert_close(found[0].0, c64(1.0, 0.0), 1e-12);
+    assert_eq!(found[1].1, 2);
+    assert_close(found[1].0, c64(0.0, -2.0), 1e-12);
+    assert_eq!(found[2].1, 1);
+    assert_close(found[2].0, c64(0.5, 0.0), 1e-12);
+}
+
+fn assert_same_roots(found: &[c64], expected: &[c64], tol: f64) {
+    let mut remaining = expected.to_vec();
+    for z in found {
+        let (index, distance) = remaining
+            .iter()
+            .enumerate()
+            .map(|(k, r)| (k, (*r - *z).norm()))
+            .fold(
+                (0, f64::INFINITY),
+                |best, x| if x.1 < best.1 { x } else { best },
+            );
+        assert!(
+            distance <= tol * (1.0 + z.norm()),
+            "{:?} not in {:?}",
+            z,
+            expected
+        );
+        remaining.remove(index);
+    }
+}
+
+#[test]
+fn quadratic_avoids_cancellation() {
+    use crate::equations::solve_quadratic;
+    let roots: [c64; 2] = solve_quadratic(1.0, 1e9, 1.0);
+    assert!(roots.iter().all(|x| x.imag == 0.0));
+    assert_same_roots(&roots, &[c64(-1e9, 0.0), c64(-1e-9, 0.0)], 1e-15);
+    assert!((roots[1].real + 1e-9).abs() < 1e-24);
+    let pair: [c64; 2] = solve_quadratic(2.0, 2.0, 5.0);
+    assert_eq!(pair[0], pair[1].conj());
+    assert_same_roots(&pair, &[c64(-0.5, 1.5), c64(-0.5, -1.5)], 1e-15);
+    let expected = [c64(1e6, -2.0), c64(1e-6, 3e-6)];
+    let b = -(expected[0] + expected[1]);
+    let c = expected[0] * expected[1];
+    let roots = solve_quadratic(c64(1.0, 0.0), b, c);
+    assert_same_roots(&roots, &expected, 1e-15);
+    assert!((roots[1] - expected[1]).norm() < 1e-20);
+}
+
+#[test]
+fn cubic_real_and_complex_coefficients() {
+    use crate::equations::solve_cubic;
+    // (x - 1)(x - 2)(x + 3)
+    let roots: [c64; 3] = solve_cubic(1.0, 0.0, -7.0, 6.0);
+    assert!(roots.iter().all(|x| x.imag == 0.0));
+    assert_same_roots(
+        &roots,
+        &[c64(1.0, 0.0), c64(2.0, 0.0), c64(-3.0, 0.0)],
+        1e-14,
+    );
+    // (x - 2)(x² + 2x + 5)
+    let roots: [c64; 3] = solve_cubic(2.0, 0.0, 2.0, -20.0);
+    assert_eq!(roots[1], roots[2].conj());
+    assert_eq!(roots[0].imag, 0.0);
+    assert_same_roots(
+        &roots,
+        &[c64(2.0, 0.0), c64(-1.0, 2.0), c64(-1.0, -2.0)],
+        1e-14,
+    );
+    // Triple root
+    let roots: [c64; 3] = solve_cubic(1.0, -3.0, 3.0, -1.0);
+    assert_same_roots(&roots, &[c64(1.0, 0.0); 3], 1e-12);
+    let expected = [c64(1.0, 2.0), c64(-0.5, 0.25), c64(3.0, -1.0)];
+    let a = c64(0.5, -1.5);
+    let b = -(expected[0] + expected[1] + expected[2]) * a;
+    let c = (expected[0] * expected[1] + expected[0] * expected[2] + expected[1] * expected[2]) * a;
+    let d = -(expected[0] * expected[1] * expected[2]) * a;
+    assert_same_roots(&solve_cubic(a, b, c, d), &expected, 1e-14);
+}
+
+#[test]
+fn quartic_real_and_complex_coefficients() {
+    use crate::equations::solve_quartic;
+    use crate::polynomial::Polynomial;
+    let coeffs = |roots: &[c64], lead: c64| {
+        let p = Polynomial::from_roots(roots).scale(lead);
+        let c = p.coeffs().to_vec();
+        (c[4], c[3], c[2], c[1], c[0])
+    };
+    let real_roots = [c64(-2.0, 0.0), c64(0.5, 0.0), c64(1.0, 0.0), c64(4.0, 0.0)];
+    let (a, b, c, d, e) = coeffs(&real_roots, c64(3.0, 0.0));
+    let roots = solve_quartic(a.real, b.real, c.real, d.real, e.real);
+    assert!(roots.iter().all(|x| x.imag == 0.0));
+    assert_same_roots(&roots, &real_roots, 1e-13);
+    let mixed = [
+        c64(1.0, 2.0),
+        c64(1.0, -2.0),
+        c64(-3.0, 0.0),
+        c64(0.25, 0.0),
+    ];
+    let (a, b, c, d, e) = coeffs(&mixed, c64(1.0, 0.0));
+    let roots = solve_quartic(a.real, b.real, c.real, d.real, e.real);
+    assert_same_roots(&roots, &mixed, 1e-13);
+    // Biquadratic x⁴ - 5x² + 4
+    let roots: [c64; 4] = solve_quartic(1.0, 0.0, -5.0, 0.0, 4.0);
+    let expected = [c64(1.0, 0.0), c64(-1.0, 0.0), c64(2.0, 0.0), c64(-2.0, 0.0)];
+    assert_same_roots(&roots, &expected, 1e-14);
+    let general = [c64(1.0, 1.0), c64(-2.0, 0.5), c64(0.0, -3.0), c64(2.5, 0.0)];
+    let (a, b, c, d, e) = coeffs(&general, c64(0.5, 2.0));
+    assert_same_roots(&solve_quartic(a, b, c, d, e), &general, 1e-13);
+}
+
+fn test_matrix(rows: usize, cols: usize, seed: usize) -> crate::matrix::CMatrix<f64> {
+    crate::matrix::CMatrix::from_fn(rows, cols, |i, j| {
+        let k = (i * cols + j + seed) as f64;
+        c64((0.7 * k).sin() + 0.1 * i as f64, (1.3 * k + 0.4).cos())
+    })
+}
+
+#[test]
+fn vector_blas_level_one() {
+    use crate::matrix::CVector;
+    let x = CVector::new(vec![c64(1.0, 2.0), c64(-1.0, 0.5), c64(0.0, 3.0)]);
+    let mut y = CVector::new(vec![c64(2.0, -1.0), c64(0.5, 0.5), c64(1.0, 1.0)]);
+    let expected = x
+        .as_slice()
+        .iter()
+        .zip(y.as_slice())
+        .fold(c64(0.0, 0.0), |acc, (a, b)| acc + a.conj() * *b);
+    assert_close(x.dotc(&y), expected, 1e-15);
+    assert_close(x.dotc(&x), c64(x.norm() * x.norm(), 0.0), 1e-14);
+    assert_close(x.dotu(&y), x.conj().dotc(&y), 1e-15);
+    let before = y.clone();
+    y.axpy(c64(0.0, 2.0), &x);
+    for k in 0..3 {
+        assert_close(y[k], before[k] + c64(0.0, 2.0) * x[k], 1e-15);
+    }
+    let huge = CVector::new(vec![c64(3e200, 0.0), c64(0.0, 4e200)]);
+    assert!((huge.norm() / 5e200 - 1.0).abs() < 1e-15);
+}
+
+#[test]
+fn matrix_products_match_naive_loops() {
+    use crate::matrix::{gemm, gemv, CMatrix, CVector};
+    // Sizes cross the tile edge to exercise partial blocks
+    let a = test_matrix(70, 130, 1);
+    let b = test_matrix(130, 67, 2);
+    let naive = CMatrix::from_fn(70, 67, |i, j| {
+        (0..130).fold(c64(0.0, 0.0), |acc, k| acc + a[(i, k)] * b[(k, j)])
+    });
+    let product = &a * &b;
+    for (x, y) in product.as_slice().iter().zip(naive.as_slice()) {
+        assert_close(*x, *y, 1e-12);
+    }
+    let (alpha, beta) = (c64(0.5, -1.0), c64(2.0, 0.25));
+    let mut c = test_matrix(70, 67, 3);
+    let expected = &naive.scale(alpha) + &c.scale(beta);
+    gemm(alpha, &a, &b, beta, &mut c);
+    for (x, y) in c.as_slice().iter().zip(expected.as_slice()) {
+        assert_close(*x, *y, 1e-12);
+    }
+    let x = CVector::new((0..130).map(|k| c64(k as f64 * 0.01, 1.0)).collect());
+    let mut y = CVector::new(vec![c64(f64::NAN, 0.0); 70]);
+    gemv(c64(1.0, 0.0), &a, &x, c64(0.0, 0.0), &mut y);
+    for i in 0..70 {
+        let row = CVector::new(a.row(i).to_vec());
+        assert_close(y[i], row.dotu(&x), 1e-12);
+    }
+}
+
+#[test]
+fn matrix_structure_operations() {
+    use crate::matrix::CMatrix;
+    let a = test_matrix(3, 2, 4);
+    let b = test_matrix(2, 4, 5);
+    let h = a.hermitian();
+    assert_eq!((h.rows(), h.cols()), (2, 3));
+    assert_eq!(h[(1, 2)], a[(2, 1)].conj());
+    assert_eq!(h.hermitian(), a);
+    // (AB)ᴴ = BᴴAᴴ
+    let left = (&a * &b).hermitian();
+    let right = &b.hermitian() * &a.hermitian();
+    for (x, y) in left.as_slice().iter().zip(right.as_slice()) {
+        assert_close(*x, *y, 1e-14);
+    }
+    let sum: f64 = a.as_slice().iter().map(|x| x.square_norm()).sum();
+    assert!((a.frobenius_norm() - sum.sqrt()).abs() < 1e-14);
+    let square = test_matrix(3, 3, 6);
+    assert_close(
+        square.trace(),
+        square[(0, 0)] + square[(1, 1)] + square[(2, 2)],
+        1e-15,
+    );
+    assert!((&square + &square.hermitian()).is_hermitian(1e-15));
+    let k = a.kron(&b);
+    assert_eq!((k.rows(), k.cols()), (6, 8));
+    assert_eq!(k[(5, 7)], a[(2, 1)] * b[(1, 3)]);
+    assert_eq!(k[(2, 5)], a[(1, 1)] * b[(0, 1)]);
+    // tr(A ⊗ B) = tr(A)·tr(B)
+    let c = test_matrix(2, 2, 7);
+    assert_close(square.kron(&c).trace(), square.trace() * c.trace(), 1e-14);
+    assert_eq!(&CMatrix::identity(3) * &square, square);
+}
+
+#[test]
+#[should_panic(expected = "index out of bounds")]
+fn matrix_index_past_last_column() {
+    use crate::matrix::CMatrix;
+    let m = CMatrix::<f64>::zeros(2, 3);
+    let _ = m[(0, 3)];
+}
+
+fn random_matrix(rows: usize, cols: usize, seed: usize) -> crate::matrix::CMatrix<f64> {
+    // Fractional parts of a scrambled sine, uniform-looking in [-0.5, 0.5)
+    let noise = |k: usize| {
+        let x = (k as f64 * 12.9898 + seed as f64 * 78.233).sin() * 43758.5453;
+        x - x.floor() - 0.5
+    };
+    crate::matrix::CMatrix::from_fn(rows, cols, |i, j| {
+        let k = 2 * (i * cols + j);
+        c64(noise(k), noise(k + 1))
+    })
+}
+
+fn assert_matrix_close(
+    left: &crate::matrix::CMatrix<f64>,
+    right: &crate::matrix::CMatrix<f64>,
+    tol: f64,
+) {
+    assert_eq!((left.rows(), left.cols()), (right.rows(), right.cols()));
+    for (x, y) in left.as_slice().iter().zip(right.as_slice()) {
+        assert_close(*x, *y, tol);
+    }
+}
+
+#[test]
+fn lu_factors_solves_and_inverts() {
+    use crate::matrix::{CMatrix, CVector};
+    let a = random_matrix(6, 6, 11);
+    let lu = a.lu();
+    assert!(!lu.is_singular());
+    assert_matrix_close(&(&lu.p() * &a), &(&lu.l() * &lu.u()), 1e-13);
+    let b = CVector::new((0..6).map(|k| c64(k as f64, 1.0 - k as f64)).collect());
+    let x = lu.solve(&b).unwrap();
+    assert!((&(&a * &x) - &b).norm() < 1e-12);
+    let xh = lu.solve_hermitian(&b).unwrap();
+    assert!((&(&a.hermitian() * &xh) - &b).norm() < 1e-12);
+    assert_matrix_close(&(&a * &lu.inverse().unwrap()), &CMatrix::identity(6), 1e-12);
+    // det of 3×3 by the rule of Sarrus
+    let m = random_matrix(3, 3, 12);
+    let e = |i, j| m[(i, j)];
+    let sarrus =
+        e(0, 0) * e(1, 1) * e(2, 2) + e(0, 1) * e(1, 2) * e(2, 0) + e(0, 2) * e(1, 0) * e(2, 1)
+            - e(0, 2) * e(1, 1) * e(2, 0)
+            - e(0, 0) * e(1, 2) * e(2, 1)
+            - e(0, 1) * e(1, 0) * e(2, 2);
+    assert_close(m.lu().det(), sarrus, 1e-13);
+    let singular = CMatrix::new(
+        2,
+        2,
+        vec![c64(1.0, 1.0), c64(2.0, 2.0), c64(0.5, 0.5), c64(1.0, 1.0)],
+    );
+    let lu = singular.lu();
+    assert!(lu.is_singular());
+    assert!(lu.solve(&CVector::zeros(2)).is_none());
+    assert_eq!(lu.det(), c64(0.0, 0.0));
+    assert!(lu.condition_estimate().is_infinite());
+    // Singular only up to rounding, the last pivot is not exactly zero
+    let rounded = CMatrix::from_fn(3, 3, |i, j| c64((3 * i + j + 1) as f64, 0.0));
+    let lu = rounded.lu();
+    assert!(lu.is_singular());
+    assert!(lu.solve(&CVector::zeros(3)).is_none());
+    assert!(lu.det().norm() < 1e-12);
+}
+
+#[test]
+fn condition_estimate_bounds_true_condition_number() {
+    use crate::matrix::CMatrix;
+    // Complex Hilbert-like matrix, condition grows quickly with size
+    let a = CMatrix::from_fn(6, 6, |i, j| {
+        c64(1.0 / (i + j + 1) as f64, 0.1 * (i as f64 - j as f64))
+    });
+    let exact = a.norm_one() * a.lu().inverse().unwrap().norm_one();
+    let estimate = a.lu().condition_estimate();
+    assert!(estimate <= exact * (1.0 + 1e-8));
+    assert!(estimate >= exact / 3.0);
+    let h = &(&a.hermitian() * &a) + &CMatrix::identity(6).scale(c64(1e-6, 0.0));
+    let exact = h.norm_one() * h.lu().inverse().unwrap().norm_one();
+    let estimate = h.cholesky().unwrap().condition_estimate();
+    assert!(estimate <= exact * (1.0 + 1e-8) && estimate >= exact / 3.0);
+    assert!((CMatrix::<f64>::identity(4).lu().condition_estimate() - 1.0).abs() < 1e-15);
+}
+
+#[test]
+fn qr_factors_and_least_squares() {
+    use crate::matrix::{CMatrix, CVector};
+    let a = random_matrix(8, 4, 21);
+    let qr = a.qr();
+    let (q, r) = (qr.q(), qr.r());
+    assert_matrix_close(&(&q * &r), &a, 1e-13);
+    assert_matrix_close(&(&q.hermitian() * &q), &CMatrix::identity(4), 1e-13);
+    for i in 1..4 {
+        for j in 0..i {
+            assert_eq!(r[(i, j)], c64(0.0, 0.0));
+        }
+    }
+    let b = CVector::new(
+        (0..8)
+            .map(|k| c64((k as f64).cos(), 0.5 * k as f64))
+            .collect(),
+    );
+    let x = qr.solve_least_squares(&b).unwrap();
+    // Residual is orthogonal to the column space
+    let residual = &(&a * &x) - &b;
+    assert!((&a.hermitian() * &residual).norm() < 1e-12);
+    let normal = (&a.hermitian() * &a)
+        .cholesky()
+        .unwrap()
+        .solve(&(&a.hermitian() * &b));
+    assert!((&x - &normal).norm() < 1e-10);
+    let deficient = CMatrix::from_fn(5, 3, |i, j| {
+        c64((i + 1) as f64 * (j % 2) as f64, i as f64 * (j % 2) as f64)
+    });
+    assert!(deficient
+        .qr()
+        .solve_least_squares(&CVector::zeros(5))
+        .is_none());
+}
+
+#[test]
+fn cholesky_of_hermitian_positive_definite_matrix() {
+    use crate::matrix::{CMatrix, CVector};
+    let g = random_matrix(5, 5, 31);
+    let a = &(&g * &g.hermitian()) + &CMatrix::identity(5);
+    let chol = a.cholesky().unwrap();
+    let l = chol.l();
+    assert_matrix_close(&(l * &l.hermitian()), &a, 1e-13);
+    assert!((0..5).all(|i| l[(i, i)].imag == 0.0 && l[(i, i)].real > 0.0));
+    let b = CVector::new((0..5).map(|k| c64(1.0, k as f64)).collect());
+    assert!((&(&a * &chol.solve(&b)) - &b).norm() < 1e-12);
+    assert_close(c64(chol.det(), 0.0), a.lu().det(), 1e-12);
+    assert_matrix_close(&(&a * &chol.inverse()), &CMatrix::identity(5), 1e-12);
+    let indefinite = CMatrix::from_diagonal(&[c64(1.0, 0.0), c64(-2.0, 0.0)]);
+    assert!(indefinite.cholesky().is_none());
+}
+
+#[test]
+fn schur_and_general_eigenvectors() {
+    use crate::matrix::CMatrix;
+    let a = random_matrix(7, 7, 41);
+    let schur = a.schur().unwrap();
+    let (q, t) = (&schur.q, &schur.t);
+    assert_matrix_close(&(&q.hermitian() * q), &CMatrix::identity(7), 1e-13);
+    assert_matrix_close(&(&(q * t) * &q.hermitian()), &a, 1e-12);
+    for j in 0..7 {
+        for i in j + 1..7 {
+            assert_eq!(t[(i, j)], c64(0.0, 0.0));
+        }
+    }
+    let eigen = a.eig().unwrap();
+    for k in 0..7 {
+        let v = eigen.vectors.column(k);
+        assert!((v.norm() - 1.0).abs() < 1e-13);
+        let residual = &(&a * &v) - &v.scale(eigen.values[k]);
+        assert!(residual.norm() < 1e-12);
+    }
+    let sum = eigen.values.iter().fold(c64(0.0, 0.0), |acc, &x| acc + x);
+    let product = eigen.values.iter().fold(c64(1.0, 0.0), |acc, &x| acc * x);
+    assert_close(sum, a.trace(), 1e-12);
+    assert_close(product, a.lu().det(), 1e-12);
+    let big = random_matrix(40, 40, 42);
+    let schur = big.schur().unwrap();
+    let restored = &(&schur.q * &schur.t) * &schur.q.hermitian();
+    assert!((&restored - &big).frobenius_norm() < 1e-12 * big.frobenius_norm());
+    // A NaN entry never deflates, which is reported instead of a made-up T
+    let mut broken = random_matrix(4, 4, 43);
+    broken[(2, 1)] = c64(f64::NAN, 0.0);
+    assert!(broken.schur().is_none());
+    assert!(broken.eig().is_none() && broken.eigenvalues().is_none());
+    assert!(broken.sqrtm().is_none() && broken.logm().is_none());
+}
+
+#[test]
+fn eigenvalues_of_known_matrices() {
+    use crate::matrix::CMatrix;
+    // Companion matrix of (x - 1)(x - 2i)(x + 3) has those roots as eigenvalues
+    let roots = [c64(1.0, 0.0), c64(0.0, 2.0), c64(-3.0, 0.0)];
+    let p = crate::polynomial::Polynomial::from_roots(&roots);
+    let c = p.coeffs();
+    let companion = CMatrix::from_fn(3, 3, |i, j| {
+        if i == 0 {
+            -c[2 - j]
+        } else if i == j + 1 {
+            c64(1.0, 0.0)
+        } else {
+            c64(0.0, 0.0)
+        }
+    });
+    let values = companion.eigenvalues().unwrap();
+    assert_same_roots(&values, &roots, 1e-12);
+    // Jordan block is defective, eigenvalues still come out
+    let jordan = CMatrix::new(
+        2,
+        2,
+        vec![c64(2.0, 1.0), c64(1.0, 0.0), c64(0.0, 0.0), c64(2.0, 1.0)],
+    );
+    assert_same_roots(&jordan.eigenvalues().unwrap(), &[c64(2.0, 1.0); 2], 1e-12);
+    // Rotation generator has purely imaginary spectrum ±i
+    let rotation = CMatrix::new(
+        2,
+        2,
+        vec![c64(0.0, 0.0), c64(-1.0, 0.0), c64(1.0, 0.0), c64(0.0, 0.0)],
+    );
+    assert_same_roots(
+        &rotation.eigenvalues().unwrap(),
+        &[c64(0.0, 1.0), c64(0.0, -1.0)],
+        1e-14,
+    );
+}
+
+#[test]
+fn hermitian_eigensolver() {
+    use crate::matrix::CMatrix;
+    let g = random_matrix(6, 6, 51);
+    let h = &g + &g.hermitian();
+    let eigen = h.eigh();
+    assert!(eigen.values.windows(2).all(|w| w[0] <= w[1]));
+    let v = &eigen.vectors;
+    assert_matrix_close(&(&v.hermitian() * v), &CMatrix::identity(6), 1e-13);
+    let lambda: Vec<c64> = eigen.values.iter().map(|&x| c64(x, 0.0)).collect();
+    let reconstructed = &(v * &CMatrix::from_diagonal(&lambda)) * &v.hermitian();
+    assert_matrix_close(&reconstructed, &h, 1e-12);
+    let mut general: Vec<f64> = h.eigenvalues().unwrap().iter().map(|z| z.real).collect();
+    general.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    for (x, y) in general.iter().zip(&eigen.values) {
+        assert!((x - y).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn singular_value_decomposition() {
+    use crate::matrix::CMatrix;
+    for (m, n) in [(7, 4), (3, 5), (4, 4)] {
+        let a = random_matrix(m, n, 61 + m);
+        let svd = a.svd();
+        let k = m.min(n);
+        assert_eq!((svd.u.rows(), svd.u.cols()), (m, k));
+        assert_eq!((svd.v.rows(), svd.v.cols()), (n, k));
+        assert!(svd.singular_values.windows(2).all(|w| w[0] >= w[1]));
+        assert_matrix_close(&(&svd.u.hermitian() * &svd.u), &CMatrix::identity(k), 1e-13);
+        assert_matrix_close(&(&svd.v.hermitian() * &svd.v), &CMatrix::identity(k), 1e-13);
+        let sigma: Vec<c64> = svd.singular_values.iter().map(|&s| c64(s, 0.0)).collect();
+        let reconstructed = &(&svd.u * &CMatrix::from_diagonal(&sigma)) * &svd.v.hermitian();
+        assert_matrix_close(&reconstructed, &a, 1e-12);
+        // Squares of singular values are eigenvalues of AᴴA
+        let gram = (&a.hermitian() * &a).eigh();
+        for (s, lambda) in svd.singular_values.iter().zip(gram.values.iter().rev()) {
+            assert!((s * s - lambda).abs() < 1e-12);
+        }
+    }
+    // Rank one matrix keeps a full orthonormal U
+    let x = random_matrix(4, 1, 71);
+    let y = random_matrix(1, 3, 72);
+    let svd = (&x * &y).svd();
+    assert!(svd.singular_values[1] < 1e-14 && svd.singular_values[2] < 1e-14);
+    assert_matrix_close(&(&svd.u.hermitian() * &svd.u), &CMatrix::identity(3), 1e-13);
+}
+
+#[test]
+fn matrix_exponential() {
+    use crate::matrix::CMatrix;
+    // Hamiltonian evolution U = exp(-iHt) is unitary and diagonal in the eigenbasis of H
+    let g = random_matrix(5, 5, 81);
+    let h = &g + &g.hermitian();
+    let t = 0.7;
+    let u = h.scale(c64(0.0, -t)).expm();
+    assert_matrix_close(&(&u.hermitian() * &u), &CMatrix::identity(5), 1e-13);
+    let eigen = h.eigh();
+    let phases: Vec<c64> = eigen
+        .values
+        .iter()
+        .map(|&l| c64(0.0, -l * t).exp())
+        .collect();
+    let expected =
+        &(&eigen.vectors * &CMatrix::from_diagonal(&phases)) * &eigen.vectors.hermitian();
+    assert_matrix_close(&u, &expected, 1e-12);
+    // Small, medium and large norms take different Padé degrees
+    for scale in [1e-3, 0.1, 0.5, 1.5, 40.0] {
+        let a = random_matrix(4, 4, 82).scale(c64(scale, 0.0));
+        let e = a.expm();
+        // e^A = V·e^Λ·V⁻¹ and det e^A = e^tr A
+        let eigen = a.eig().unwrap();
+        let exps: Vec<c64> = eigen.values.iter().map(|l| l.exp()).collect();
+        let inverse = eigen.vectors.lu().inverse().unwrap();
+        let expected = &(&eigen.vectors * &CMatrix::from_diagonal(&exps)) * &inverse;
+        assert!((&e - &expected).frobenius_norm() < 1e-11 * expected.frobenius_norm());
+        if scale < 10.0 {
+            // For large norms the eigenvalues of e^A spread too far for an accurate determinant
+            let det = e.lu().det();
+            assert!((det - a.trace().exp()).norm() < 1e-11 * det.norm());
+        }
+    }
+    // Nilpotent matrix, series terminates: e^N = I + N + N²/2
+    let n = CMatrix::from_fn(3, 3, |i, j| {
+        if j == i + 1 {
+            c64(2.0, 1.0)
+        } else {
+            c64(0.0, 0.0)
+        }
+    });
+    let n2 = &n * &n;
+    let expected = &(&CMatrix::identity(3) + &n) + &n2.scale(c64(0.5, 0.0));
+    assert_matrix_close(&n.expm(), &expected, 1e-15);
+    assert_eq!(CMatrix::<f64>::zeros(3, 3).expm(), CMatrix::identity(3));
+    // Non-finite entries give NaN instead of a panic
+    for bad in [f64::INFINITY, f64::NAN] {
+        let mut a = CMatrix::identity(3);
+        a[(1, 2)] = c64(bad, 0.0);
+        assert!(a.expm().as_slice().iter().all(|x| x.real.is_nan()));
+    }
+    // Triangular input gets the exact scalar exponentials on the diagonal
+    // on both the low-degree and the scaling and squaring path
+    for scale in [0.5, 20.0] {
+        let upper = CMatrix::from_fn(3, 3, |i, j| match i.cmp(&j) {
+            std::cmp::Ordering::Greater => c64(0.0, 0.0),
+            _ => c64(0.3 + 0.2 * j as f64, 0.1 * i as f64 - 0.15).scale(scale),
+        });
+        let e = upper.expm();
+        for i in 0..3 {
+            assert_eq!(e[(i, i)], upper[(i, i)].exp());
+        }
+    }
+}
+
+#[test]
+fn matrix_square_root_and_logarithm() {
+    use crate::matrix::CMatrix;
+    let a = &random_matrix(5, 5, 91) + &CMatrix::identity(5).scale(c64(2.0, 0.0));
+    let root = a.sqrtm().unwrap();
+    assert_matrix_close(&(&root * &root), &a, 1e-12);
+    // Principal root has eigenvalues in the right half plane
+    assert!(root.eigenvalues().unwrap().iter().all(|z| z.real > 0.0));
+    let log = a.logm().unwrap();
+    assert_matrix_close(&log.expm(), &a, 1e-11);
+    assert!(log
+        .eigenvalues()
+        .unwrap()
+        .iter()
+        .all(|z| z.imag.abs() < std::f64::consts::PI));
+    let b = random_matrix(4, 4, 92).scale(c64(0.8, 0.0));
+    assert_matrix_close(&b.expm().logm().unwrap(), &b, 1e-11);
+    // Rotation by θ has logarithm θ·[[0, -1], [1, 0]]
+    let theta: f64 = 2.5;
+    let rotation = CMatrix::new(
+        2,
+        2,
+        vec![
+            c64(theta.cos(), 0.0),
+            c64(-theta.sin(), 0.0),
+            c64(theta.sin(), 0.0),
+            c64(theta.cos(), 0.0),
+        ],
+    );
+    let generator = CMatrix::new(
+        2,
+        2,
+        vec![
+            c64(0.0, 0.0),
+            c64(-theta, 0.0),
+            c64(theta, 0.0),
+            c64(0.0, 0.0),
+        ],
+    );
+    assert_matrix_close(&rotation.logm().unwrap(), &generator, 1e-13);
+    let singular = CMatrix::new(
+        2,
+        2,
+        vec![c64(1.0, 0.0), c64(2.0, 0.0), c64(2.0, 0.0), c64(4.0, 0.0)],
+    );
+    assert!(singular.logm().is_none());
+    let jordan = CMatrix::new(
+        2,
+        2,
+        vec![c64(0.0, 0.0), c64(1.0, 0.0), c64(0.0, 0.0), c64(0.0, 0.0)],
+    );
+    assert!(jordan.sqrtm().is_none());
+}
+
+#[test]
+fn mobius_apply_compose_and_inverse() {
+    use crate::mobius::Mobius;
+    let f = Mobius::new(c64(2.0, 1.0), c64(-1.0, 0.5), c64(0.5, -1.0), c64(1.0, 3.0));
+    let g = Mobius::new(c64(0.0, 1.0), c64(3.0, 0.0), c64(1.0, 1.0), c64(-2.0, 0.0));
+    for &z in &[c64(0.3, -0.7), c64(-2.0, 1.5), c64(10.0, 4.0)] {
+        let composed = (f * g).apply(Some(z)).unwrap();
+        assert_close(composed, f.apply(g.apply(Some(z))).unwrap(), 1e-13);
+        assert_close(f.inverse().apply(f.apply(Some(z))).unwrap(), z, 1e-13);
+    }
+    // Infinity maps to a/c, the pole -d/c maps to infinity
+    assert_close(f.apply(None).unwrap(), f.a / f.c, 1e-15);
+    assert_eq!(f.apply(Some(-f.d / f.c)), None);
+    assert_eq!(Mobius::translation(c64(1.0, 2.0)).apply(None), None);
+    assert_eq!(Mobius::reciprocal().apply(Some(c64(0.0, 0.0))), None);
+    assert_eq!(Mobius::reciprocal().apply(None), Some(c64(0.0, 0.0)));
+    let normalized = f.normalize();
+    assert_close(normalized.det(), c64(1.0, 0.0), 1e-14);
+    assert_close(
+        normalized.apply(Some(c64(0.1, 0.2))).unwrap(),
+        f.apply(Some(c64(0.1, 0.2))).unwrap(),
+        1e-14,
+    );
+    // Disk automorphisms preserve the unit circle and move p to the origin
+    let p = c64(0.4, -0.3);
+    let h = Mobius::disk_automorphism(0.7, p);
+    assert_close(h.apply(Some(p)).unwrap(), c64(0.0, 0.0), 1e-15);
+    for k in 0..8 {
+        let z = c64::from_polar(1.0, k as f64 * 0.8);
+        assert!((h.apply(Some(z)).unwrap().norm() - 1.0).abs() < 1e-14);
+    }
+}
+
+#[test]
+fn mobius_classification_and_fixed_points() {
+    use crate::mobius::{Mobius, MobiusKind};
+    let tol = 1e-12;
+    assert_eq!(
+        Mobius::<f64>::identity().classify(tol),
+        MobiusKind::Identity
+    );
+    assert!(Mobius::<f64>::identity().fixed_points().is_empty());
+    let dilation = Mobius::dilation(c64(3.0, 0.0));
+    assert_eq!(dilation.classify(tol), MobiusKind::Hyperbolic);
+    assert_eq!(dilation.fixed_points(), vec![None, Some(c64(0.0, 0.0))]);
+    let rotation = Mobius::dilation(c64::from_polar(1.0, 1.2));
+    assert_eq!(rotation.classify(tol), MobiusKind::Elliptic);
+    assert_eq!(
+        Mobius::dilation(c64::from_polar(2.0, 1.2)).classify(tol),
+        MobiusKind::Loxodromic
+    );
+    let translation = Mobius::translation(c64(1.0, -1.0));
+    assert_eq!(translation.classify(tol), MobiusKind::Parabolic);
+    assert_eq!(translation.fixed_points(), vec![None]);
+    // Conjugation by a generic map keeps the class and moves the fixed points
+    let m = Mobius::new(c64(1.0, 2.0), c64(0.5, 0.0), c64(-1.0, 0.5), c64(2.0, 1.0));
+    let conjugate = |f: Mobius<f64>| m * f * m.inverse();
+    assert_eq!(conjugate(dilation).classify(1e-10), MobiusKind::Hyperbolic);
+    assert_eq!(conjugate(rotation).classify(1e-10), MobiusKind::Elliptic);
+    assert_eq!(
+        conjugate(translation).classify(1e-10),
+        MobiusKind::Parabolic
+    );
+    let expected = [
+        m.apply(None).unwrap(),
+        m.apply(Some(c64(0.0, 0.0))).unwrap(),
+    ];
+    let fixed: Vec<c64> = conjugate(dilation)
+        .fixed_points()
+        .into_iter()
+        .map(|z| z.unwrap())
+        .collect();
+    assert_same_roots(&fixed, &expected, 1e-12);
+    let f = Mobius::new(c64(2.0, 1.0), c64(-1.0, 0.5), c64(0.5, -1.0), c64(1.0, 3.0));
+    for z in f.fixed_points() {
+        assert_close(f.apply(z).unwrap(), z.unwrap(), 1e-13);
+    }
+}
+
+#[test]
+fn mobius_from_three_points() {
+    use crate::mobius::Mobius;
+    let from = [
+        Some(c64(1.0, 0.0)),
+        Some(c64(0.0, 1.0)),
+        Some(c64(-1.0, 0.0)),
+    ];
+    let to = [Some(c64(0.0, 0.0)), Some(c64(1.0, 0.0)), None];
+    let f = Mobius::from_points(from, to).unwrap();
+    assert_close(f.apply(from[0]).unwrap(), c64(0.0, 0.0), 1e-15);
+    assert_close(f.apply(from[1]).unwrap(), c64(1.0, 0.0), 1e-15);
+    assert_eq!(f.apply(from[2]), None);
+    // Infinity as a source point, and mapping the triple onto itself
+    let from = [None, Some(c64(2.0, -1.0)), Some(c64(0.5, 0.5))];
+    let to = [
+        Some(c64(3.0, 3.0)),
+        Some(c64(-1.0, 0.0)),
+        Some(c64(0.0, 2.0)),
+    ];
+    let g = Mobius::from_points(from, to).unwrap();
+    for k in 0..3 {
+        assert_close(g.apply(from[k]).unwrap(), to[k].unwrap(), 1e-13);
+    }
+    let identity = Mobius::from_points(from, from).unwrap();
+    assert_close(
+        identity.apply(Some(c64(0.7, -0.1))).unwrap(),
+        c64(0.7, -0.1),
+        1e-14,
+    );
+    let repeated = [Some(c64(1.0, 0.0)), Some(c64(1.0, 0.0)), None];
+    assert!(Mobius::from_points(repeated, to).is_none());
+    assert!(Mobius::from_points(to, [None, None, Some(c64(0.0, 0.0))]).is_none());
+}
+
+#[test]
+fn extended_plane_arithmetic() {
+    use crate::extended::Extended::{self, Finite, Infinity};
+    let z = Finite(c64(2.0, -1.0));
+    let w = Finite(c64(0.5, 3.0));
+    let zero = Finite(c64(0.0, 0.0));
+    assert_eq!(z + w, Finite(c64(2.5, 2.0)));
+    assert_eq!(z * w, Finite(c64(4.0, 5.5)));
+    assert_eq!(z + Infinity, Infinity);
+    assert_eq!(Infinity - z, Infinity);
+    assert_eq!(z * Infinity, Infinity);
+    assert_eq!(Extended::<f64>::Infinity * Infinity, Infinity);
+    assert_eq!(z / zero, Infinity);
+    assert_eq!(z / Infinity, zero);
+    assert_eq!(Infinity / z, Infinity);
+    assert_eq!(-Extended::<f64>::Infinity, Infinity);
+    assert_eq!(zero.recip(), Infinity);
+    assert_eq!(Extended::<f64>::Infinity.recip(), zero);
+    assert!((Extended::<f64>::Infinity + Infinity).is_nan());
+    assert!((Extended::<f64>::Infinity - Infinity).is_nan());
+    assert!((zero * Infinity).is_nan());
+    assert!((zero / zero).is_nan());
+    assert!((Extended::<f64>::Infinity / Infinity).is_nan());
+    assert!(!Extended::<f64>::Infinity.is_nan());
+    assert_eq!(Extended::from(c64(f64::INFINITY, 0.0)), Infinity);
+    assert_eq!(Extended::from(c64(1.0, 0.0)), Finite(c64(1.0, 0.0)));
+    assert_eq!(format!("{}", Extended::<f64>::Infinity), "∞");
+}
+
+#[test]
+fn extended_plane_stereographic_projection() {
+    use crate::extended::Extended::{self, Finite, Infinity};
+    assert_eq!(Finite(c64(0.0, 0.0)).to_sphere(), [0.0, 0.0, -1.0]);
+    assert_eq!(Finite(c64(1e300, 1e300)).to_sphere(), [0.0, 0.0, 1.0]);
+    assert_eq!(Extended::<f64>::from_sphere([0.0, 0.0, 1.0]), Infinity);
+    for &z in &[
+        c64(0.3, -0.2),
+        c64(-4.0, 7.0),
+        c64(1e-8, 2e-8),
+        c64(3e7, -1e7),
+        c64(0.0, -1.0),
+    ] {
+        let point = Finite(z).to_sphere();
+        let radius = point.iter().map(|x| x * x).sum::<f64>();
+        assert!((radius - 1.0).abs() < 1e-15);
+        let back = Extended::from_sphere(point).finite().unwrap();
+        assert!((back - z).norm() <= 1e-15 * z.norm());
+        // The chordal distance is the straight line distance on the sphere
+        for &w in &[Finite(c64(1.0, 1.0)), Finite(c64(-2e5, 0.0)), Infinity] {
+            let other = w.to_sphere();
+            let chord = (0..3)
+                .map(|k| (point[k] - other[k]).powi(2))
+                .sum::<f64>()
+                .sqrt();
+            assert!((Finite(z).chordal_distance(w) - chord).abs() < 1e-12);
+            assert_eq!(Finite(z).chordal_distance(w), w.chordal_distance(Finite(z)));
+        }
+    }
+    assert_eq!(Extended::<f64>::Infinity.chordal_distance(Infinity), 0.0);
+    // Antipodal points z and -1/z̄ are at distance 2
+    let z = c64(0.6, 0.8) * 3.0;
+    let antipode = -z.conj().inv();
+    assert!((Finite(z).chordal_distance(Finite(antipode)) - 2.0).abs() < 1e-15);
+}
+
+#[test]
+fn mobius_on_extended_plane() {
+    use crate::extended::Extended::{self, Finite, Infinity};
+    use crate::mobius::Mobius;
+    assert_eq!(Extended::from(None::<c64>), Infinity);
+    assert_eq!(Extended::from(Some(c64(1.0, 2.0))), Finite(c64(1.0, 2.0)));
+    assert_eq!(Option::<c64>::from(Extended::<f64>::Infinity), None);
+    let cayley = Mobius::new(c64(1.0, 0.0), c64(0.0, -1.0), c64(1.0, 0.0), c64(0.0, 1.0));
+    assert_eq!(
+        cayley.apply_extended(Finite(c64(0.0, 1.0))),
+        Finite(c64(0.0, 0.0))
+    );
+    assert_eq!(cayley.apply_extended(Finite(c64(0.0, -1.0))), Infinity);
+    assert_eq!(cayley.apply_extended(Infinity), Finite(c64(1.0, 0.0)));
+    assert_eq!(
+        Mobius::dilation(c64(3.0, 0.0)).fixed_points_extended(),
+        vec![Infinity, Finite(c64(0.0, 0.0))]
+    );
+    assert!(Mobius::<f64>::identity().fixed_points_extended().is_empty());
+    let from = [Infinity, Finite(c64(2.0, -1.0)), Finite(c64(0.5, 0.5))];
+    let to = [Finite(c64(0.0, 0.0)), Infinity, Finite(c64(1.0, 0.0))];
+    let f = Mobius::from_extended_points(from, to).unwrap();
+    assert_eq!(f.apply_extended(from[1]), Infinity);
+    assert_close(
+        f.apply_extended(from[0]).finite().unwrap(),
+        c64(0.0, 0.0),
+        1e-15,
+    );
+    assert_close(
+        f.apply_extended(from[2]).finite().unwrap(),
+        c64(1.0, 0.0),
+        1e-15,
+    );
+    assert!(Mobius::from_extended_points([Infinity; 3], to).is_none());
+}
+
+#[test]
+fn geometry_lines_and_circles() {
+    use crate::geometry::{rotate_about, Circle, Line};
+    let center = c64(1.0, 2.0);
+    let rotated = rotate_about(c64(3.0, 2.0), center, FRAC_PI_2);
+    assert_close(rotated, c64(1.0, 4.0), 1e-15);
+    let line = Line::through(c64(0.0, 1.0), c64(2.0, 2.0));
+    for &z in &[c64(3.0, -1.0), c64(-0.5, 4.0), c64(1.0, 1.5)] {
+        let image = line.reflect(z);
+        assert_close(line.reflect(image), z, 1e-14);
+        assert_close(line.project(image), line.project(z), 1e-14);
+        assert!((line.signed_distance(image) + line.signed_distance(z)).abs() < 1e-14);
+    }
+    assert!(line.signed_distance(c64(0.0, 5.0)) > 0.0);
+    let other = Line::through(c64(0.0, 0.0), c64(1.0, -1.0));
+    let crossing = line.intersect(&other).unwrap();
+    assert!(line.signed_distance(crossing).abs() < 1e-15);
+    assert!(other.signed_distance(crossing).abs() < 1e-15);
+    let parallel = Line {
+        point: c64(5.0, 5.0),
+        direction: c64(-4.0, -2.0),
+    };
+    assert!(line.intersect(&parallel).is_none());
+
+    let (a, b, c) = (c64(4.0, 1.0), c64(-2.0, 3.0), c64(0.5, -6.0));
+    let circle = Circle::through(a, b, c).unwrap();
+    for z in [a, b, c] {
+        assert!(((z - circle.center).norm() - circle.radius).abs() < 1e-13);
+    }
+    assert!(Circle::through(a, (a + b) * 0.5, b).is_none());
+    assert!(circle.contains(circle.center));
+    let outside = circle.center + c64(circle.radius * 3.0, 0.0);
+    assert!(!circle.contains(outside));
+    let inverted = circle.invert(outside);
+    assert!(((inverted - circle.center).norm() - circle.radius / 3.0).abs() < 1e-13);
+    assert_close(circle.invert(a), a, 1e-13);
+
+    let unit = Circle::new(c64(0.0, 0.0), 1.0);
+    let secant = Line::through(c64(-2.0, 0.6), c64(2.0, 0.6));
+    let points = unit.intersect_line(&secant);
+    assert_eq!(points.len(), 2);
+    assert_close(points[0], c64(-0.8, 0.6), 1e-15);
+    assert_close(points[1], c64(0.8, 0.6), 1e-15);
+    let tangent = Line::through(c64(3.0, 1.0), c64(-1.0, 1.0));
+    assert_eq!(unit.intersect_line(&tangent), vec![c64(0.0, 1.0)]);
+    let miss = Line::through(c64(0.0, 1.5), c64(1.0, 1.5));
+    assert!(unit.intersect_line(&miss).is_empty());
+    // Far away line nearly tangent to a small circle keeps both points accurate
+    let small = Circle::new(c64(1e6, 0.0), 1e-3);
+    let points = small.intersect_line(&Line::through(c64(0.0, 0.0), c64(1.0, 0.0)));
+    assert_close(points[0], c64(1e6 - 1e-3, 0.0), 1e-9);
+    assert_close(points[1], c64(1e6 + 1e-3, 0.0), 1e-9);
+}
+
+#[test]
+fn geometry_polygons() {
+    use crate::geometry::{convex_hull, point_in_polygon, signed_area, winding_number};
+    let triangle = [c64(0.0, 0.0), c64(4.0, 0.0), c64(0.0, 3.0)];
+    assert_eq!(signed_area(&triangle), 6.0);
+    let reversed: Vec<c64> = triangle.iter().rev().cloned().collect();
+    assert_eq!(signed_area(&reversed), -6.0);
+    assert!(point_in_polygon(c64(1.0, 1.0), &triangle));
+    assert!(point_in_polygon(c64(1.0, 1.0), &reversed));
+    assert!(!point_in_polygon(c64(3.0, 2.0), &triangle));
+    assert_eq!(winding_number(c64(1.0, 1.0), &triangle), 1);
+    assert_eq!(winding_number(c64(1.0, 1.0), &reversed), -1);
+    // Pentagram: the central pentagon is wound twice
+    let star: Vec<c64> = (0..5)
+        .map(|k| c64::from_polar(1.0, FRAC_PI_2 + k as f64 * 4.0 * std::f64::consts::PI / 5.0))
+        .collect();
+    assert_eq!(winding_number(c64(0.0, 0.0), &star), 2);
+    assert!(point_in_polygon(c64(0.0, 0.0), &star));
+    assert!(!point_in_polygon(c64(0.0, -0.9), &star));
+
+    let points: Vec<c64> = (0..200)
+        .map(|k| {
+            let t = k as f64;
+            c64((t * 1.7).sin() * (t * 0.3).cos(), (t * 2.3).cos() * 0.8)
+        })
+        .chain(vec![c64(2.0, 0.0), c64(0.0, 2.0), c64(1.0, 1.0)])
+        .collect();
+    let hull = convex_hull(&points);
+    assert!(hull.contains(&c64(2.0, 0.0)) && hull.contains(&c64(0.0, 2.0)));
+    // c64(1, 1) lies on the edge between them and is left out
+    assert!(!hull.contains(&c64(1.0, 1.0)));
+    assert!(signed_area(&hull) > 0.0);
+    let n = hull.len();
+    for k in 0..n {
+        let (a, b) = (hull[k], hull[(k + 1) % n]);
+        for &p in &points {
+            let side = (b - a).conj() * (p - a);
+            assert!(side.imag >= -1e-12);
+        }
+    }
+    let collinear = [c64(0.0, 0.0), c64(2.0, 2.0), c64(1.0, 1.0), c64(1.0, 1.0)];
+    assert_eq!(convex_hull(&collinear), vec![c64(0.0, 0.0), c64(2.0, 2.0)]);
+    assert_eq!(convex_hull(&[c64(1.0, 0.0)]), vec![c64(1.0, 0.0)]);
+}
+
+#[test]
+fn conformal_standard_maps() {
+    use crate::conformal::{cayley, disk_to_right_half_plane, ConformalMap, Joukowski, Strip};
+    fn check<M: ConformalMap<f64>>(map: &M, points: &[c64]) {
+        for &z in points {
+            let w = map.map(z);
+            assert_close(map.inverse_map(w), z, 1e-13);
+            let h = 1e-6;
+            let numeric = (map.map(z + c64(h, 0.0)) - map.map(z - c64(h, 0.0))) / c64(2.0 * h, 0.0);
+            assert_close(
+                map.derivative(z),
+                numeric,
+                1e-8 * map.derivative(z).norm().max(1.0),
+            );
+        }
+    }
+    let upper = [c64(0.3, 0.2), c64(-2.0, 5.0), c64(1.5, 0.01)];
+    let disk = [c64(0.3, 0.2), c64(-0.5, -0.5), c64(0.0, 0.95)];
+    let cayley = cayley();
+    check(&cayley, &upper);
+    assert!(upper.iter().all(|&z| cayley.map(z).norm() < 1.0));
+    let half_plane = disk_to_right_half_plane();
+    check(&half_plane, &disk);
+    assert!(disk.iter().all(|&z| half_plane.map(z).real > 0.0));
+    assert_close(half_plane.map(c64(0.0, 0.0)), c64(1.0, 0.0), 1e-15);
+
+    let joukowski = Joukowski::new(1.5);
+    check(&joukowski, &[c64(2.0, 0.5), c64(-1.0, 1.2), c64(0.1, -3.0)]);
+    // The exterior branch of the inverse, also just above and below the slit
+    assert!((joukowski.inverse_map(c64(1.0, 1e-9)).norm() - 1.5).abs() < 1e-8);
+    assert!(joukowski.inverse_map(c64(-2.0, -1e-3)).norm() >= 1.5);
+    let airfoil = joukowski.airfoil(c64(-0.15, 0.1), 64);
+    assert_close(airfoil[0], c64(3.0, 0.0), 1e-15);
+    assert!(airfoil.iter().all(|w| w.real <= 3.0 + 1e-12));
+    // Cambered airfoil: the upper surface lies above the lower one
+    assert!(crate::geometry::signed_area(&airfoil) > 0.0);
+
+    let strip = Strip::new(2.0);
+    check(&strip, &[c64(0.5, 0.5), c64(-3.0, 1.9), c64(4.0, 0.1)]);
+    assert!(strip.map(c64(-1.0, 1.0)).imag > 0.0);
+    let edge = strip.map(c64(10.0, 2.0));
+    assert!(edge.imag.abs() < 1e-15 * edge.norm());
+}
+
+#[test]
+fn schwarz_christoffel_polygons() {
+    use crate::conformal::{ConformalMap, SchwarzChristoffel};
+    let square = [
+        c64(1.0, -1.0),
+        c64(1.0, 1.0),
+        c64(-1.0, 1.0),
+        c64(-1.0, -1.0),
+    ];
+    let map = SchwarzChristoffel::new(&square, c64(0.0, 0.0)).unwrap();
+    // Symmetry puts the prevertices a quarter turn apart
+    let p = map.prevertices();
+    for k in 0..4 {
+        assert_close(p[(k + 1) % 4] / p[k], c64(0.0, 1.0), 1e-10);
+        assert_close(map.map(p[k]), square[k], 1e-10);
+    }
+    assert_close(map.map(c64(0.0, 0.0)), c64(0.0, 0.0), 1e-10);
+    // Map of an edge midpoint lies on that edge
+    let mid = (p[0] + p[1]).unscale((p[0] + p[1]).norm());
+    assert!((map.map(mid).real - 1.0).abs() < 1e-10);
+
+    let l_shape = [
+        c64(0.0, 0.0),
+        c64(2.0, 0.0),
+        c64(2.0, 1.0),
+        c64(1.0, 1.0),
+        c64(1.0, 2.0),
+        c64(0.0, 2.0),
+    ];
+    let map = SchwarzChristoffel::new(&l_shape, c64(0.5, 0.5)).unwrap();
+    for (&z, &w) in map.prevertices().iter().zip(&l_shape) {
+        assert!((z.norm() - 1.0).abs() < 1e-14);
+        assert_close(map.map(z), w, 1e-9);
+    }
+    assert_close(map.map(c64(0.0, 0.0)), c64(0.5, 0.5), 1e-10);
+    for &z in &[c64(0.3, 0.2), c64(-0.6, 0.1), c64(0.1, -0.9), c64(0.5, 0.5)] {
+        let w = map.map(z);
+        assert!(crate::geometry::point_in_polygon(w, &l_shape));
+        assert_close(map.inverse_map(w), z, 1e-10);
+        let h = 1e-6;
+        let numeric = (map.map(z + c64(0.0, h)) - map.map(z - c64(0.0, h))) / c64(0.0, 2.0 * h);
+        assert_close(map.derivative(z), numeric, 1e-7);
+    }
+    let triangle = [c64(0.0, 0.0), c64(3.0, 0.0), c64(0.0, 1.0)];
+    let map = SchwarzChristoffel::new(&triangle, c64(0.8, 0.3)).unwrap();
+    for (&z, &w) in map.prevertices().iter().zip(&triangle) {
+        assert_close(map.map(z), w, 1e-9);
+    }
+}
+
+#[test]
+fn contour_integrals_along_paths() {
+    use crate::contour::{Contour, Path};
+    use std::f64::consts::PI;
+    let segment = Contour::polyline(&[c64(0.0, 0.0), c64(1.0, 1.0)]);
+    let value = segment.integrate(|z| z * z, 1e-13);
+    assert_close(value, c64(1.0, 1.0).powi(3) / c64(3.0, 0.0), 1e-14);
+    // Path independence of an entire function along an open polyline
+    let points = [c64(-1.0, 0.5), c64(0.3, 2.0), c64(1.0, -1.0), c64(2.0, 0.0)];
+    let value = Contour::polyline(&points).integrate(|z| z.exp(), 1e-13);
+    assert_close(value, points[3].exp() - points[0].exp(), 1e-12);
+    // Closed contours: Cauchy's theorem and the winding of 1/z
+    let square = Contour::polygon(&[
+        c64(-1.0, -1.0),
+        c64(1.0, -1.0),
+        c64(1.0, 1.0),
+        c64(-1.0, 1.0),
+    ]);
+    assert!(square.integrate(|z| z.sin() * z.exp(), 1e-13).norm() < 1e-12);
+    assert_close(
+        square.integrate(|z| z.inv(), 1e-13),
+        c64(0.0, 2.0 * PI),
+        1e-12,
+    );
+    let half = Contour::new(vec![Path::Arc {
+        center: c64(0.0, 0.0),
+        radius: 2.0,
+        start_angle: 0.0,
+        end_angle: PI,
+    }]);
+    assert_close(half.integrate(|z| z.inv(), 1e-13), c64(0.0, PI), 1e-13);
+    assert_close(half.paths[0].end(), c64(-2.0, 0.0), 1e-15);
+    // Mixed contour: boundary of the upper half disk of radius 3 around 1/(z² + 1)
+    let upper = Contour::polyline(&[c64(-3.0, 0.0), c64(3.0, 0.0)]).then(Path::Arc {
+        center: c64(0.0, 0.0),
+        radius: 3.0,
+        start_angle: 0.0,
+        end_angle: PI,
+    });
+    let value = upper.integrate(|z| (z * z + c64(1.0, 0.0)).inv(), 1e-13);
+    assert_close(value, c64(PI, 0.0), 1e-12);
+    // Integrable endpoint singularity ∫₀¹ dx/√x = 2
+    let unit = Contour::polyline(&[c64(0.0, 0.0), c64(1.0, 0.0)]);
+    let value = unit.integrate(|z| z.sqrt().inv(), 1e-10);
+    assert_close(value, c64(2.0, 0.0), 1e-8);
+}
+
+#[test]
+fn contour_integrals_with_unreachable_tolerance() {
+    use crate::contour::Contour;
+    use std::cell::Cell;
+    use std::f64::consts::PI;
+    let circle = Contour::circle(c64(0.0, 0.0), 1.0);
+    let calls = Cell::new(0);
+    let counted = |z: c64| {
+        calls.set(calls.get() + 1);
+        z.inv().scale(1e6)
+    };
+    // Rounding noise of the panels is far above 1e-12
+    let value = circle.integrate(counted, 1e-12);
+    assert_close(value, c64(0.0, 2e6 * PI), 1e-13);
+    assert!(calls.get() < 1_000);
+    let value = circle.integrate(|z| z.exp() / z, 0.0);
+    assert_close(value, c64(0.0, 2.0 * PI), 1e-14);
+    // NaN panels cannot be refined, they end up in the result
+    calls.set(0);
+    let value = circle.integrate(
+        |z| {
+            calls.set(calls.get() + 1);
+            c64(f64::NAN, 0.0) * z
+        },
+        1e-10,
+    );
+    assert!(value.real.is_nan());
+    assert_eq!(calls.get(), 15);
+    // Integrands that never settle stop after a bounded number of panels
+    calls.set(0);
+    circle.integrate(
+        |z| {
+            calls.set(calls.get() + 1);
+            c64((z.real * 1e4).sin() * 1e-3 / (1e-8 + z.imag.abs()), 0.0)
+        },
+        0.0,
+    );
+    // 15 evaluations per panel, budget plus at most one pending panel per level
+    assert!(calls.get() <= 15 * (2_000 + 51));
+}
+
+#[test]
+fn residues_and_argument_principle() {
+    use crate::contour::{argument_principle, residue, Contour, Path};
+    let r = residue(|z: c64| z.exp() / (z * z), c64(0.0, 0.0), 0.5, 1e-13);
+    assert_close(r, c64(1.0, 0.0), 1e-12);
+    let r = residue(|z: c64| z.tan(), c64(FRAC_PI_2, 0.0), 0.3, 1e-13);
+    assert_close(r, c64(-1.0, 0.0), 1e-12);
+    let r = residue(
+        |z: c64| (z * z + c64(1.0, 0.0)).inv(),
+        c64(0.0, 1.0),
+        1.0,
+        1e-13,
+    );
+    assert_close(r, c64(0.0, -0.5), 1e-12);
+
+    let circle = Contour::circle(c64(0.0, 0.0), 2.0);
+    // Three zeros and a double pole inside, a zero and a pole outside
+    let f = |z: c64| {
+        (z - c64(1.0, 1.0)) * (z + c64(0.5, 0.0)) * (z - c64(0.0, -1.5)) * (z - c64(3.0, 0.0))
+            / ((z - c64(0.2, 0.3)).powi(2) * (z - c64(0.0, 4.0)))
+    };
+    assert_eq!(argument_principle(f, &circle), Some(1));
+    assert_eq!(argument_principle(|z: c64| z.powi(7), &circle), Some(7));
+    assert_eq!(argument_principle(|z: c64| z.exp(), &circle), Some(0));
+    assert_eq!(
+        argument_principle(|z: c64| z - c64(2.0, 0.0), &circle),
+        None
+    );
+    // Nyquist: counterclockwise D-contour around the right half-plane counts
+    // unstable closed-loop poles of 1 + K/(s + 1)³
+    let radius = 1e3;
+    let axis: Vec<c64> = (0..=400)
+        .map(|k| c64(0.0, radius * (1.0 - k as f64 / 200.0)))
+        .collect();
+    let d_contour = Contour::polyline(&axis).then(Path::Arc {
+        center: c64(0.0, 0.0),
+        radius,
+        start_angle: -FRAC_PI_2,
+        end_angle: FRAC_PI_2,
+    });
+    let closed_loop =
+        |k: f64| move |s: c64| c64(1.0, 0.0) + c64(k, 0.0) / (s + c64(1.0, 0.0)).powi(3);
+    assert_eq!(argument_principle(closed_loop(5.0), &d_contour), Some(0));
+    assert_eq!(argument_principle(closed_loop(10.0), &d_contour), Some(2));
+}
+
+#[test]
+fn complex_step_differentiation() {
+    use crate::differentiation::{
+        complex_step_derivative, complex_step_gradient, complex_step_jacobian,
+    };
+    // Squire and Trapp's test function, where finite differences lose half the digits
+    let f = |z: c64| z.exp() / (z.sin().powi(3) + z.cos().powi(3)).sqrt();
+    let x = 1.5;
+    let slope = complex_step_derivative(f, x);
+    let (s, c) = (x.sin(), x.cos());
+    let g = (s.powi(3) + c.powi(3)).sqrt();
+    let dg = 1.5 * (s * s * c - c * c * s) / g;
+    let exact = x.exp() / g - x.exp() * dg / (g * g);
+    assert!((slope - exact).abs() < 1e-15 * exact.abs());
+    assert_eq!(complex_step_derivative(|z: c64| z * z, 1e100), 2e100);
+
+    // f(x, y) = x²y + sin(y)
+    let f = |v: &[c64]| v[0] * v[0] * v[1] + v[1].sin();
+    let gradient = complex_step_gradient(f, &[3.0, 0.5]);
+    assert_eq!(gradient, vec![3.0, 9.0 + 0.5f64.cos()]);
+
+    // Polar to Cartesian coordinates
+    let polar = |v: &[c64]| vec![v[0] * v[1].cos(), v[0] * v[1].sin()];
+    let (r, theta): (f64, f64) = (2.0, 0.7);
+    let jacobian = complex_step_jacobian(polar, &[r, theta]);
+    let expected = [
+        [theta.cos(), -r * theta.sin()],
+        [theta.sin(), r * theta.cos()],
+    ];
+    for i in 0..2 {
+        for k in 0..2 {
+            assert!((jacobian[i][k] - expected[i][k]).abs() < 1e-15);
+        }
+    }
+}
+
+#[test]
+fn dual_number_wirtinger_derivatives() {
+    use crate::differentiation::Dual;
+    let z0 = c64(0.4, -1.3);
+    let z = Dual::variable(z0);
+    // Holomorphic composition: f(z) = sin(z)·e^(z²) / (1 + z)
+    let f = (z.sin() * (z * z).exp()) / (z + c64(1.0, 0.0));
+    let value = |w: c64| w.sin() * (w * w).exp() / (w + c64(1.0, 0.0));
+    let h = 1e-6;
+    let numeric = (value(z0 + c64(h, 0.0)) - value(z0 - c64(h, 0.0))) / c64(2.0 * h, 0.0);
+    assert_close(f.value, value(z0), 1e-15);
+    assert_close(f.dz, numeric, 1e-8 * numeric.norm());
+    assert_eq!(f.dzbar, c64(0.0, 0.0));
+    for g in [
+        z.ln(),
+        z.sqrt(),
+        z.tan(),
+        z.tanh(),
+        z.powi(-3),
+        z.powc(c64(0.5, 0.2)),
+        z.cosh() - z.sinh(),
+    ] {
+        assert_eq!(g.dzbar, c64(0.0, 0.0));
+    }
+    assert_close(z.powi(-3).dz, z0.powi(-4) * -3.0, 1e-14);
+    assert_close(z.ln().dz, z0.inv(), 1e-15);
+    assert_close((z.cosh() - z.sinh()).dz, -(-z0).exp(), 1e-14);
+    assert_close(z.powc(c64(2.0, 0.0)).dz, z0 * 2.0, 1e-14);
+
+    // Real loss L = |z - a|² + Re(z)³ has gradient 2(z - a) + 3x²
+    let a = c64(1.0, 1.0);
+    let loss =
+        (z - a).square_norm() + Dual::from(c64(0.5, 0.0)) * (z + z.conj()).powi(3) * c64(0.25, 0.0);
+    let expected = (z0 - a) * 2.0 + c64(3.0 * z0.real * z0.real, 0.0);
+    assert_close(loss.gradient(), expected, 1e-14);
+    // |z|, conjugation and non-holomorphic parts
+    let magnitude = z.norm();
+    assert_close(magnitude.value, c64(z0.norm(), 0.0), 1e-15);
+    assert_close(magnitude.gradient(), z0 / z0.norm(), 1e-15);
+    let conjugate = z.conj();
+    assert_eq!(conjugate.dz, c64(0.0, 0.0));
+    assert_eq!(conjugate.dzbar, c64(1.0, 0.0));
+    assert_eq!(Dual::constant(a).dz, c64(0.0, 0.0));
+}
+
+#[test]
+fn root_finding_solvers() {
+    use crate::roots::{halley, muller, newton, secant, RootConfig};
+    let config = RootConfig::default();
+    // z³ = 1 from the basin of e^(2πi/3)
+    let f = |z: c64| z * z * z - c64(1.0, 0.0);
+    let df = |z: c64| z * z * 3.0;
+    let d2f = |z: c64| z * 6.0;
+    let target = c64(-0.5, 3f64.sqrt() / 2.0);
+    let start = c64(-0.4, 1.0);
+    let by_newton = newton(f, df, start, &config);
+    let by_halley = halley(f, df, d2f, start, &config);
+    for result in [by_newton, by_halley] {
+        assert!(result.converged);
+        assert_close(result.root, target, 1e-15);
+        assert!(result.residual < 1e-14);
+    }
+    assert!(by_halley.iterations < by_newton.iterations);
+    let by_secant = secant(f, start, start * 1.01, &config);
+    assert!(by_secant.converged);
+    assert_close(by_secant.root, target, 1e-15);
+    let by_muller = muller(f, [start, start * 1.01, start * 0.99], &config);
+    assert!(by_muller.converged);
+    assert_close(by_muller.root, target, 1e-15);
+
+    // Dispersion relation of a damped mode: z = tanh(z)·(1 + i) near 1.5
+    let g = |z: c64| z - z.tanh() * c64(1.0, 1.0);
+    let result = secant(g, c64(1.0, 0.5), c64(1.5, 0.5), &config);
+    assert!(result.converged && result.residual < 1e-14);
+    let other = muller(g, [c64(1.0, 0.5), c64(1.5, 0.5), c64(1.2, 0.8)], &config);
+    assert_close(other.root, result.root, 1e-13);
+
+    // Real start keeps Newton on the real axis for z² + 1, so it cannot converge
+    let capped = RootConfig {
+        max_iterations: 25,
+        ..RootConfig::default()
+    };
+    let h = |z: c64| z * z + c64(1.0, 0.0);
+    let result = newton(h, |z: c64| z * 2.0, c64(0.3, 0.0), &capped);
+    assert!(!result.converged);
+    assert_eq!(result.iterations, 25);
+    assert_eq!(result.root.imag, 0.0);
+    // Zero derivative stops immediately
+    let result = newton(h, |z: c64| z * 2.0, c64(0.0, 0.0), &config);
+    assert!(!result.converged);
+    assert_eq!(result.iterations, 0);
+    assert_eq!(result.residual, 1.0);
+    // A residual tolerance ends the iteration early
+    let loose = RootConfig {
+        residual_tol: 1e-3,
+        ..RootConfig::default()
+    };
+    let result = newton(f, df, start, &loose);
+    assert!(result.converged && result.residual <= 1e-3);
+    assert!(result.iterations < by_newton.iterations);
+    // Exact roots stop without a step
+    let result = secant(h, c64(0.0, 1.0), c64(0.0, 1.0), &config);
+    assert!(result.converged);
+    assert_eq!(result.iterations, 0);
+}
+
+#[test]
+fn power_series_arithmetic() {
+    use crate::series::PowerSeries;
+    let n = 12;
+    let x = PowerSeries::<f64>::variable(n);
+    let one = PowerSeries::constant(c64(1.0, 0.0), n);
+    // f = (2 + i) + 3x - x² + ...
+    let f = PowerSeries::new(
+        (0..n)
+            .map(|k| {
+                c64(
+                    2.0 / (k + 1) as f64,
+                    if k == 0 { 1.0 } else { -0.3 * k as f64 },
+                )
+            })
+            .collect(),
+    );
+    let check = |a: &PowerSeries<f64>, b: &PowerSeries<f64>, tol: f64| {
+        assert_eq!(a.precision(), b.precision());
+        for (&p, &q) in a.coeffs().iter().zip(b.coeffs()) {
+            assert_close(p, q, tol);
+        }
+    };
+    check(&(&f * &f.inv().unwrap()), &one, 1e-13);
+    check(&f.exp().ln().unwrap(), &f, 1e-12);
+    check(&f.ln().unwrap().exp(), &f, 1e-12);
+    let root = f.sqrt().unwrap();
+    check(&(&root * &root), &f, 1e-13);
+    assert!(x.inv().is_none() && x.ln().is_none() && x.sqrt().is_none());
+    check(&(&(&f + &x) - &x), &f, 1e-15);
+    check(
+        &(-&f + f.clone()),
+        &PowerSeries::constant(c64(0.0, 0.0), n),
+        0.0,
+    );
+    // exp(ln(1 + x)) = 1 + x by composition
+    let log1p = (&one + &x).ln().unwrap();
+    check(
+        &one.exp().compose(&log1p).scale(c64(1.0, 0.0)),
+        &one.exp(),
+        0.0,
+    );
+    check(&x.exp().compose(&log1p), &(&one + &x), 1e-15);
+    // Reversion of x·eˣ is the Lambert W series Σ (-k)^(k-1)/k! xᵏ
+    let w = (&x * &x.exp()).reversion().unwrap();
+    let mut factorial = 1.0;
+    for k in 1..n {
+        factorial *= k as f64;
+        let expected = (-(k as f64)).powi(k as i32 - 1) / factorial;
+        assert_close(
+            w.coeffs()[k],
+            c64(expected, 0.0),
+            1e-12 * expected.abs().max(1.0),
+        );
+    }
+    check(&(&x * &x.exp()).compose(&w), &x, 1e-12);
+    assert!((&x * &x).reversion().is_none());
+    // Calculus and evaluation
+    let sine = PowerSeries::new(
+        (0..n)
+            .map(|k| match k % 4 {
+                1 => c64(1.0, 0.0),
+                3 => c64(-1.0, 0.0),
+                _ => c64(0.0, 0.0),
+            })
+            .map({
+                let mut factorial = 1.0;
+                let mut k = 0;
+                move |c| {
+                    if k > 0 {
+                        factorial *= k as f64;
+                    }
+                    k += 1;
+                    c / factorial
+                }
+            })
+            .collect(),
+    );
+    assert_close(sine.eval(c64(0.5, 0.2)), c64(0.5, 0.2).sin(), 1e-13);
+    assert_close(
+        sine.derivative().eval(c64(0.5, 0.2)),
+        c64(0.5, 0.2).cos(),
+        1e-12,
+    );
+    assert_eq!(sine.derivative().precision(), n - 1);
+    assert_eq!(sine.integral(c64(0.0, 0.0)).precision(), n + 1);
+    assert_eq!(sine.truncate(5).precision(), 5);
+}
+
+#[test]
+fn pade_and_laurent_series() {
+    use crate::series::{LaurentSeries, PowerSeries};
+    // ln(1 + x) has radius 1, its Padé approximants continue it to x = 3
+    let n = 21;
+    let log1p = PowerSeries::new(
+        (0..n)
+            .map(|k| {
+                if k == 0 {
+                    c64(0.0, 0.0)
+                } else {
+                    c64((-1f64).powi(k + 1) / k as f64, 0.0)
+                }
+            })
+            .collect(),
+    );
+    let pade = log1p.pade(10, 10).unwrap();
+    assert!((pade.eval(c64(3.0, 0.0)) - c64(4f64.ln(), 0.0)).norm() < 1e-7);
+    assert!((pade.eval(c64(-0.5, 2.0)) - c64(0.5, 2.0).ln()).norm() < 1e-7);
+    let exp = PowerSeries::<f64>::variable(6).exp();
+    let pade = exp.pade(2, 2).unwrap();
+    let scale = pade.denominator.coeffs()[0];
+    assert_close(scale, c64(1.0, 0.0), 0.0);
+    assert_close(pade.numerator.coeffs()[1], c64(0.5, 0.0), 1e-15);
+    assert_close(pade.denominator.coeffs()[2], c64(1.0 / 12.0, 0.0), 1e-15);
+    // [m/0] is the Taylor polynomial
+    let taylor = exp.pade(3, 0).unwrap();
+    assert_eq!(taylor.denominator.degree(), 0);
+    assert_close(taylor.numerator.coeffs()[3], c64(1.0 / 6.0, 0.0), 1e-16);
+    let zero_series = PowerSeries::new(vec![c64(1.0, 0.0), c64(0.0, 0.0), c64(0.0, 0.0)]);
+    assert!(zero_series.pade(1, 1).is_none());
+
+    // 1/sin(x) = 1/x + x/6 + 7x³/360 + ...
+    let x = PowerSeries::<f64>::variable(10);
+    let sine = LaurentSeries::from_power_series(PowerSeries::new(
+        (0..10)
+            .map(|k| match k {
+                1 => c64(1.0, 0.0),
+                3 => c64(-1.0 / 6.0, 0.0),
+                5 => c64(1.0 / 120.0, 0.0),
+                7 => c64(-1.0 / 5040.0, 0.0),
+                9 => c64(1.0 / 362_880.0, 0.0),
+                _ => c64(0.0, 0.0),
+            })
+            .collect(),
+    ));
+    let cosecant = sine.inv();
+    assert_eq!(cosecant.valuation(), -1);
+    assert_eq!(cosecant.order(), 8);
+    assert_close(cosecant.residue(), c64(1.0, 0.0), 1e-15);
+    assert_close(cosecant.coeff(1), c64(1.0 / 6.0, 0.0), 1e-15);
+    assert_close(cosecant.coeff(3), c64(7.0 / 360.0, 0.0), 1e-15);
+    assert_close(cosecant.coeff(-3), c64(0.0, 0.0), 0.0);
+    let z = c64(0.1, 0.05);
+    assert_close(cosecant.eval(z), z.sin().inv(), 1e-12);
+    // eˣ/x³ has residue 1/2, and dividing by x³ again shifts the valuation
+    let mut terms = vec![c64(0.0, 0.0); 10];
+    terms[0] = c64(1.0, 0.0);
+    let cube = LaurentSeries::new(3, terms);
+    let quotient = &LaurentSeries::from_power_series(x.exp()) / &cube;
+    assert_eq!(quotient.valuation(), -3);
+    assert_close(quotient.residue(), c64(0.5, 0.0), 1e-16);
+    // Sums align valuations and keep the smaller order
+    let sum = &cosecant + &quotient;
+    assert_eq!(sum.valuation(), -3);
+    assert_eq!(sum.order(), quotient.order().min(cosecant.order()));
+    assert_close(sum.residue(), c64(1.5, 0.0), 1e-15);
+    let difference = &sum - &quotient;
+    assert_close(difference.coeff(-1), c64(1.0, 0.0), 1e-15);
+    assert_close(difference.coeff(-3), c64(0.0, 0.0), 0.0);
+    let product = &cosecant * &sine;
+    assert_eq!(product.valuation(), 0);
+    assert_close(product.coeff(0), c64(1.0, 0.0), 1e-15);
+    assert_close(product.coeff(4), c64(0.0, 0.0), 1e-15);
+}
+
+#[test]
+fn ode_fixed_step_integrators() {
+    use crate::ode::{implicit_midpoint, rk4};
+    let i = c64(0.0, 1.0);
+    // y' = i·t·y has y = exp(i·t²/2)
+    let chirp = |t: f64, y: &[c64]| vec![i.scale(t) * y[0]];
+    let exact = |t: f64| c64(0.0, 0.5 * t * t).exp();
+    let coarse = rk4(chirp, 0.0, &[c64(1.0, 0.0)], 2.0, 50);
+    let fine = rk4(chirp, 0.0, &[c64(1.0, 0.0)], 2.0, 100);
+    assert!(coarse.finished);
+    assert_eq!(fine.times.len(), 101);
+    assert_eq!(fine.times[100], 2.0);
+    assert_eq!(fine.evaluations, 401);
+    let coarse_error = (coarse.final_state()[0] - exact(2.0)).norm();
+    let fine_error = (fine.final_state()[0] - exact(2.0)).norm();
+    assert!(fine_error < 1e-6);
+    // Fourth order
+    assert!((coarse_error / fine_error).log2() > 3.8);
+    // Hermite dense output between the nodes
+    assert_close(fine.interpolate(1.23)[0], exact(1.23), 1e-6);
+    assert_close(fine.interpolate(0.0)[0], c64(1.0, 0.0), 0.0);
+    assert_close(fine.interpolate(2.0)[0], fine.final_state()[0], 0.0);
+    assert_close(
+        fine.derivatives()[100][0],
+        chirp(2.0, fine.final_state())[0],
+        0.0,
+    );
+
+    // Discrete nonlinear Schrödinger equation on a ring of three sites
+    let dnls = |_t: f64, y: &[c64]| {
+        (0..3)
+            .map(|k| {
+                let hopping = y[(k + 1) % 3] + y[(k + 2) % 3];
+                -i * (hopping + y[k].scale(2.0 * y[k].square_norm()))
+            })
+            .collect::<Vec<c64>>()
+    };
+    let start = [c64(1.0, 0.0), c64(0.3, 0.2), c64(0.0, -0.5)];
+    let norm = |y: &[c64]| y.iter().map(|z| z.square_norm()).sum::<f64>();
+    let midpoint = implicit_midpoint(dnls, 0.0, &start, 20.0, 400);
+    let explicit = rk4(dnls, 0.0, &start, 20.0, 400);
+    assert!(midpoint.finished);
+    for state in &midpoint.states {
+        assert!((norm(state) - norm(&start)).abs() < 1e-13);
+    }
+    assert!((norm(explicit.final_state()) - norm(&start)).abs() > 1e-6);
+    // Second order on the chirp
+    let error = |steps| {
+        let solution = implicit_midpoint(chirp, 0.0, &[c64(1.0, 0.0)], 2.0, steps);
+        (solution.final_state()[0] - exact(2.0)).norm()
+    };
+    assert!((error(100) / error(200)).log2() > 1.9);
+    // The fixed-point iteration fails when h·L is too large
+    let stiff = implicit_midpoint(
+        |_t, y: &[c64]| vec![y[0].scale(-50.0)],
+        0.0,
+        &[c64(1.0, 0.0)],
+        1.0,
+        10,
+    );
+    assert!(!stiff.finished);
+    assert_eq!(stiff.times.len(), 1);
+}
+
+#[test]
+fn ode_adaptive_integrator_and_events() {
+    use crate::ode::{dormand_prince, OdeConfig};
+    let i = c64(0.0, 1.0);
+    // Coupled modes exchanging power, y' = -iKy with K = [[δ, κ], [κ, -δ]]
+    let (delta, kappa) = (0.3, 1.1);
+    let coupled = |_t: f64, y: &[c64]| {
+        vec![
+            -i * (y[0].scale(delta) + y[1].scale(kappa)),
+            -i * (y[0].scale(kappa) - y[1].scale(delta)),
+        ]
+    };
+    let omega = (delta * delta + kappa * kappa).sqrt();
+    let exact = |t: f64| {
+        let (c, s) = ((omega * t).cos(), (omega * t).sin() / omega);
+        [c64(c, -delta * s), c64(0.0, -kappa * s)]
+    };
+    let config = OdeConfig {
+        abs_tol: 1e-11,
+        rel_tol: 1e-11,
+        ..OdeConfig::default()
+    };
+    let start = [c64(1.0, 0.0), c64(0.0, 0.0)];
+    let solution = dormand_prince(coupled, 0.0, &start, 10.0, &config);
+    assert!(solution.finished);
+    assert_eq!(*solution.times.last().unwrap(), 10.0);
+    assert!(solution.times.windows(2).all(|w| w[0] < w[1]));
+    for (&t, state) in solution.times.iter().zip(&solution.states) {
+        let expected = exact(t);
+        assert_close(state[0], expected[0], 1e-9);
+        assert_close(state[1], expected[1], 1e-9);
+    }
+    let loose = dormand_prince(coupled, 0.0, &start, 10.0, &OdeConfig::default());
+    assert!(loose.times.len() < solution.times.len());
+    assert!((loose.final_state()[1] - exact(10.0)[1]).norm() < 1e-6);
+    // Between the large steps the dense output is as accurate as at them,
+    // where cubic Hermite interpolation would lose an order of magnitude
+    let error = |t: f64, y: &[c64]| (y[1] - exact(t)[1]).norm();
+    let at_steps = loose
+        .times
+        .iter()
+        .zip(&loose.states)
+        .fold(0.0, |acc: f64, (&t, y)| acc.max(error(t, y)));
+    for w in loose.times.windows(2) {
+        for fraction in [0.25, 0.5, 0.75] {
+            let t = w[0] + fraction * (w[1] - w[0]);
+            assert!(error(t, &loose.interpolate(t)) < 2.0 * at_steps);
+        }
+    }
+    // Dense output, fourth order in the accepted step like the steps themselves
+    for k in 0..100 {
+        let t = 0.1 * k as f64 + 0.0123;
+        let y = solution.interpolate(t);
+        assert_close(y[0], exact(t)[0], 1e-9);
+        assert_close(y[1], exact(t)[1], 1e-9);
+    }
+    // Full transfer is impossible with detuning. The power in the second
+    // mode is stationary at multiples of ωt = π/2, starting with t = 0.
+    let power = |_t: f64, y: &[c64]| (y[1].conj() * coupled(0.0, y)[1]).real;
+    let stationary = solution.events(power, 1e-12);
+    let spacing = FRAC_PI_2 / omega;
+    assert_eq!(stationary.len(), (10.0 / spacing) as usize + 1);
+    for (k, &t) in stationary.iter().enumerate() {
+        assert!((t - k as f64 * spacing).abs() < 1e-9);
+    }
+    let at_start = solution.events(|t, _y| t, 1e-12);
+    assert_eq!(at_start, vec![0.0]);
+    assert!(solution.events(|_t, _y| 1.0, 1e-12).is_empty());
+
+    // y' = y² blows up at t = 1 and the step size collapses before it
+    let blowup = dormand_prince(
+        |_t, y: &[c64]| vec![y[0] * y[0]],
+        0.0,
+        &[c64(1.0, 0.0)],
+        2.0,
+        &config,
+    );
+    assert!(!blowup.finished);
+    let end = *blowup.times.last().unwrap();
+    assert!(end < 1.0 && end > 0.99);
+    let limited = OdeConfig {
+        max_steps: 5,
+        ..config
+    };
+    let stopped = dormand_prince(coupled, 0.0, &start, 10.0, &limited);
+    assert!(!stopped.finished);
+    assert_eq!(stopped.times.len(), 6);
+    let capped = OdeConfig {
+        max_step: 0.01,
+        ..OdeConfig::default()
+    };
+    let solution = dormand_prince(coupled, 0.0, &start, 1.0, &capped);
+    assert!(solution
+        .times
+        .windows(2)
+        .all(|w| w[1] - w[0] <= 0.01 + 1e-15));
+}
@@ -0,0 +1,112 @@
+//! Window functions for spectral analysis
+//!
+//! Symmetric windows suit filter design, periodic windows (one sample of a
+//! longer symmetric window dropped) suit spectral analysis and STFT.
+//! ```rust
+//! use ancomplex::window::Window;
+//!
+//! let w: Vec<f64> = Window::Hann.periodic(4);
+//! assert_eq!(w.len(), 4);
+//! assert!((w[1] - 0.5).abs() < 1e-12 && (w[2] - 1.0).abs() < 1e-12);
+//! ```
+use crate::cast::from_f64;
+use num_traits::{Float, FloatConst};
+
+/// Standard window shapes
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Window<T> {
+    Rectangular,
+    Hann,
+    Hamming,
+    /// Four-term Blackman–Harris window
+    BlackmanHarris,
+    /// Flat top window with the coefficients used by scipy
+    FlatTop,
+    /// Kaiser window with shape parameter β
+    Kaiser(T),
+    /// Tukey (tapered cosine) window, the parameter is the fraction inside the taper
+    Tukey(T),
+}
+
+impl<T: Float + FloatConst> Window<T> {
+    /// Symmetric window of length `len`, w[n] = w[len - 1 - n]
+    pub fn symmetric(&self, len: usize) -> Vec<T> {
+        if len == 1 {
+            return vec![T::one()];
+        }
+        let span = from_f64::<T>(len as f64 - 1.0);
+        (0..len)
+            .map(|n| self.value(from_f64::<T>(n as f64) / span))
+            .collect()
+    }
+
+    /// Periodic window of length `len`, first `len` samples of symmetric window of `len + 1`
+    pub fn periodic(&self, len: usize) -> Vec<T> {
+        let mut window = self.symmetric(len + 1);
+        window.truncate(len);
+        window
+    }
+
+    /// Window shape at relative position `x` in [0, 1]
+    fn value(&self, x: T) -> T {
+        let two_pi = T::PI() + T::PI();
+        let cosine_sum = |coeffs: &[f64]| {
+            coeffs.iter().enumerate().fold(T::zero(), |acc, (k, &a)| {
+                let term = from_f64::<T>(a) * (two_pi * x * from_f64::<T>(k as f64)).cos();
+                if k % 2 == 0 {
+                    acc + term
+                } else {
+                    acc - term
+                }
+            })
+        };
+        match *self {
+            Window::Rectangular => T::one(),
+            Window::Hann => cosine_sum(&[0.5, 0.5]),
+            Window::Hamming => cosine_sum(&[0.54, 0.46]),
+            Window::BlackmanHarris => cosine_sum(&[0.35875, 0.48829, 0.14128, 0.01168]),
+            Window::FlatTop => cosine_sum(&[
+                0.215_578_95,
+                0.416_631_58,
+                0.277_263_158,
+                0.083_578_947,
+                0.006_947_368,
+            ]),
+            Window::Kaiser(beta) => {
+                let t = x + x - T::one();
+                bessel_i0(beta * (T::one() - t * t).max(T::zero()).sqrt()) / bessel_i0(beta)
+            }
+            Window::Tukey(alpha) => {
+                if alpha <= T::zero() {
+                    return T::one();
+                }
+                if alpha >= T::one() {
+                    return Window::Hann.value(x);
+                }
+                let half = alpha / from_f64::<T>(2.0);
+                let taper = |t: T| (T::one() + (T::PI() * t).cos()) / from_f64::<T>(2.0);
+                if x < half {
+                    taper(x / half - T::one())
+                } else if x > T::one() - half {
+                    taper((x - T::one()) / half + T::one())
+                } else {
+                    T::one()
+                }
+            }
+        }
+    }
+}
+
+/// Modified Bessel function of the first kind I₀(x)
+fn bessel_i0<T: Float>(x: T) -> T {
+    let quarter_x2 = x * x / from_f64::<T>(4.0);
+    let mut term = T::one();
+    let mut sum = T::one();
+    let mut k = T::one();
+    while term > T::epsilon() * sum {
+        term = term * quarter_x2 / (k * k);
+        sum = sum + term;
+        k = k + T::one();
+    }
+    sum
+}